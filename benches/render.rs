@@ -0,0 +1,50 @@
+// Times the converter on generated documentation, run with `cargo bench`.
+// Set BENCH_CLASSES to change the size of the input.
+use std::env;
+use std::fs::{self, File};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+const BINARY: &str = env!("CARGO_BIN_EXE_json_to_md");
+const RUNS: usize = 5;
+
+fn main() {
+    let classes = env::var("BENCH_CLASSES").unwrap_or_else(|_| "5000".to_string());
+    let directory = env::temp_dir().join(format!("arucas-md-bench-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+
+    let input = File::create(directory.join("AllDocs.json")).unwrap();
+    let status = Command::new(BINARY)
+        .args(["gen-sample", "--classes", &classes])
+        .stdout(input)
+        .status()
+        .unwrap();
+    assert!(status.success(), "Could not generate the sample");
+    println!("{} classes, {} runs each", classes, RUNS);
+
+    bench(&directory, "render", &[]);
+    bench(&directory, "render --jobs 1", &["--jobs", "1"]);
+    bench(&directory, "render --anchors", &["--anchors"]);
+    bench(&directory, "render --format-examples", &["--format-examples"]);
+    bench(&directory, "lint", &["lint", "--json"]);
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+fn bench(directory: &std::path::Path, name: &str, args: &[&str]) {
+    let mut times: Vec<Duration> = (0..RUNS).map(|_| {
+        let start = Instant::now();
+        let status = Command::new(BINARY)
+            .args(args)
+            .current_dir(directory)
+            .stdout(Stdio::null())
+            .status()
+            .unwrap();
+        let elapsed = start.elapsed();
+        assert!(status.success() || args.contains(&"lint"), "'{}' failed", name);
+        elapsed
+    }).collect();
+    times.sort();
+
+    println!("{:<28} median {:>10.2?}  min {:>10.2?}  max {:>10.2?}", name, times[RUNS / 2], times[0], times[RUNS - 1]);
+}
@@ -0,0 +1,82 @@
+// Generates include/arucas_md.h, the C header of the functions in src/ffi.rs
+use std::fs;
+
+const SOURCE: &str = "src/ffi.rs";
+const HEADER: &str = "include/arucas_md.h";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", SOURCE);
+    let source = fs::read_to_string(SOURCE).expect("Could not read the FFI source");
+
+    let mut header = String::from(
+        "// Generated from src/ffi.rs by build.rs, do not edit\n\
+        #ifndef ARUCAS_MD_H\n#define ARUCAS_MD_H\n\n#include <stddef.h>\n\n\
+        #ifdef __cplusplus\nextern \"C\" {\n#endif\n\n\
+        typedef struct ArucasMdDocument ArucasMdDocument;\n\n"
+    );
+
+    let mut docs: Vec<&str> = Vec::new();
+    for line in source.lines() {
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc.trim());
+            continue;
+        }
+        if let Some(signature) = line.strip_prefix("pub unsafe extern \"C\" fn ").or_else(|| line.strip_prefix("pub extern \"C\" fn ")) {
+            // Safety sections are for Rust callers, the first paragraph describes the function
+            for doc in docs.iter().take_while(|doc| !doc.is_empty()) {
+                header.push_str(&format!("// {}\n", doc));
+            }
+            header.push_str(&declaration(signature));
+            header.push('\n');
+        }
+        if !line.starts_with("#[") {
+            docs.clear();
+        }
+    }
+
+    header.push_str("#ifdef __cplusplus\n}\n#endif\n\n#endif\n");
+    if fs::read_to_string(HEADER).ok().as_deref() != Some(header.as_str()) {
+        fs::create_dir_all("include").and_then(|_| fs::write(HEADER, header)).expect("Could not write the C header");
+    }
+}
+
+// `name(a: T, b: U) -> R {` as `R name(T a, U b);`
+fn declaration(signature: &str) -> String {
+    let (name, rest) = signature.split_once('(').unwrap();
+    let (params, rest) = rest.split_once(')').unwrap();
+    let returns = match rest.trim().trim_end_matches('{').trim().strip_prefix("->") {
+        Some(returns) => c_type(returns.trim()),
+        None => "void".to_string()
+    };
+
+    let params: Vec<String> = params.split(", ").filter(|param| !param.is_empty()).map(|param| {
+        let (name, type_name) = param.split_once(": ").unwrap();
+        named(&c_type(type_name), name)
+    }).collect();
+    let params = if params.is_empty() { "void".to_string() } else { params.join(", ") };
+    format!("{}({});\n", named(&returns, name), params)
+}
+
+// `char *name` but `size_t name`
+fn named(type_name: &str, name: &str) -> String {
+    if type_name.ends_with('*') { format!("{}{}", type_name, name) } else { format!("{} {}", type_name, name) }
+}
+
+fn c_type(type_name: &str) -> String {
+    if let Some(pointee) = type_name.strip_prefix("*const ") {
+        return format!("const {}", pointer(&c_type(pointee)));
+    }
+    if let Some(pointee) = type_name.strip_prefix("*mut ") {
+        return pointer(&c_type(pointee));
+    }
+    match type_name {
+        "c_char" => "char",
+        "usize" => "size_t",
+        "Document" => "ArucasMdDocument",
+        other => panic!("No C type for '{}' in {}", other, SOURCE)
+    }.to_string()
+}
+
+fn pointer(type_name: &str) -> String {
+    if type_name.ends_with('*') { format!("{}*", type_name) } else { format!("{} *", type_name) }
+}
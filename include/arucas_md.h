@@ -0,0 +1,38 @@
+// Generated from src/ffi.rs by build.rs, do not edit
+#ifndef ARUCAS_MD_H
+#define ARUCAS_MD_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct ArucasMdDocument ArucasMdDocument;
+
+// Parses documentation given as JSON with the options of `renderClasses` in the WebAssembly API,
+// `options` may be null. Returns null if the document is invalid and, if `error` isn't null, points
+// it at a message to be freed with `arucas_md_free_string`.
+ArucasMdDocument *arucas_md_parse(const char *json, const char *options, char **error);
+
+// Renders the markdown of Classes.md, null-terminated with its length, not counting the
+// terminator, written to `length`. Free it with `arucas_md_free_buffer`.
+char *arucas_md_render_classes(const ArucasMdDocument *document, size_t *length);
+
+// Renders the markdown of Extensions.md, as `arucas_md_render_classes`.
+char *arucas_md_render_extensions(const ArucasMdDocument *document, size_t *length);
+
+// Frees markdown returned by a render function, given the length it was returned with.
+void arucas_md_free_buffer(char *buffer, size_t length);
+
+// Frees an error message from `arucas_md_parse`.
+void arucas_md_free_string(char *string);
+
+// Frees a parsed document.
+void arucas_md_free(ArucasMdDocument *document);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,52 @@
+use std::path::Path;
+use serde_json::{json, Value};
+use crate::diff;
+use crate::doc_parser::DocParser;
+use crate::output::write_file;
+use crate::snapshot;
+
+pub const FILE: &str = "algolia.json";
+
+// Writes DocSearch records for every documented item: the class (or extension) is
+// lvl0, the section lvl1 and the item itself lvl2. URLs point into the pages of the
+// `html` format under the base URL. Returns the number of records.
+pub fn write(parser: &DocParser, path: &Path, base_url: &str) -> Result<usize, String> {
+    let locale = &parser.options.locale;
+    let base_url = base_url.trim_end_matches('/');
+
+    let records: Vec<Value> = diff::items(parser).into_iter().map(|(key, item)| {
+        let section = match item.kind {
+            "class" => None,
+            "static member" => Some(locale.static_members.as_str()),
+            "member" => Some(locale.members.as_str()),
+            "constructor" => Some(locale.constructors.as_str()),
+            "static method" => Some(locale.static_methods.as_str()),
+            "method" => Some(locale.methods.as_str()),
+            _ => Some("Functions")
+        };
+        let directory = if item.kind == "function" { "extensions" } else { "classes" };
+        let page = format!("{}/{}/{}.html", base_url, directory, parser.file_name(&item.owner));
+        let anchor = DocParser::anchor_id(&item.anchor);
+
+        json!({
+            "objectID": snapshot::hash(&key),
+            "url": format!("{}#{}", page, anchor),
+            "url_without_anchor": page,
+            "anchor": anchor,
+            "type": if section.is_some() { "lvl2" } else { "lvl0" },
+            "hierarchy": {
+                "lvl0": item.owner,
+                "lvl1": section,
+                "lvl2": section.map(|_| parser.signature(&item.signature)),
+                "lvl3": null,
+                "lvl4": null,
+                "lvl5": null,
+                "lvl6": null
+            },
+            "content": item.desc
+        })
+    }).collect();
+
+    write_file(path, &(serde_json::to_string_pretty(&records).unwrap() + "\n"))?;
+    Ok(records.len())
+}
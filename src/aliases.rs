@@ -0,0 +1,19 @@
+use crate::doc_parser::DocParser;
+
+pub const FILE: &str = "TypeAliases.md";
+
+// TypeAliases.md: a table of every alias with the type it stands for
+pub fn aliases(parser: &DocParser) -> String {
+    let mut md = String::from("# Type aliases\n\n");
+    md.push_str("| Alias | Type | Description |\n");
+    md.push_str("| --- | --- | --- |\n");
+    for alias in parser.type_aliases() {
+        md.push_str(&format!(
+            "| `{}` | {} | {} |\n",
+            alias.name,
+            parser.linked_type(&alias.type_name, "", true).replace('|', "\\|"),
+            alias.desc.join(" ").replace('|', "\\|")
+        ));
+    }
+    md
+}
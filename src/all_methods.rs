@@ -0,0 +1,39 @@
+use std::collections::BTreeMap;
+use crate::diff::{self, Item};
+use crate::doc_parser::DocParser;
+
+// Where an item is rendered, given whether it belongs to an extension, its owner and the item
+pub type Link<'a> = &'a dyn Fn(bool, &str, &Item) -> String;
+
+// AllMethods.md: every method and extension function sorted by name and grouped by
+// first letter, for when the name is known but not the class it belongs to
+pub fn all_methods(parser: &DocParser, link: Link) -> String {
+    let mut letters: BTreeMap<char, Vec<(String, Item)>> = BTreeMap::new();
+    for item in diff::items(parser).into_values() {
+        if !matches!(item.kind, "method" | "static method" | "function") {
+            continue;
+        }
+        let call = &item.signature[..item.signature.find('(').unwrap_or(item.signature.len())];
+        let name = call.rsplit('.').next().unwrap_or(call).to_string();
+        let letter = name.chars().next().map_or('#', |c| c.to_ascii_uppercase());
+        letters.entry(letter).or_default().push((name, item));
+    }
+
+    let mut md = String::from("# All Methods\n");
+    for (letter, mut items) in letters {
+        items.sort_by(|(a, a_item), (b, b_item)| {
+            (a.to_lowercase(), &a_item.owner, &a_item.signature).cmp(&(b.to_lowercase(), &b_item.owner, &b_item.signature))
+        });
+
+        md.push_str(&format!("\n## {}\n\n", letter));
+        for (_, item) in items {
+            let extension = item.kind == "function";
+            md.push_str(&format!(
+                "- [`{}`]({}){} - {} of `{}`\n",
+                parser.signature(&item.signature), link(extension, &item.owner, &item), parser.badges(item.deprecated, item.experimental, false),
+                item.kind, item.owner
+            ));
+        }
+    }
+    md
+}
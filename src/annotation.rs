@@ -0,0 +1,19 @@
+// GitHub Actions workflow commands, shown inline on pull requests
+pub fn annotation(level: &str, file: &str, title: Option<&str>, message: &str) -> String {
+    let mut line = format!("::{} file={}", level, escape_property(file));
+    if let Some(title) = title {
+        line.push_str(",title=");
+        line.push_str(&escape_property(title));
+    }
+    line.push_str("::");
+    line.push_str(&escape_data(message));
+    line
+}
+
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_property(property: &str) -> String {
+    escape_data(property).replace(':', "%3A").replace(',', "%2C")
+}
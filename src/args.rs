@@ -0,0 +1,579 @@
+use std::collections::BTreeMap;
+use std::{env, fs};
+use std::str::FromStr;
+use serde_json::Value;
+use json_to_md::config::Config;
+use json_to_md::doc_parser::InstanceNames;
+use json_to_md::pdf;
+
+#[derive(Default, PartialEq)]
+pub enum Command {
+    #[default]
+    Generate,
+    Schema,
+    TestExamples,
+    Lint,
+    Summary,
+    Changelog,
+    GenSample,
+    Scaffold,
+    Publish,
+    Feed,
+    Show,
+    Search,
+    Browse,
+    Completions,
+    Man
+}
+
+// A command as described by shell completions and the man page
+pub struct CommandHelp {
+    pub name: &'static str,
+    pub value: Option<&'static str>,
+    pub help: &'static str
+}
+
+// A flag as described by shell completions and the man page. A value of `<path>` or `<dir>`
+// completes files, `choices` are the values it takes if they are fixed.
+pub struct Flag {
+    pub names: &'static [&'static str],
+    pub value: Option<&'static str>,
+    pub choices: &'static [&'static str],
+    pub help: &'static str
+}
+
+impl Flag {
+    const fn new(name: &'static [&'static str], value: Option<&'static str>, help: &'static str) -> Flag {
+        Flag { names: name, value, choices: &[], help }
+    }
+
+    const fn choice(name: &'static [&'static str], value: &'static str, choices: &'static [&'static str], help: &'static str) -> Flag {
+        Flag { names: name, value: Some(value), choices, help }
+    }
+
+    // Whether any of its names are among the arguments
+    fn is_in(&self, args: &[String]) -> bool {
+        self.names.iter().any(|name| args.iter().any(|arg| arg == name))
+    }
+}
+
+pub const ENV_PREFIX: &str = "ARUCAS_MD_";
+
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish", "powershell"];
+
+// Every command `parse` knows, kept in step with it
+pub const COMMANDS: &[CommandHelp] = &[
+    CommandHelp { name: "schema", value: None, help: "Prints the JSON Schema of the input" },
+    CommandHelp { name: "test-examples", value: None, help: "Runs every example through the interpreter given with --interpreter" },
+    CommandHelp { name: "lint", value: None, help: "Checks the documentation for problems" },
+    CommandHelp { name: "summary", value: None, help: "Prints a markdown summary of what changed since the last recorded snapshot" },
+    CommandHelp { name: "changelog", value: None, help: "Adds a section describing what changed to the changelog" },
+    CommandHelp { name: "feed", value: None, help: "Records the documentation and writes an Atom feed of the changes" },
+    CommandHelp { name: "show", value: Some("<Class.name>"), help: "Prints the documentation of a class, member, method or function" },
+    CommandHelp { name: "search", value: Some("<query>"), help: "Lists the items whose name or description contain the query" },
+    CommandHelp { name: "browse", value: None, help: "Opens an interactive browser of the documentation in the terminal" },
+    CommandHelp { name: "gen-sample", value: None, help: "Prints generated documentation with --classes classes" },
+    CommandHelp { name: "scaffold", value: None, help: "Prints a small example AllDocs.json" },
+    CommandHelp { name: "publish", value: None, help: "Regenerates the pages of the wiki repository given with --wiki-repo and pushes them" },
+    CommandHelp { name: "completions", value: Some("<shell>"), help: "Prints the completion script of bash, zsh, fish or powershell" }
+];
+
+// Every flag `parse` knows, kept in step with it
+pub const FLAGS: &[Flag] = &[
+    Flag::new(&["--input"], Some("<path>"), "Reads the documentation from the file instead of AllDocs.json"),
+    Flag::choice(&["--input-format"], "<format>", &["json", "yaml"], "Format of the input, by default going by its extension"),
+    Flag::choice(&["--format"], "<name>", Format::NAMES, "What to generate, markdown by default"),
+    Flag::new(&["--plugin"], Some("<command>"), "Generates the output with a program of its own"),
+    Flag::new(&["--split"], None, "Writes every class and extension to a file of its own"),
+    Flag::new(&["--metadata"], None, "Ends markdown pages with a comment recording how they were generated"),
+    Flag::new(&["--metadata-timestamp"], None, "Also records when pages were generated in the metadata comment"),
+    Flag::new(&["--prune"], None, "Deletes split pages an earlier run wrote for classes that are gone"),
+    Flag::new(&["--out"], Some("<template>"), "Path of split pages, such as docs/{kind}/{category}/{name}.md"),
+    Flag::new(&["--stream"], None, "Reads and renders one class at a time"),
+    Flag::new(&["--mermaid"], None, "Embeds a Mermaid class diagram at the top of Classes.md"),
+    Flag::new(&["--mermaid-file"], Some("<path>"), "Writes the Mermaid class diagram to a file"),
+    Flag::new(&["--dot-file"], Some("<path>"), "Writes a Graphviz graph of how classes use each other"),
+    Flag::new(&["--include"], Some("<names>"), "Comma separated classes to document, all others are skipped"),
+    Flag::new(&["--exclude"], Some("<names>"), "Comma separated classes to skip"),
+    Flag::new(&["--stable"], None, "Leaves out experimental classes and functions"),
+    Flag::new(&["--no-deprecated"], None, "Leaves out deprecated functions"),
+    Flag::new(&["--deprecated-only"], None, "Keeps only deprecated functions"),
+    Flag::new(&["--lenient"], None, "Skips validation, leaving out malformed entries"),
+    Flag::new(&["--strict"], None, "Validation also fails on unknown fields"),
+    Flag::new(&["--anchors"], None, "Adds a unique anchor before every heading"),
+    Flag::new(&["--anchor-key"], Some("<template>"), "Derives anchors from the template, such as {class}-{name}-{arity}"),
+    Flag::choice(&["--instance-names"], "<style>", &["brackets", "class", "variable"], "How the instance of instance methods and members is written"),
+    Flag::new(&["--lowercase-files"], None, "Names the pages of classes and extensions in lowercase"),
+    Flag::choice(&["--line-endings"], "<endings>", &["lf", "crlf"], "Line endings of the markdown output"),
+    Flag::new(&["--max-blank-lines"], Some("<n>"), "Most blank lines in a row in the markdown output"),
+    Flag::new(&["--reflow"], Some("<width>"), "Joins descriptions into paragraphs wrapped at the width, 0 for no wrapping"),
+    Flag::new(&["--void-types"], Some("<names>"), "Comma separated return types that mean nothing is returned"),
+    Flag::new(&["--accessors"], None, "Documents getX and setX method pairs together as a property"),
+    Flag::choice(&["--example-output"], "<style>", &["block", "comment"], "Where the output of an example is written"),
+    Flag::new(&["--variants"], Some("<names>"), "Comma separated example variants to keep"),
+    Flag::new(&["--variant-tabs"], None, "Shows the variants of an example as tabs in the html format"),
+    Flag::new(&["--group-members"], None, "Lists assignable members before read-only ones"),
+    Flag::new(&["--link-types"], None, "Links every type that is a documented class to its page"),
+    Flag::new(&["--check-links"], None, "Checks that every link between the pages written leads somewhere"),
+    Flag::new(&["--report"], Some("<path>"), "Writes a JSON report of the run"),
+    Flag::new(&["--min-coverage"], Some("<percent>"), "Fails with exit code 3 below this percentage of complete items"),
+    Flag::new(&["--max-missing-descriptions"], Some("<n>"), "Fails with exit code 3 if more items are missing a description"),
+    Flag::new(&["--max-missing-examples"], Some("<n>"), "Fails with exit code 3 if more items are missing an example"),
+    Flag::new(&["-v", "--verbose"], None, "Also reports debug details, twice or -vv for every item rendered"),
+    Flag::new(&["-vv"], None, "Reports every item as it is rendered"),
+    Flag::new(&["-q", "--quiet"], None, "Only reports errors"),
+    Flag::new(&["--log-json"], None, "Writes what is reported on stderr as JSON lines"),
+    Flag::new(&["--no-progress"], None, "Leaves out progress bars"),
+    Flag::choice(&["--fail-on"], "<level>", &["warnings", "errors", "never"], "What fails the run with its exit code, errors by default"),
+    Flag::new(&["--overlay"], Some("<path>"), "Merges a JSON or TOML overlay over the input"),
+    Flag::new(&["--fragments"], Some("<dir>"), "Merges every TOML file in the directory into the input"),
+    Flag::new(&["--locale"], Some("<name>"), "Uses the translation locales/<name>.toml for the fixed strings"),
+    Flag::new(&["--languages"], Some("<codes>"), "Comma separated languages to generate, each in its own directory"),
+    Flag::new(&["--default-language"], Some("<code>"), "Language used for untranslated entries, en by default"),
+    Flag::new(&["--format-examples"], None, "Normalises the indentation of examples"),
+    Flag::new(&["--example-indent"], Some("<n>"), "Normalises the indentation of examples to n spaces per level"),
+    Flag::new(&["--example-max-width"], Some("<n>"), "Fails the run when example lines are longer than n characters"),
+    Flag::new(&["--format-annotations"], None, "Prints problems as GitHub Actions annotations"),
+    Flag::new(&["--archive"], None, "Records the documentation in the .arucas-md state directory"),
+    Flag::new(&["--keep-snapshots"], Some("<n>"), "Deletes all but the newest n recorded snapshots"),
+    Flag::new(&["--jobs"], Some("<n>"), "Number of threads rendering classes and extensions"),
+    Flag::new(&["--emit-json"], Some("<path>"), "Writes the document the output was generated from"),
+    Flag::new(&["--front-matter"], None, "Starts every markdown page with front matter"),
+    Flag::new(&["--front-matter-template"], Some("<path>"), "Uses the front matter in the file"),
+    Flag::new(&["--glossary"], None, "Also writes Glossary.md"),
+    Flag::new(&["--contributors"], None, "Also writes Contributors.md"),
+    Flag::new(&["--all-methods"], None, "Also writes AllMethods.md"),
+    Flag::new(&["--sources"], Some("<path>"), "Reads a file mapping names to their Java source"),
+    Flag::new(&["--source-url"], Some("<url>"), "Base URL sources are relative to"),
+    Flag::new(&["--config"], Some("<path>"), "Reads settings from the config file instead of arucas-md.toml"),
+    Flag::new(&["--theme"], Some("<path>"), "Theme of the html format, light, dark or a stylesheet"),
+    Flag::new(&["--base-url"], Some("<url>"), "Where the html format is hosted"),
+    Flag::new(&["--width"], Some("<n>"), "Columns the text format is wrapped to"),
+    Flag::new(&["--notion-page"], Some("<id>"), "Creates the pages of the notion format under the Notion page"),
+    Flag::choice(&["--pdf-engine"], "<engine>", pdf::ENGINES, "Program printing the pdf format, the first installed by default"),
+    Flag::new(&["--interpreter"], Some("<command>"), "Command running examples for test-examples"),
+    Flag::new(&["--json"], None, "Prints lint problems as JSON"),
+    Flag::new(&["--old"], Some("<path>"), "Documentation summary compares against"),
+    Flag::new(&["--changelog"], Some("<path>"), "Changelog the changelog command adds to"),
+    Flag::new(&["--release"], Some("<version>"), "Version of the changelog section"),
+    Flag::new(&["--snapshot"], Some("<path>"), "Where the changelog command records the documentation"),
+    Flag::new(&["--feed"], Some("<path>"), "Where the feed command writes the feed"),
+    Flag::new(&["--kind"], Some("<kind>"), "Kind of items search lists"),
+    Flag::new(&["--class"], Some("<name>"), "Class whose items search lists"),
+    Flag::new(&["--classes"], Some("<n>"), "Number of classes gen-sample generates"),
+    Flag::new(&["--wiki-repo"], Some("<git-url>"), "Wiki repository publish pushes to"),
+    Flag::new(&["--message"], Some("<template>"), "Commit message of publish"),
+    Flag::new(&["--dry-run"], None, "Lists what publish would commit without committing"),
+    Flag::new(&["--generate-man"], None, "Prints the man page of the tool")
+];
+
+// What fails the run, chosen with `--fail-on`
+#[derive(Clone, Copy, PartialEq)]
+pub enum FailOn {
+    Warnings,
+    Errors,
+    // Only failures that keep the run from finishing
+    Never
+}
+
+// What the documentation is rendered as
+#[derive(Default, PartialEq, Clone, Copy)]
+pub enum Format {
+    #[default]
+    Markdown,
+    Docset,
+    GitBook,
+    Confluence,
+    BBCode,
+    Discord,
+    DiscordEmbeds,
+    Obsidian,
+    Text,
+    Html,
+    Algolia,
+    Notion,
+    // FullReference.md, or FullReference.html
+    Reference,
+    ReferenceHtml,
+    // FullReference.pdf, printed by a program such as weasyprint
+    Pdf,
+    // A program of its own, chosen with `--plugin`
+    Plugin
+}
+
+impl Format {
+    pub const NAMES: &[&str] = &["markdown", "docset", "gitbook", "confluence", "bbcode", "discord", "discord-embeds", "obsidian", "text", "html", "algolia", "notion", "reference", "reference-html", "pdf"];
+
+    fn parse(name: &str) -> Result<Format, String> {
+        match name {
+            "markdown" => Ok(Format::Markdown),
+            "docset" => Ok(Format::Docset),
+            "gitbook" => Ok(Format::GitBook),
+            "confluence" => Ok(Format::Confluence),
+            "bbcode" => Ok(Format::BBCode),
+            "discord" => Ok(Format::Discord),
+            "discord-embeds" => Ok(Format::DiscordEmbeds),
+            "obsidian" => Ok(Format::Obsidian),
+            "text" => Ok(Format::Text),
+            "html" => Ok(Format::Html),
+            "algolia" => Ok(Format::Algolia),
+            "notion" => Ok(Format::Notion),
+            "reference" => Ok(Format::Reference),
+            "reference-html" => Ok(Format::ReferenceHtml),
+            "pdf" => Ok(Format::Pdf),
+            _ => Err(format!("Unknown format '{}', expected one of {}", name, Format::NAMES.join(", ")))
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Args {
+    pub command: Command,
+    pub mermaid: bool,
+    pub mermaid_file: Option<String>,
+    pub dot_file: Option<String>,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub stable: bool,
+    // Only deprecated functions are kept if true, none if false
+    pub deprecated: Option<bool>,
+    pub link_types: bool,
+    pub crlf: bool,
+    pub max_blank_lines: Option<usize>,
+    pub reflow: Option<usize>,
+    pub void_types: Option<Vec<String>>,
+    pub accessors: bool,
+    pub output_comments: bool,
+    pub variants: Option<Vec<String>>,
+    pub variant_tabs: bool,
+    pub group_members: bool,
+    pub anchor_key: Option<String>,
+    pub instance_names: InstanceNames,
+    pub lowercase_files: bool,
+    pub check_links: bool,
+    pub report: Option<String>,
+    pub min_coverage: Option<f64>,
+    pub max_missing_descriptions: Option<usize>,
+    pub max_missing_examples: Option<usize>,
+    pub plugin: Option<String>,
+    pub wiki_repo: Option<String>,
+    pub message: Option<String>,
+    pub dry_run: bool,
+    pub notion_page: Option<String>,
+    pub pdf_engine: Option<String>,
+    pub lenient: bool,
+    pub strict: bool,
+    pub anchors: bool,
+    // How many times `-v` was given
+    pub verbose: usize,
+    pub quiet: bool,
+    pub log_json: bool,
+    pub no_progress: bool,
+    pub fail_on: Option<FailOn>,
+    pub out: Option<String>,
+    pub prune: bool,
+    pub metadata: bool,
+    pub metadata_timestamp: bool,
+    pub shell: Option<String>,
+    pub overlays: Vec<String>,
+    pub locale: Option<String>,
+    pub languages: Vec<String>,
+    pub default_language: Option<String>,
+    pub interpreter: Option<String>,
+    pub example_indent: Option<usize>,
+    pub example_max_width: Option<usize>,
+    pub config: Option<String>,
+    pub json: bool,
+    pub annotations: bool,
+    pub old: Option<String>,
+    pub changelog: Option<String>,
+    pub snapshot: Option<String>,
+    pub release: Option<String>,
+    pub archive: bool,
+    pub keep_snapshots: Option<usize>,
+    pub jobs: Option<usize>,
+    pub split: bool,
+    pub classes: Option<usize>,
+    pub stream: bool,
+    pub input: Option<String>,
+    pub input_format: Option<String>,
+    pub fragments: Vec<String>,
+    pub emit_json: Option<String>,
+    pub format: Format,
+    pub feed: Option<String>,
+    pub front_matter: bool,
+    pub front_matter_template: Option<String>,
+    pub width: Option<usize>,
+    pub query: Option<String>,
+    pub kind: Option<String>,
+    pub class: Option<String>,
+    pub theme: Option<String>,
+    pub base_url: Option<String>,
+    pub glossary: bool,
+    pub contributors: bool,
+    pub all_methods: bool,
+    pub sources: Option<String>,
+    pub source_url: Option<String>
+}
+
+impl Args {
+    pub fn parse(mut iter: impl Iterator<Item = String>) -> Result<Args, String> {
+        let mut args = Args::default();
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "schema" => args.command = Command::Schema,
+                "test-examples" => args.command = Command::TestExamples,
+                "lint" => args.command = Command::Lint,
+                "summary" => args.command = Command::Summary,
+                "changelog" => args.command = Command::Changelog,
+                "gen-sample" => args.command = Command::GenSample,
+                "scaffold" => args.command = Command::Scaffold,
+                "publish" => args.command = Command::Publish,
+                "feed" => args.command = Command::Feed,
+                "show" => {
+                    args.command = Command::Show;
+                    args.query = Some(Args::value(&mut iter, &arg)?);
+                }
+                "browse" => args.command = Command::Browse,
+                "completions" => {
+                    args.command = Command::Completions;
+                    let shell = Args::value(&mut iter, &arg)?;
+                    if !SHELLS.contains(&shell.as_str()) {
+                        return Err(format!("Unknown shell '{}', expected one of {}", shell, SHELLS.join(", ")));
+                    }
+                    args.shell = Some(shell);
+                }
+                "--generate-man" => args.command = Command::Man,
+                "search" => {
+                    args.command = Command::Search;
+                    args.query = Some(Args::value(&mut iter, &arg)?);
+                }
+                "--mermaid" => args.mermaid = true,
+                "--mermaid-file" => args.mermaid_file = Some(Args::value(&mut iter, &arg)?),
+                "--dot-file" => args.dot_file = Some(Args::value(&mut iter, &arg)?),
+                "--include" => args.include.extend(Args::list(&mut iter, &arg)?),
+                "--exclude" => args.exclude.extend(Args::list(&mut iter, &arg)?),
+                "--stable" => args.stable = true,
+                "--no-deprecated" => args.deprecated = Some(false),
+                "--deprecated-only" => args.deprecated = Some(true),
+                "--link-types" => args.link_types = true,
+                "--line-endings" => args.crlf = match Args::value(&mut iter, &arg)?.as_str() {
+                    "lf" => false,
+                    "crlf" => true,
+                    other => return Err(format!("Unknown line endings '{}', expected 'lf' or 'crlf'", other))
+                },
+                "--max-blank-lines" => args.max_blank_lines = Some(Args::number(&mut iter, &arg)?),
+                "--reflow" => args.reflow = Some(Args::number(&mut iter, &arg)?),
+                "--void-types" => args.void_types = Some(Args::list(&mut iter, &arg)?),
+                "--accessors" => args.accessors = true,
+                "--variants" => args.variants = Some(Args::list(&mut iter, &arg)?),
+                "--variant-tabs" => args.variant_tabs = true,
+                "--group-members" => args.group_members = true,
+                "--example-output" => args.output_comments = match Args::value(&mut iter, &arg)?.as_str() {
+                    "block" => false,
+                    "comment" => true,
+                    other => return Err(format!("Unknown example output '{}', expected 'block' or 'comment'", other))
+                },
+                "--lenient" => args.lenient = true,
+                "--strict" => args.strict = true,
+                "--anchors" => args.anchors = true,
+                "--check-links" => args.check_links = true,
+                "--report" => args.report = Some(Args::value(&mut iter, &arg)?),
+                "--min-coverage" => args.min_coverage = Some(Args::number(&mut iter, &arg)?),
+                "--max-missing-descriptions" => args.max_missing_descriptions = Some(Args::number(&mut iter, &arg)?),
+                "--max-missing-examples" => args.max_missing_examples = Some(Args::number(&mut iter, &arg)?),
+                "--out" => {
+                    let template = Args::value(&mut iter, &arg)?;
+                    if !template.contains("{name}") {
+                        return Err(format!("The '--out' template '{}' needs '{{name}}' so pages don't overwrite each other", template));
+                    }
+                    args.out = Some(template);
+                    args.split = true;
+                }
+                "--anchor-key" => args.anchor_key = Some(Args::value(&mut iter, &arg)?),
+                "--instance-names" => args.instance_names = match Args::value(&mut iter, &arg)?.as_str() {
+                    "brackets" => InstanceNames::Brackets,
+                    "class" => InstanceNames::Class,
+                    "variable" => InstanceNames::Variable,
+                    other => return Err(format!("Unknown instance names '{}', expected 'brackets', 'class' or 'variable'", other))
+                },
+                "--lowercase-files" => args.lowercase_files = true,
+                "--fail-on" => args.fail_on = Some(match Args::value(&mut iter, &arg)?.as_str() {
+                    "warnings" => FailOn::Warnings,
+                    "errors" => FailOn::Errors,
+                    "never" => FailOn::Never,
+                    other => return Err(format!("Unknown level '{}', expected 'warnings', 'errors' or 'never'", other))
+                }),
+                "-v" | "--verbose" => args.verbose += 1,
+                "-vv" => args.verbose += 2,
+                "-q" | "--quiet" => args.quiet = true,
+                "--log-json" => args.log_json = true,
+                "--no-progress" => args.no_progress = true,
+                "--overlay" => args.overlays.push(Args::value(&mut iter, &arg)?),
+                "--format" => args.format = Format::parse(&Args::value(&mut iter, &arg)?)?,
+                "--wiki-repo" => args.wiki_repo = Some(Args::value(&mut iter, &arg)?),
+                "--message" => args.message = Some(Args::value(&mut iter, &arg)?),
+                "--dry-run" => args.dry_run = true,
+                "--notion-page" => args.notion_page = Some(Args::value(&mut iter, &arg)?),
+                "--pdf-engine" => {
+                    let engine = Args::value(&mut iter, &arg)?;
+                    if !pdf::ENGINES.contains(&engine.as_str()) {
+                        return Err(format!("Unknown PDF engine '{}', expected one of {}", engine, pdf::ENGINES.join(", ")));
+                    }
+                    args.pdf_engine = Some(engine);
+                }
+                "--plugin" => {
+                    args.format = Format::Plugin;
+                    args.plugin = Some(Args::value(&mut iter, &arg)?);
+                }
+                "--front-matter" => args.front_matter = true,
+                "--front-matter-template" => args.front_matter_template = Some(Args::value(&mut iter, &arg)?),
+                "--emit-json" => args.emit_json = Some(Args::value(&mut iter, &arg)?),
+                "--fragments" => args.fragments.push(Args::value(&mut iter, &arg)?),
+                "--locale" => args.locale = Some(Args::value(&mut iter, &arg)?),
+                "--languages" => args.languages.extend(Args::list(&mut iter, &arg)?),
+                "--default-language" => args.default_language = Some(Args::value(&mut iter, &arg)?),
+                "--interpreter" => args.interpreter = Some(Args::value(&mut iter, &arg)?),
+                "--format-examples" => args.example_indent = args.example_indent.or(Some(4)),
+                "--example-indent" => args.example_indent = Some(Args::number(&mut iter, &arg)?),
+                "--example-max-width" => args.example_max_width = Some(Args::number(&mut iter, &arg)?),
+                "--config" => args.config = Some(Args::value(&mut iter, &arg)?),
+                "--json" => args.json = true,
+                "--format-annotations" => args.annotations = true,
+                "--old" => args.old = Some(Args::value(&mut iter, &arg)?),
+                "--changelog" => args.changelog = Some(Args::value(&mut iter, &arg)?),
+                "--snapshot" => args.snapshot = Some(Args::value(&mut iter, &arg)?),
+                "--release" => args.release = Some(Args::value(&mut iter, &arg)?),
+                "--feed" => args.feed = Some(Args::value(&mut iter, &arg)?),
+                "--archive" => args.archive = true,
+                "--keep-snapshots" => args.keep_snapshots = Some(Args::number(&mut iter, &arg)?),
+                "--kind" => args.kind = Some(Args::value(&mut iter, &arg)?),
+                "--class" => args.class = Some(Args::value(&mut iter, &arg)?),
+                "--base-url" => args.base_url = Some(Args::value(&mut iter, &arg)?),
+                "--glossary" => args.glossary = true,
+                "--contributors" => args.contributors = true,
+                "--sources" => args.sources = Some(Args::value(&mut iter, &arg)?),
+                "--source-url" => args.source_url = Some(Args::value(&mut iter, &arg)?),
+                "--all-methods" => args.all_methods = true,
+                "--theme" => args.theme = Some(Args::value(&mut iter, &arg)?),
+                "--width" => args.width = Some(Args::number(&mut iter, &arg)?),
+                "--jobs" => args.jobs = Some(Args::number(&mut iter, &arg)?),
+                "--split" => args.split = true,
+                "--prune" => args.prune = true,
+                "--metadata" => args.metadata = true,
+                "--metadata-timestamp" => {
+                    args.metadata = true;
+                    args.metadata_timestamp = true;
+                }
+                "--stream" => args.stream = true,
+                "--input" => args.input = Some(Args::value(&mut iter, &arg)?),
+                "--input-format" => args.input_format = Some(Args::value(&mut iter, &arg)?),
+                "--classes" => args.classes = Some(Args::number(&mut iter, &arg)?),
+                _ => return Err(format!("Unknown argument '{}'", arg))
+            }
+        }
+
+        Ok(args)
+    }
+
+    // Flags set with `ARUCAS_MD_*` environment variables, in a `.env` file or in the `[options]`
+    // table of the config file, to go before the ones given. `ARUCAS_MD_FORMAT=html` is
+    // `--format html` and `ARUCAS_MD_SPLIT=true` is `--split`. Flags that are also given are
+    // left out, the command line wins over the environment, which wins over `.env`, which wins
+    // over the config file
+    pub fn environment(given: &[String]) -> Result<Vec<String>, String> {
+        let mut variables: BTreeMap<String, String> = fs::read_to_string(".env").map(|content| dotenv(&content)).unwrap_or_default();
+        // Other variables are none of our business, even when they aren't UTF-8
+        for (variable, value) in env::vars_os() {
+            let Some(variable) = variable.to_str().filter(|variable| variable.starts_with(ENV_PREFIX)) else {
+                continue;
+            };
+            let value = value.into_string().map_err(|_| format!("The value of environment variable '{}' is not valid UTF-8", variable))?;
+            variables.insert(variable.to_string(), value);
+        }
+
+        let mut flags = Vec::new();
+        for (variable, value) in variables {
+            let Some(name) = variable.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let name = format!("--{}", name.to_lowercase().replace('_', "-"));
+            let Some(flag) = FLAGS.iter().find(|flag| flag.names.contains(&name.as_str())) else {
+                return Err(format!("Unknown environment variable '{}', there is no '{}' flag", variable, name));
+            };
+            if flag.is_in(given) {
+                continue;
+            }
+
+            if flag.value.is_some() {
+                flags.push(name);
+                flags.push(value);
+                continue;
+            }
+            match value.to_lowercase().as_str() {
+                "true" | "1" | "yes" => flags.push(name),
+                "false" | "0" | "no" | "" => {}
+                _ => return Err(format!("Expected true or false for '{}' but found '{}'", variable, value))
+            }
+        }
+
+        // The config file may itself be chosen on the command line or in the environment
+        let path = given.iter().chain(&flags).skip_while(|arg| *arg != "--config").nth(1).cloned();
+        let config = Config::load(path.as_deref())?;
+        for (key, value) in config.options {
+            let name = format!("--{}", key);
+            let Some(flag) = FLAGS.iter().find(|flag| flag.names.contains(&name.as_str())) else {
+                return Err(format!("Unknown option '{}' in the config file, there is no '{}' flag", key, name));
+            };
+            if flag.is_in(given) || flag.is_in(&flags) {
+                continue;
+            }
+
+            match (flag.value.is_some(), value) {
+                (false, Value::Bool(true)) => flags.push(name),
+                (false, Value::Bool(false)) => {}
+                (true, Value::String(value)) => flags.extend([name, value]),
+                (true, Value::Number(value)) => flags.extend([name, value.to_string()]),
+                // Lists such as `include = ["List", "String"]` are given comma separated
+                (true, Value::Array(items)) if items.iter().all(Value::is_string) => {
+                    let items: Vec<&str> = items.iter().filter_map(Value::as_str).collect();
+                    flags.extend([name, items.join(",")]);
+                }
+                (false, value) => return Err(format!("Expected true or false for '{}' in the config file but found {}", key, value)),
+                (true, value) => return Err(format!("Expected a string for '{}' in the config file but found {}", key, value))
+            }
+        }
+        Ok(flags)
+    }
+
+    fn value(iter: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+        iter.next().ok_or_else(|| format!("Missing value for '{}'", flag))
+    }
+
+    fn number<T: FromStr>(iter: &mut impl Iterator<Item = String>, flag: &str) -> Result<T, String> {
+        let value = Args::value(iter, flag)?;
+        value.parse().map_err(|_| format!("Expected a number for '{}' but found '{}'", flag, value))
+    }
+
+    fn list(iter: &mut impl Iterator<Item = String>, flag: &str) -> Result<Vec<String>, String> {
+        let value = Args::value(iter, flag)?;
+        Ok(value.split(',').map(|name| name.trim().to_string()).filter(|name| !name.is_empty()).collect())
+    }
+}
+
+// The variables of a `.env` file, `KEY=value` lines where the value may be quoted and the line may
+// start with `export`. Blank lines and lines starting with `#` are skipped
+fn dotenv(content: &str) -> BTreeMap<String, String> {
+    let mut variables = BTreeMap::new();
+    for line in content.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let unquoted = ['"', '\''].iter().find_map(|quote| value.strip_prefix(*quote).and_then(|value| value.strip_suffix(*quote)));
+        variables.insert(key.trim().to_string(), unquoted.unwrap_or(value).to_string());
+    }
+    variables
+}
@@ -0,0 +1,47 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path};
+use serde_json::Value;
+use crate::output::{create_dir, replace};
+
+// Directory the assets are copied to, next to the pages linking to them
+pub const DIRECTORY: &str = "assets";
+
+// Every asset listed by a class or function, as written in the input
+pub fn collect(json: &Value) -> BTreeSet<String> {
+    let mut assets = BTreeSet::new();
+    let mut add = |value: &Value| {
+        assets.extend(value["assets"].as_array().into_iter().flatten().filter_map(Value::as_str).map(str::to_string));
+    };
+
+    for class in json["classes"].as_object().into_iter().flat_map(|classes| classes.values()) {
+        add(class);
+        for section in ["methods", "static_methods"] {
+            class[section].as_array().into_iter().flatten().for_each(&mut add);
+        }
+    }
+    for functions in json["extensions"].as_object().into_iter().flat_map(|extensions| extensions.values()) {
+        functions.as_array().into_iter().flatten().for_each(&mut add);
+    }
+    assets
+}
+
+// Copies the assets, relative to `source`, into the assets directory of `target`.
+// Paths that would leave either directory are refused. Returns the number copied.
+pub fn copy(json: &Value, source: &Path, target: &Path) -> Result<usize, String> {
+    let assets = collect(json);
+    for asset in &assets {
+        let relative = Path::new(asset);
+        if !relative.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir)) {
+            return Err(format!("Asset '{}' must be a relative path within the input's directory", asset));
+        }
+
+        let destination = target.join(DIRECTORY).join(relative);
+        if let Some(parent) = destination.parent() {
+            create_dir(parent)?;
+        }
+        fs::read(source.join(relative)).and_then(|content| replace(&destination, content))
+            .map_err(|error| format!("Could not copy asset {}: {}", source.join(relative).display(), error))?;
+    }
+    Ok(assets.len())
+}
@@ -0,0 +1,151 @@
+use std::path::Path;
+use crate::doc_parser::DocParser;
+use crate::html;
+use crate::output::write_file;
+
+// Writes Classes.bbcode and Extensions.bbcode for forums without markdown support
+pub fn write(parser: &DocParser, directory: &Path) -> Result<(), String> {
+    let mut classes = Vec::new();
+    let mut extensions = Vec::new();
+    parser.write_classes(&mut classes).and_then(|_| parser.write_extensions(&mut extensions))
+        .map_err(|error| format!("Could not render documentation: {}", error))?;
+
+    write_file(&directory.join("Classes.bbcode"), &from_markdown(&String::from_utf8_lossy(&classes)))?;
+    write_file(&directory.join("Extensions.bbcode"), &from_markdown(&String::from_utf8_lossy(&extensions)))
+}
+
+// Converts the markdown written by the renderer, headings become bold text, lists
+// `[list]` tags and examples `[code]` blocks. Anchors have no equivalent and are dropped.
+pub fn from_markdown(md: &str) -> String {
+    let mut bbcode = String::new();
+    let mut depth = 0;
+    let mut code = false;
+    let mut quote = false;
+
+    for line in md.lines() {
+        if code {
+            if line.trim_end() == "```" {
+                bbcode.push_str("[/code]\n");
+                code = false;
+            } else {
+                bbcode.push_str(line);
+                bbcode.push('\n');
+            }
+            continue;
+        }
+
+        let quoted = line.strip_prefix('>').map(|rest| rest.strip_prefix(' ').unwrap_or(rest));
+        if quoted.is_some() != quote {
+            close_lists(&mut bbcode, &mut depth, 0);
+            bbcode.push_str(if quote { "[/quote]\n" } else { "[quote]\n" });
+            quote = !quote;
+        }
+        if let Some(quoted) = quoted {
+            bbcode.push_str(&inline(quoted));
+            bbcode.push('\n');
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let list_depth = if trimmed.starts_with("- ") { (line.len() - trimmed.len()) / 2 + 1 } else { 0 };
+        let block_start = line.starts_with("```") || html::heading(line).is_some() || html::anchor(line).is_some();
+
+        // Lines directly after a list item continue it
+        if list_depth == 0 && depth > 0 && !line.trim().is_empty() && !block_start {
+            bbcode.push_str(&inline(line));
+            bbcode.push('\n');
+            continue;
+        }
+
+        if list_depth == 0 {
+            close_lists(&mut bbcode, &mut depth, 0);
+        }
+
+        if line.starts_with("```") {
+            if let Some(title) = html::fence_title(line) {
+                bbcode.push_str(&format!("[b]{}[/b]\n", title));
+            }
+            bbcode.push_str("[code]\n");
+            code = true;
+        } else if let Some((level, title)) = html::heading(line) {
+            let size = match level { 1 => "6", 2 => "5", _ => "4" };
+            bbcode.push_str(&format!("[size={}][b]{}[/b][/size]\n", size, inline(title)));
+        } else if html::anchor(line).is_some() {
+            continue;
+        } else if list_depth > 0 {
+            close_lists(&mut bbcode, &mut depth, list_depth);
+            while depth < list_depth {
+                bbcode.push_str("[list]\n");
+                depth += 1;
+            }
+            bbcode.push_str("[*]");
+            bbcode.push_str(&inline(&trimmed[2..]));
+            bbcode.push('\n');
+        } else {
+            bbcode.push_str(&inline(line));
+            bbcode.push('\n');
+        }
+    }
+
+    close_lists(&mut bbcode, &mut depth, 0);
+    if quote {
+        bbcode.push_str("[/quote]\n");
+    }
+    if code {
+        bbcode.push_str("[/code]\n");
+    }
+    bbcode
+}
+
+fn close_lists(bbcode: &mut String, depth: &mut usize, target: usize) {
+    while *depth > target {
+        bbcode.push_str("[/list]\n");
+        *depth -= 1;
+    }
+}
+
+// Inline code is set in a monospace font, bold text, images and links are converted
+fn inline(text: &str) -> String {
+    let mut bbcode = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(['`', '[', '!']) {
+        bbcode.push_str(&bold(&rest[..start]));
+        rest = &rest[start..];
+
+        if let Some((_, source, end)) = rest.strip_prefix('!').and_then(html::link_at) {
+            bbcode.push_str(&format!("[img]{}[/img]", source));
+            rest = &rest[end + 1..];
+        } else if let Some(text) = rest.strip_prefix('!') {
+            bbcode.push('!');
+            rest = text;
+        } else if let Some(code) = rest.strip_prefix('`') {
+            let end = code.find('`').unwrap_or(code.len());
+            bbcode.push_str("[font=monospace]");
+            bbcode.push_str(&code[..end]);
+            bbcode.push_str("[/font]");
+            rest = code.get(end + 1..).unwrap_or_default();
+        } else if let Some((label, target, end)) = html::link_at(rest) {
+            bbcode.push_str(&format!("[url={}]{}[/url]", target, inline(label)));
+            rest = &rest[end..];
+        } else {
+            bbcode.push('[');
+            rest = &rest[1..];
+        }
+    }
+    bbcode.push_str(&bold(rest));
+    bbcode
+}
+
+fn bold(text: &str) -> String {
+    if text.matches("**").count() % 2 == 1 {
+        return text.to_string();
+    }
+
+    let mut bbcode = String::new();
+    for (i, part) in text.split("**").enumerate() {
+        bbcode.push_str(if i % 2 == 1 { "[b]" } else { "" });
+        bbcode.push_str(part);
+        bbcode.push_str(if i % 2 == 1 { "[/b]" } else { "" });
+    }
+    bbcode
+}
@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use crate::doc_parser::DocParser;
+use crate::text;
+
+const LIST_WIDTH: usize = 26;
+
+// A class or extension, with its documentation as plain text
+struct Page {
+    name: String,
+    lines: Vec<String>,
+    examples: Vec<String>
+}
+
+struct Browser {
+    pages: Vec<Page>,
+    // Indexes of the pages matching the search
+    visible: Vec<usize>,
+    selected: usize,
+    scroll: usize,
+    search: String,
+    searching: bool,
+    copied: usize,
+    status: String,
+    rows: usize,
+    columns: usize
+}
+
+enum Key {
+    Char(char),
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Enter,
+    Escape,
+    Backspace,
+    Other
+}
+
+// Browses the documentation in the terminal: classes and extensions on the left, the
+// selected one on the right. The terminal is switched to raw mode with `stty` for the
+// duration, so this needs a Unix terminal.
+pub fn browse(parser: &DocParser) -> Result<(), String> {
+    let (rows, columns) = terminal_size()?;
+    let width = columns.saturating_sub(LIST_WIDTH + 3).max(20);
+
+    let mut pages = Vec::new();
+    for class in parser.classes() {
+        let name = class.name.clone();
+        pages.push(page(name, &parser.render_class(class, &mut HashMap::new()), width));
+    }
+    for (name, functions) in parser.extensions() {
+        let md = parser.render_extension(&name, &functions, &mut HashMap::new());
+        pages.push(page(name, &md, width));
+    }
+    if pages.is_empty() {
+        return Err("There is no documentation to browse".to_string());
+    }
+
+    let saved = stty(&["-g"])?;
+    // Reads time out after a tenth of a second so a lone escape can be told apart from a sequence
+    stty(&["raw", "-echo", "min", "0", "time", "1"])?;
+    let mut out = io::stdout();
+    let result = Browser {
+        visible: (0..pages.len()).collect(),
+        pages,
+        selected: 0,
+        scroll: 0,
+        search: String::new(),
+        searching: false,
+        copied: 0,
+        status: String::new(),
+        rows,
+        columns
+    }.run(&mut out);
+
+    // The terminal is restored even if drawing failed
+    write!(out, "\x1b[?25h\x1b[?1049l").and_then(|_| out.flush()).ok();
+    stty(&[saved.trim()])?;
+    result.map_err(|error| format!("Could not draw to the terminal: {}", error))
+}
+
+fn page(name: String, md: &str, width: usize) -> Page {
+    let mut examples = Vec::new();
+    let mut example: Option<String> = None;
+    for line in md.lines() {
+        if let Some(code) = &mut example {
+            if line.trim_end() == "```" {
+                examples.push(example.take().unwrap_or_default());
+            } else {
+                code.push_str(line);
+                code.push('\n');
+            }
+        } else if line.starts_with("```") {
+            example = Some(String::new());
+        }
+    }
+
+    let lines = text::from_markdown(md, width).lines().map(str::to_string).collect();
+    Page { name, lines, examples }
+}
+
+impl Browser {
+    fn run(&mut self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "\x1b[?1049h\x1b[?25l")?;
+        let mut input = io::stdin().lock();
+        loop {
+            self.draw(out)?;
+            let key = read_key(&mut input)?;
+            if self.searching {
+                self.search_key(key);
+                continue;
+            }
+
+            self.status.clear();
+            match key {
+                Key::Char('q') | Key::Escape => return Ok(()),
+                Key::Char('/') => self.searching = true,
+                Key::Up | Key::Char('k') => self.select(self.selected.saturating_sub(1)),
+                Key::Down | Key::Char('j') => self.select(self.selected + 1),
+                Key::PageDown | Key::Char(' ') => self.scroll_by(self.body_rows() as isize),
+                Key::PageUp | Key::Char('b') => self.scroll_by(-(self.body_rows() as isize)),
+                Key::Char('c') => self.copy(out)?,
+                _ => {}
+            }
+        }
+    }
+
+    fn search_key(&mut self, key: Key) {
+        match key {
+            Key::Enter | Key::Escape => self.searching = false,
+            Key::Backspace => {
+                self.search.pop();
+            }
+            Key::Char(c) if !c.is_control() => self.search.push(c),
+            _ => return
+        }
+
+        let search = self.search.to_lowercase();
+        self.visible = (0..self.pages.len()).filter(|&i| {
+            let page = &self.pages[i];
+            page.name.to_lowercase().contains(&search) || page.lines.iter().any(|line| line.to_lowercase().contains(&search))
+        }).collect();
+        self.select(0);
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.visible.len().saturating_sub(1));
+        self.scroll = 0;
+        self.copied = 0;
+    }
+
+    fn scroll_by(&mut self, rows: isize) {
+        let length = self.current().map_or(0, |page| page.lines.len());
+        let last = length.saturating_sub(self.body_rows());
+        self.scroll = self.scroll.saturating_add_signed(rows).min(last);
+    }
+
+    // Copies the next example of the page through the terminal's clipboard escape,
+    // pressing again moves on to the example after it
+    fn copy(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let Some(page) = self.current() else {
+            return Ok(());
+        };
+        if page.examples.is_empty() {
+            self.status = "No examples on this page".to_string();
+            return Ok(());
+        }
+
+        let index = self.copied % page.examples.len();
+        write!(out, "\x1b]52;c;{}\x07", base64(page.examples[index].as_bytes()))?;
+        self.status = format!("Copied example {} of {}", index + 1, page.examples.len());
+        self.copied += 1;
+        Ok(())
+    }
+
+    fn current(&self) -> Option<&Page> {
+        self.visible.get(self.selected).map(|&i| &self.pages[i])
+    }
+
+    fn body_rows(&self) -> usize {
+        self.rows.saturating_sub(1).max(1)
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let mut screen = String::from("\x1b[H");
+        let body_width = self.columns.saturating_sub(LIST_WIDTH + 3);
+        let lines = self.current().map_or(&[][..], |page| &page.lines[self.scroll.min(page.lines.len())..]);
+
+        // The list scrolls to keep the selected page in view
+        let first = self.selected.saturating_sub(self.body_rows() - 1);
+        for row in 0..self.body_rows() {
+            let name = self.visible.get(first + row).map_or("", |&i| self.pages[i].name.as_str());
+            let name = fit(name, LIST_WIDTH);
+            if first + row == self.selected {
+                screen.push_str(&format!(" \x1b[7m{}\x1b[0m", name));
+            } else {
+                screen.push_str(&format!(" {}", name));
+            }
+            screen.push_str(" \u{2502} ");
+            screen.push_str(lines.get(row).map_or("", |line| line.as_str()).chars().take(body_width).collect::<String>().as_str());
+            screen.push_str("\x1b[K\r\n");
+        }
+
+        let status = if self.searching {
+            format!("/{}", self.search)
+        } else if !self.status.is_empty() {
+            self.status.clone()
+        } else {
+            "\u{2191}\u{2193} select  space/b scroll  / search  c copy example  q quit".to_string()
+        };
+        screen.push_str(&format!("\x1b[7m{}\x1b[0m\x1b[K", fit(&status, self.columns)));
+        out.write_all(screen.as_bytes())?;
+        out.flush()
+    }
+}
+
+// Pads or cuts the text to exactly `width` characters
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted: String = text.chars().take(width).collect();
+    let length = fitted.chars().count();
+    fitted.push_str(&" ".repeat(width - length));
+    fitted
+}
+
+fn read_key(input: &mut impl Read) -> io::Result<Key> {
+    let mut byte = [0u8; 1];
+    while input.read(&mut byte)? == 0 {}
+    Ok(match byte[0] {
+        b'\r' | b'\n' => Key::Enter,
+        0x7f | 0x08 => Key::Backspace,
+        0x1b => {
+            // Arrow and paging keys arrive as escape sequences, a lone escape has nothing after it
+            let mut sequence = [0u8; 2];
+            if input.read(&mut sequence[..1])? == 0 || sequence[0] != b'[' {
+                return Ok(Key::Escape);
+            }
+            input.read_exact(&mut sequence[1..])?;
+            match sequence[1] {
+                b'A' => Key::Up,
+                b'B' => Key::Down,
+                b'5' | b'6' => {
+                    input.read_exact(&mut byte)?;
+                    if sequence[1] == b'5' { Key::PageUp } else { Key::PageDown }
+                }
+                _ => Key::Other
+            }
+        }
+        byte if byte.is_ascii() => Key::Char(byte as char),
+        _ => Key::Other
+    })
+}
+
+fn stty(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).output()
+        .map_err(|error| format!("Could not run stty, browse needs a Unix terminal: {}", error))?;
+    if !output.status.success() {
+        return Err("browse needs to be run in a terminal".to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn terminal_size() -> Result<(usize, usize), String> {
+    let size = stty(&["size"])?;
+    let mut parts = size.split_whitespace().filter_map(|part| part.parse().ok());
+    match (parts.next(), parts.next()) {
+        (Some(rows), Some(columns)) => Ok((rows, columns)),
+        _ => Err("Could not read the size of the terminal".to_string())
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let value = ((chunk[0] as u32) << 16) | ((*chunk.get(1).unwrap_or(&0) as u32) << 8) | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((value >> (18 - i * 6)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
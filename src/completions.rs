@@ -0,0 +1,159 @@
+use crate::args::{Flag, COMMANDS, FLAGS, SHELLS};
+
+// The completion script of the shell, generated from the flags `parse` knows
+pub fn script(shell: &str, bin: &str) -> String {
+    match shell {
+        "bash" => bash(bin),
+        "zsh" => zsh(bin),
+        "fish" => fish(bin),
+        _ => powershell(bin)
+    }
+}
+
+// Paths and directories are completed from the file system, other values only from their choices
+fn is_path(flag: &Flag) -> bool {
+    flag.value == Some("<path>")
+}
+
+fn is_dir(flag: &Flag) -> bool {
+    flag.value == Some("<dir>")
+}
+
+fn bash(bin: &str) -> String {
+    let function = format!("_{}", bin.replace('-', "_"));
+    let names = |filter: fn(&Flag) -> bool| FLAGS.iter().filter(|flag| filter(flag)).flat_map(|flag| flag.names.iter().copied()).collect::<Vec<_>>().join("|");
+
+    let mut script = String::new();
+    script.push_str(&format!("{}() {{\n", function));
+    script.push_str("    local current=\"${COMP_WORDS[COMP_CWORD]}\" previous=\"${COMP_WORDS[COMP_CWORD-1]}\"\n");
+    script.push_str("    case \"$previous\" in\n");
+    for flag in FLAGS.iter().filter(|flag| !flag.choices.is_empty()) {
+        script.push_str(&format!("        {}) COMPREPLY=($(compgen -W \"{}\" -- \"$current\")); return;;\n", flag.names.join("|"), flag.choices.join(" ")));
+    }
+    script.push_str(&format!("        completions) COMPREPLY=($(compgen -W \"{}\" -- \"$current\")); return;;\n", SHELLS.join(" ")));
+    script.push_str(&format!("        {}) COMPREPLY=($(compgen -f -- \"$current\")); return;;\n", names(is_path)));
+    script.push_str(&format!("        {}) COMPREPLY=($(compgen -d -- \"$current\")); return;;\n", names(is_dir)));
+    script.push_str(&format!("        {}) COMPREPLY=(); return;;\n", names(|flag| flag.value.is_some() && flag.choices.is_empty() && !is_path(flag) && !is_dir(flag))));
+    script.push_str("    esac\n");
+    let words = COMMANDS.iter().map(|command| command.name).chain(FLAGS.iter().flat_map(|flag| flag.names.iter().copied())).collect::<Vec<_>>();
+    script.push_str(&format!("    COMPREPLY=($(compgen -W \"{}\" -- \"$current\"))\n", words.join(" ")));
+    script.push_str("}\n");
+    script.push_str(&format!("complete -F {} {}\n", function, bin));
+    script
+}
+
+// Help is written inside single quoted `[...]` and `name:help` specs
+fn zsh_escape(help: &str) -> String {
+    help.replace('\'', "'\\''").replace('[', "\\[").replace(']', "\\]").replace(':', "\\:")
+}
+
+fn zsh(bin: &str) -> String {
+    let function = format!("_{}", bin.replace('-', "_"));
+    let mut script = format!("#compdef {}\n\n", bin);
+    script.push_str(&format!("{}() {{\n", function));
+    script.push_str("    local -a commands\n");
+    script.push_str("    commands=(\n");
+    for command in COMMANDS {
+        script.push_str(&format!("        '{}:{}'\n", command.name, zsh_escape(command.help)));
+    }
+    script.push_str("    )\n\n");
+    script.push_str("    _arguments \\\n");
+    for flag in FLAGS {
+        let names = match flag.names {
+            [name] => name.to_string(),
+            names => format!("'({})'{{{}}}", names.join(" "), names.join(","))
+        };
+        let value = match flag.value {
+            None => String::new(),
+            Some(_) if !flag.choices.is_empty() => format!(":value:({})", flag.choices.join(" ")),
+            Some(_) if is_path(flag) => ":path:_files".to_string(),
+            Some(_) if is_dir(flag) => ":dir:_files -/".to_string(),
+            Some(value) => format!(":{}: ", zsh_escape(value))
+        };
+        script.push_str(&format!("        {}'[{}]{}' \\\n", names, zsh_escape(flag.help), value));
+    }
+    script.push_str("        '1: :_describe command commands' \\\n");
+    script.push_str("        '2: :->value'\n\n");
+    script.push_str(&format!("    [[ $state == value && $line[1] == completions ]] && _values shell {}\n", SHELLS.join(" ")));
+    script.push_str("}\n\n");
+    script.push_str(&format!("{} \"$@\"\n", function));
+    script
+}
+
+fn fish_escape(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+fn fish(bin: &str) -> String {
+    let mut script = format!("complete -c {} -f\n", bin);
+    for command in COMMANDS {
+        script.push_str(&format!("complete -c {} -n __fish_use_subcommand -a {} -d '{}'\n", bin, command.name, fish_escape(command.help)));
+    }
+    script.push_str(&format!("complete -c {} -n '__fish_seen_subcommand_from completions' -a '{}'\n", bin, SHELLS.join(" ")));
+    for flag in FLAGS {
+        let mut line = format!("complete -c {}", bin);
+        for name in flag.names {
+            match name.strip_prefix("--") {
+                Some(long) => line.push_str(&format!(" -l {}", long)),
+                // fish only knows single letters as short options, `-vv` is an old style one
+                None if name.len() == 2 => line.push_str(&format!(" -s {}", &name[1..])),
+                None => line.push_str(&format!(" -o {}", &name[1..]))
+            }
+        }
+        if !flag.choices.is_empty() {
+            line.push_str(&format!(" -x -a '{}'", flag.choices.join(" ")));
+        } else if is_path(flag) {
+            line.push_str(" -r -F");
+        } else if is_dir(flag) {
+            line.push_str(" -x -a '(__fish_complete_directories)'");
+        } else if flag.value.is_some() {
+            line.push_str(" -x");
+        }
+        line.push_str(&format!(" -d '{}'\n", fish_escape(flag.help)));
+        script.push_str(&line);
+    }
+    script
+}
+
+fn powershell_escape(text: &str) -> String {
+    text.replace('\'', "''")
+}
+
+fn powershell(bin: &str) -> String {
+    let quoted = |values: &[&str]| values.iter().map(|value| format!("'{}'", powershell_escape(value))).collect::<Vec<_>>().join(", ");
+
+    let mut script = String::from("using namespace System.Management.Automation\n\n");
+    script.push_str(&format!("Register-ArgumentCompleter -Native -CommandName '{}' -ScriptBlock {{\n", bin));
+    script.push_str("    param($wordToComplete, $commandAst, $cursorPosition)\n\n");
+    script.push_str("    $previous = $commandAst.CommandElements | Where-Object { $_.Extent.EndOffset -lt $cursorPosition } | Select-Object -Last 1\n");
+    script.push_str("    $values = switch ([string]$previous) {\n");
+    for flag in FLAGS.iter().filter(|flag| !flag.choices.is_empty()) {
+        for name in flag.names {
+            script.push_str(&format!("        '{}' {{ {} }}\n", name, quoted(flag.choices)));
+        }
+    }
+    script.push_str(&format!("        'completions' {{ {} }}\n", quoted(SHELLS)));
+    script.push_str("    }\n");
+    script.push_str("    if ($values) {\n");
+    script.push_str("        return $values | Where-Object { $_ -like \"$wordToComplete*\" } |\n");
+    script.push_str("            ForEach-Object { [CompletionResult]::new($_, $_, [CompletionResultType]::ParameterValue, $_) }\n");
+    script.push_str("    }\n\n");
+    script.push_str("    @(\n");
+    for command in COMMANDS {
+        script.push_str(&format!(
+            "        [CompletionResult]::new('{0}', '{0}', [CompletionResultType]::Command, '{1}')\n",
+            command.name, powershell_escape(command.help)
+        ));
+    }
+    for flag in FLAGS {
+        for name in flag.names {
+            script.push_str(&format!(
+                "        [CompletionResult]::new('{0}', '{0}', [CompletionResultType]::ParameterName, '{1}')\n",
+                name, powershell_escape(flag.help)
+            ));
+        }
+    }
+    script.push_str("    ) | Where-Object { $_.CompletionText -like \"$wordToComplete*\" }\n");
+    script.push_str("}\n");
+    script
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::lint;
+use crate::toml;
+
+pub const DEFAULT_PATH: &str = "arucas-md.toml";
+
+// Settings read from the TOML config file
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // Lint rules switched on or off by name
+    pub lint: BTreeMap<String, bool>,
+    pub badges: Badges,
+    pub spelling: Spelling,
+    // Names of classes and extensions that come first in the output, in this order
+    pub order: Vec<String>,
+    // Command line options by flag name without the dashes, used when neither the command
+    // line nor the environment sets them
+    pub options: BTreeMap<String, Value>
+}
+
+// Marks added after the headings and index entries of items with a status,
+// none unless the config file gives them
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Badges {
+    pub deprecated: Option<String>,
+    pub experimental: Option<String>,
+    pub read_only: Option<String>
+}
+
+// Where the `spelling` lint rule finds the words it knows, the project dictionary
+// holds jargon such as Minecraft and Arucas terms, one word per line
+#[derive(Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Spelling {
+    pub word_list: String,
+    pub dictionary: Option<String>
+}
+
+impl Default for Spelling {
+    fn default() -> Self {
+        Spelling { word_list: "/usr/share/dict/words".to_string(), dictionary: None }
+    }
+}
+
+impl Config {
+    // Reads the given config file, or the default one if it exists
+    pub fn load(path: Option<&str>) -> Result<Config, String> {
+        let path = match path {
+            Some(path) => path,
+            None if Path::new(DEFAULT_PATH).exists() => DEFAULT_PATH,
+            None => return Ok(Config::default())
+        };
+
+        let content = fs::read_to_string(path).map_err(|error| format!("Could not read config '{}': {}", path, error))?;
+        let value = toml::parse(&content).map_err(|error| format!("Could not parse config '{}': {}", path, error))?;
+        let config: Config = serde_json::from_value(value).map_err(|error| format!("Invalid config '{}': {}", path, error))?;
+
+        // A misspelt rule would otherwise be ignored without a word
+        if let Some(unknown) = config.lint.keys().find(|rule| !lint::RULES.iter().any(|(name, _)| name == rule)) {
+            let rules: Vec<&str> = lint::RULES.iter().map(|(name, _)| *name).collect();
+            return Err(format!("Invalid config '{}': unknown lint rule '{}', expected one of {}", path, unknown, rules.join(", ")));
+        }
+        Ok(config)
+    }
+}
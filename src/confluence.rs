@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::path::Path;
+use serde_json::{json, Value};
+use crate::doc_parser::DocParser;
+use crate::html::{self, Flavour};
+use crate::output::{create_dir, write_file};
+
+pub const DIRECTORY: &str = "confluence";
+
+// Confluence storage format, examples become code macros and anchors anchor macros
+const STORAGE: Flavour = Flavour {
+    code_block: |language, title, code| {
+        let language = match language {
+            "kt" | "kotlin" => "kotlin",
+            "" => "none",
+            other => other
+        };
+        let title = match title {
+            Some(title) => format!("<ac:parameter ac:name=\"title\">{}</ac:parameter>", html::escape(title)),
+            None => String::new()
+        };
+        format!(
+            "<ac:structured-macro ac:name=\"code\"><ac:parameter ac:name=\"language\">{}</ac:parameter>{}\
+            <ac:plain-text-body><![CDATA[{}]]></ac:plain-text-body></ac:structured-macro>\n",
+            html::escape(language), title, code.trim_end_matches('\n').replace("]]>", "]]]]><![CDATA[>")
+        )
+    },
+    anchor: |id| format!(
+        "<ac:structured-macro ac:name=\"anchor\"><ac:parameter ac:name=\"\">{}</ac:parameter></ac:structured-macro>\n",
+        html::escape(id)
+    ),
+    tabs: false
+};
+
+// Writes a page in Confluence storage format per class and extension, and
+// pages.json listing the title and file of each for upload scripts
+pub fn write(parser: &DocParser, path: &Path) -> Result<usize, String> {
+    for directory in ["classes", "extensions"] {
+        create_dir(&path.join(directory))?;
+    }
+
+    let mut pages = Vec::new();
+    for class in parser.classes() {
+        let file = format!("classes/{}.xhtml", class.name);
+        pages.push(json!({ "title": class.name, "file": file, "kind": "class" }));
+        let md = parser.render_class(class, &mut HashMap::new());
+        write_file(&path.join(&file), &html::convert(&md, &STORAGE))?;
+    }
+
+    for (name, functions) in parser.extensions() {
+        let file = format!("extensions/{}.xhtml", name);
+        pages.push(json!({ "title": name, "file": file, "kind": "extension" }));
+        let md = parser.render_extension(&name, &functions, &mut HashMap::new());
+        write_file(&path.join(&file), &html::convert(&md, &STORAGE))?;
+    }
+
+    let count = pages.len();
+    write_file(&path.join("pages.json"), &serde_json::to_string_pretty(&Value::Array(pages)).unwrap())?;
+    Ok(count)
+}
@@ -0,0 +1,34 @@
+use std::collections::BTreeMap;
+use crate::doc_parser::DocParser;
+
+// Contributors.md: everyone credited by the document or a class, with the classes
+// each of them contributed
+pub fn contributors(parser: &DocParser) -> String {
+    let mut authors: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for author in parser.authors() {
+        authors.entry(author).or_default();
+    }
+    for class in parser.classes() {
+        for author in class.authors.iter().flatten() {
+            authors.entry(author.clone()).or_default().push(class.name.clone());
+        }
+    }
+
+    let mut md = String::from("# Contributors\n\n");
+    if authors.is_empty() {
+        md.push_str("No contributors are credited.\n");
+        return md;
+    }
+
+    for (author, classes) in authors {
+        md.push_str("- ");
+        md.push_str(&author);
+        if !classes.is_empty() {
+            let links: Vec<String> = classes.iter().map(|class| format!("[{}]({})", class, parser.link(false, class, None))).collect();
+            md.push_str(": ");
+            md.push_str(&links.join(", "));
+        }
+        md.push('\n');
+    }
+    md
+}
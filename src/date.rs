@@ -0,0 +1,32 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn now_seconds() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0)
+}
+
+// Today's date as YYYY-MM-DD in UTC
+pub fn today() -> String {
+    let (year, month, day) = civil_from_days((now_seconds() / 86400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// The time as an RFC 3339 timestamp in UTC, as feeds use
+pub fn timestamp(seconds: u64) -> String {
+    let (year, month, day) = civil_from_days((seconds / 86400) as i64);
+    let time = seconds % 86400;
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, time / 3600, time / 60 % 60, time % 60)
+}
+
+// Converts days since the unix epoch to a (year, month, day) date
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, BTreeSet};
+use crate::doc_parser::{Class, DocParser, Function, Member, Param};
+
+pub fn mermaid(classes: &[Class]) -> String {
+    let names = class_names(classes);
+    let mut md = String::new();
+
+    md.push_str("classDiagram\n");
+
+    for class in classes {
+        let mut body = Vec::new();
+
+        if let Some(statics) = &class.static_members {
+            for member in statics {
+                body.push(mermaid_member(member, true));
+            }
+        }
+
+        if let Some(members) = &class.members {
+            for member in members {
+                body.push(mermaid_member(member, false));
+            }
+        }
+
+        if let Some(methods) = &class.methods {
+            for method in methods {
+                body.push(mermaid_function(method, false));
+            }
+        }
+
+        if let Some(static_methods) = &class.static_methods {
+            for method in static_methods {
+                body.push(mermaid_function(method, true));
+            }
+        }
+
+        md.push_str("    class ");
+        md.push_str(&class.name);
+
+        // Mermaid doesn't like empty class bodies
+        if body.is_empty() {
+            md.push('\n');
+            continue;
+        }
+
+        md.push_str(" {\n");
+        for line in body {
+            md.push_str("        ");
+            md.push_str(&line);
+            md.push('\n');
+        }
+        md.push_str("    }\n");
+    }
+
+    for class in classes {
+        for used in used_types(class, &names).keys() {
+            md.push_str("    ");
+            md.push_str(&class.name);
+            md.push_str(" ..> ");
+            md.push_str(used);
+            md.push('\n');
+        }
+    }
+
+    md
+}
+
+pub fn dot(classes: &[Class]) -> String {
+    let names = class_names(classes);
+    let mut dot = String::new();
+
+    dot.push_str("digraph Arucas {\n");
+    dot.push_str("    node [shape=box];\n");
+
+    for class in classes {
+        dot.push_str("    \"");
+        dot.push_str(&class.name);
+        dot.push_str("\";\n");
+    }
+
+    for class in classes {
+        for (used, usages) in used_types(class, &names) {
+            let label: Vec<&str> = usages.into_iter().collect();
+            dot.push_str("    \"");
+            dot.push_str(&class.name);
+            dot.push_str("\" -> \"");
+            dot.push_str(used);
+            dot.push_str("\" [label=\"");
+            dot.push_str(&label.join(", "));
+            dot.push_str("\"];\n");
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+pub fn class_names(classes: &[Class]) -> BTreeSet<&str> {
+    classes.iter().map(|class| class.name.as_str()).collect()
+}
+
+// Documented classes that a class uses, mapped to how they are used
+pub fn used_types<'a>(class: &'a Class, names: &BTreeSet<&str>) -> BTreeMap<&'a str, BTreeSet<&'static str>> {
+    let mut types = BTreeMap::new();
+
+    let members = class.static_members.iter().chain(class.members.iter()).flatten();
+    for member in members {
+        if let Some(type_name) = &member.type_name {
+            add_usage(&mut types, type_name, "member");
+        }
+    }
+
+    let functions = class.methods.iter().chain(class.static_methods.iter()).flatten();
+    for function in functions {
+        add_param_types(&mut types, &function.params);
+        if let Some(returns) = &function.returns {
+            add_usage(&mut types, &returns.type_name, "return");
+        }
+    }
+
+    if let Some(constructors) = &class.constructors {
+        for constructor in constructors {
+            add_param_types(&mut types, &constructor.params);
+        }
+    }
+
+    types.retain(|type_name, _| *type_name != class.name && names.contains(type_name));
+    types
+}
+
+fn add_param_types<'a>(types: &mut BTreeMap<&'a str, BTreeSet<&'static str>>, params: &'a Option<Vec<Param>>) {
+    if let Some(params) = params {
+        for param in params {
+            add_usage(types, &param.type_name, "parameter");
+        }
+    }
+}
+
+fn add_usage<'a>(types: &mut BTreeMap<&'a str, BTreeSet<&'static str>>, type_name: &'a str, usage: &'static str) {
+    for name in DocParser::type_names(type_name) {
+        types.entry(name).or_default().insert(usage);
+    }
+}
+
+fn mermaid_member(member: &Member, is_static: bool) -> String {
+    let mut line = String::from("+");
+    if let Some(type_name) = &member.type_name {
+        line.push_str(&mermaid_type(type_name));
+        line.push(' ');
+    }
+    line.push_str(&member.name);
+    if is_static {
+        line.push('$');
+    }
+    line
+}
+
+fn mermaid_function(function: &Function, is_static: bool) -> String {
+    let mut line = String::from("+");
+    line.push_str(&function.name);
+    line.push('(');
+    if let Some(params) = &function.params {
+        let names: Vec<&str> = params.iter().map(|param| param.name.as_str()).collect();
+        line.push_str(&names.join(", "));
+    }
+    line.push(')');
+    if is_static {
+        line.push('$');
+    }
+    if let Some(returns) = &function.returns {
+        line.push(' ');
+        line.push_str(&mermaid_type(&returns.type_name));
+    }
+    line
+}
+
+// Mermaid reads `|` as part of the diagram syntax, so unions and nullable types are
+// written out in words: `String | Number?` is `String or Number or Null`
+fn mermaid_type(type_name: &str) -> String {
+    let mut names: Vec<&str> = DocParser::type_names(type_name).collect();
+    if type_name.split('|').any(|part| part.trim().ends_with('?')) && !names.contains(&"Null") {
+        names.push("Null");
+    }
+    names.join(" or ")
+}
@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+use crate::doc_parser::{DocParser, Function, Member, Param};
+
+// A documented item, keyed so that overloads are told apart by arity
+pub struct Item {
+    pub owner: String,
+    pub kind: &'static str,
+    pub signature: String,
+    pub desc: String,
+    pub deprecated: bool,
+    pub experimental: bool,
+    // Key of the anchor added before the item's heading
+    pub anchor: String
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Deprecated,
+    Changed
+}
+
+impl ChangeKind {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Deprecated => "deprecated",
+            ChangeKind::Changed => "changed"
+        }
+    }
+}
+
+pub struct Change {
+    pub kind: ChangeKind,
+    pub item: Item
+}
+
+pub fn diff(old: &DocParser, new: &DocParser) -> Vec<Change> {
+    let mut old_items = items(old);
+    let mut changes = Vec::new();
+
+    for (key, item) in items(new) {
+        let kind = match old_items.remove(&key) {
+            None => ChangeKind::Added,
+            Some(previous) if item.deprecated && !previous.deprecated => ChangeKind::Deprecated,
+            Some(previous) if item.desc != previous.desc => ChangeKind::Changed,
+            Some(_) => continue
+        };
+        changes.push(Change { kind, item });
+    }
+
+    for (_, item) in old_items {
+        changes.push(Change { kind: ChangeKind::Removed, item });
+    }
+
+    changes.sort_by(|a, b| (&a.item.owner, a.kind, &a.item.signature).cmp(&(&b.item.owner, b.kind, &b.item.signature)));
+    changes
+}
+
+pub fn items(parser: &DocParser) -> BTreeMap<String, Item> {
+    let mut items = BTreeMap::new();
+
+    for class in parser.classes() {
+        let owner = class.name.clone();
+        let desc = class.desc.as_ref().map(|desc| desc.join("\n")).unwrap_or_default();
+        insert(&mut items, Item { owner: owner.clone(), kind: "class", signature: owner.clone(), desc, deprecated: false, experimental: class.experimental.unwrap_or(false), anchor: owner.clone() });
+
+        add_members(parser, &mut items, &owner, "static member", &owner, &class.static_members, &format!("{}-static", owner));
+        add_members(parser, &mut items, &owner, "member", &format!("<{}>", owner), &class.members, &owner);
+
+        let constructors = class.constructors.as_ref().map_or(0, Vec::len);
+        for constructor in class.constructors.iter().flatten() {
+            let signature = format!("new {}({})", owner, param_names(&constructor.params));
+            let desc = constructor.desc.join("\n");
+            let params = constructor.params.as_ref().map_or(0, Vec::len);
+            let anchor = DocParser::constructor_anchor(&owner, constructors, params);
+            let anchor = parser.anchor_key(anchor, "constructor", &owner, "new", params);
+            insert(&mut items, Item { owner: owner.clone(), kind: "constructor", signature, desc, deprecated: false, experimental: false, anchor });
+        }
+
+        add_functions(parser, &mut items, &owner, "method", Some(&format!("<{}>", owner)), Some(&owner), class.methods.iter().flatten());
+        add_functions(parser, &mut items, &owner, "static method", Some(&owner), Some(&format!("{}-static", owner)), class.static_methods.iter().flatten());
+    }
+
+    for (name, functions) in parser.extensions() {
+        add_functions(parser, &mut items, &name, "function", None, None, functions.iter());
+    }
+
+    items
+}
+
+fn insert(items: &mut BTreeMap<String, Item>, item: Item) {
+    let key = match item.signature.find('(') {
+        Some(index) => {
+            let params = item.signature[index + 1..].trim_end_matches(')');
+            let arity = if params.is_empty() { 0 } else { params.split(',').count() };
+            format!("{}/{}/{}/{}", item.owner, item.kind, &item.signature[..index], arity)
+        }
+        None => format!("{}/{}/{}", item.owner, item.kind, item.signature)
+    };
+    items.insert(key, item);
+}
+
+fn add_members(
+    parser: &DocParser,
+    items: &mut BTreeMap<String, Item>,
+    owner: &str,
+    kind: &'static str,
+    prefix: &str,
+    members: &Option<Vec<Member>>,
+    anchor_prefix: &str
+) {
+    for member in members.iter().flatten() {
+        insert(items, Item {
+            owner: owner.to_string(),
+            kind,
+            signature: format!("{}.{}", prefix, member.name),
+            desc: member.desc.as_ref().map(|desc| desc.join("\n")).unwrap_or_default(),
+            deprecated: false,
+            experimental: false,
+            anchor: parser.anchor_key(format!("{}-{}", anchor_prefix, member.name), kind, owner, &member.name, 0)
+        });
+    }
+}
+
+fn add_functions<'a>(
+    parser: &DocParser,
+    items: &mut BTreeMap<String, Item>,
+    owner: &str,
+    kind: &'static str,
+    prefix: Option<&str>,
+    anchor_prefix: Option<&str>,
+    functions: impl Iterator<Item = &'a Function>
+) {
+    for function in functions {
+        let name = match prefix {
+            Some(prefix) => format!("{}.{}", prefix, function.name),
+            None => function.name.clone()
+        };
+        insert(items, Item {
+            owner: owner.to_string(),
+            kind,
+            signature: format!("{}({})", name, param_names(&function.params)),
+            desc: function.desc.as_ref().map(|desc| desc.join("\n")).unwrap_or_default(),
+            deprecated: function.deprecated.is_some(),
+            experimental: function.experimental.unwrap_or(false),
+            anchor: parser.anchor_key(match anchor_prefix {
+                Some(anchor_prefix) => format!("{}-{}", anchor_prefix, function.name),
+                None => function.name.clone()
+            }, kind, owner, &function.name, function.params.as_ref().map_or(0, Vec::len))
+        });
+    }
+}
+
+fn param_names(params: &Option<Vec<Param>>) -> String {
+    params.iter().flatten().map(|param| param.name.as_str()).collect::<Vec<&str>>().join(", ")
+}
+
+pub fn plural(count: usize, kind: &str) -> String {
+    match (count, kind) {
+        (1, _) => format!("1 {}", kind),
+        (_, "class") => format!("{} classes", count),
+        (_, "entry") => format!("{} entries", count),
+        _ => format!("{} {}s", count, kind)
+    }
+}
+
+// A short markdown summary sized for a pull request comment
+pub fn summary(changes: &[Change]) -> String {
+    let mut md = String::new();
+    md.push_str("### Documentation changes\n\n");
+
+    if changes.is_empty() {
+        md.push_str("No documentation changes.\n");
+        return md;
+    }
+
+    let mut owners: BTreeMap<&str, BTreeMap<(ChangeKind, &str), usize>> = BTreeMap::new();
+    for change in changes {
+        let counts = owners.entry(&change.item.owner).or_default();
+        *counts.entry((change.kind, change.item.kind)).or_insert(0) += 1;
+    }
+
+    for (owner, counts) in owners {
+        let parts: Vec<String> = counts.iter().map(|((kind, item_kind), count)| {
+            format!("{} {}", plural(*count, item_kind), kind.verb())
+        }).collect();
+        md.push_str("- `");
+        md.push_str(owner);
+        md.push_str("`: ");
+        md.push_str(&parts.join(", "));
+        md.push('\n');
+    }
+
+    md.push_str("\n<details>\n<summary>Details</summary>\n\n");
+    for change in changes {
+        md.push_str("- ");
+        md.push_str(change.kind.verb());
+        md.push(' ');
+        md.push_str(change.item.kind);
+        md.push_str(" `");
+        md.push_str(&change.item.signature);
+        md.push_str("`\n");
+    }
+    md.push_str("\n</details>\n");
+
+    md
+}
+
+// A Keep a Changelog style section listing every change
+pub fn changelog_section(changes: &[Change], version: &str, date: &str) -> String {
+    let mut md = String::new();
+    md.push_str("## [");
+    md.push_str(version);
+    md.push_str("] - ");
+    md.push_str(date);
+    md.push('\n');
+
+    let kinds = [
+        (ChangeKind::Added, "Added"),
+        (ChangeKind::Changed, "Changed"),
+        (ChangeKind::Deprecated, "Deprecated"),
+        (ChangeKind::Removed, "Removed")
+    ];
+    for (kind, title) in kinds {
+        let matching: Vec<&Change> = changes.iter().filter(|change| change.kind == kind).collect();
+        if matching.is_empty() {
+            continue;
+        }
+
+        md.push_str("\n### ");
+        md.push_str(title);
+        md.push('\n');
+        for change in matching {
+            md.push_str("- ");
+            md.push_str(change.item.kind);
+            md.push_str(" `");
+            md.push_str(&change.item.signature);
+            md.push_str("`\n");
+        }
+    }
+
+    md
+}
+
+// Adds the section above previous entries, keeping the title of the changelog
+pub fn insert_section(changelog: &str, section: &str) -> String {
+    if changelog.trim().is_empty() {
+        return format!("# Changelog\n\n{}", section);
+    }
+
+    let index = match changelog.find("\n## ") {
+        Some(index) => index + 1,
+        None if changelog.starts_with("## ") => 0,
+        None => changelog.len()
+    };
+
+    let (head, tail) = changelog.split_at(index);
+    let mut md = head.trim_end().to_string();
+    if !md.is_empty() {
+        md.push_str("\n\n");
+    }
+    md.push_str(section);
+    if !tail.is_empty() {
+        md.push('\n');
+        md.push_str(tail);
+    }
+    md
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use serde_json::{json, Value};
+use crate::doc_parser::{DocParser, Function};
+use crate::output::write_file;
+
+pub const FILE: &str = "discord.json";
+pub const EMBEDS_FILE: &str = "discord-embeds.json";
+
+// Discord's limits on the length of a message and the parts of an embed
+const MESSAGE_LIMIT: usize = 2000;
+const TITLE_LIMIT: usize = 256;
+const DESCRIPTION_LIMIT: usize = 4096;
+const FIELD_NAME_LIMIT: usize = 256;
+const FIELD_VALUE_LIMIT: usize = 1024;
+const FIELD_COUNT_LIMIT: usize = 25;
+const EMBED_LIMIT: usize = 6000;
+
+// Writes every documented function as a markdown snippet short enough to send in
+// a single message, keyed by `Class.method` or the bare extension function name.
+// Returns the number of snippets.
+pub fn write(parser: &DocParser, path: &Path) -> Result<usize, String> {
+    let snippets = snippets(parser);
+    write_file(path, &(serde_json::to_string_pretty(&snippets).unwrap() + "\n"))?;
+    Ok(snippets.len())
+}
+
+fn snippets(parser: &DocParser) -> BTreeMap<String, String> {
+    let mut snippets = BTreeMap::new();
+    for (key, functions) in functions(parser) {
+        let rendered: Vec<String> = functions.iter()
+            .filter_map(|(class_op, function)| parser.render_function(class_op.as_deref(), function))
+            .collect();
+        if !rendered.is_empty() {
+            snippets.insert(key, fit(&rendered.join("\n"), MESSAGE_LIMIT));
+        }
+    }
+    snippets
+}
+
+// Writes every documented function as Discord embed objects, keyed like the snippets.
+// Returns the number of functions.
+pub fn write_embeds(parser: &DocParser, path: &Path) -> Result<usize, String> {
+    let mut embeds = BTreeMap::new();
+    for (key, functions) in functions(parser) {
+        let built: Vec<Value> = functions.iter()
+            .filter_map(|(class_op, function)| embed(parser, class_op.as_deref(), function))
+            .collect();
+        if !built.is_empty() {
+            embeds.insert(key, built);
+        }
+    }
+
+    write_file(path, &(serde_json::to_string_pretty(&embeds).unwrap() + "\n"))?;
+    Ok(embeds.len())
+}
+
+// Every function by key, with what its heading is prefixed by. Overloads share a
+// heading on class pages so only the last is kept, extension overloads are all listed.
+fn functions(parser: &DocParser) -> BTreeMap<String, Vec<(Option<String>, Function)>> {
+    let mut functions: BTreeMap<String, Vec<(Option<String>, Function)>> = BTreeMap::new();
+
+    for class in parser.classes() {
+        let member_class = format!("<{}>", class.name);
+        let methods = class.methods.into_iter().flatten().map(|method| (member_class.clone(), method));
+        let statics = class.static_methods.into_iter().flatten().map(|method| (class.name.clone(), method));
+        for (class_op, function) in methods.chain(statics) {
+            functions.insert(format!("{}.{}", class.name, function.name), vec![(Some(class_op), function)]);
+        }
+    }
+
+    for (_, extension) in parser.extensions() {
+        for function in extension {
+            functions.entry(function.name.clone()).or_default().push((None, function));
+        }
+    }
+
+    functions
+}
+
+// An embed with the signature as its title and the parameters, return value,
+// errors and examples as fields, all within Discord's limits
+fn embed(parser: &DocParser, class_op: Option<&str>, function: &Function) -> Option<Value> {
+    let examples = function.examples.as_ref()?;
+    let locale = &parser.options.locale;
+
+    let mut title = String::new();
+    if let Some(class) = class_op {
+        title.push_str(&parser.owner_name(class));
+        title.push('.');
+    }
+    title.push_str(&function.name);
+    title.push('(');
+    title.push_str(&function.params.iter().flatten().map(|param| param.name.as_str()).collect::<Vec<&str>>().join(", "));
+    title.push(')');
+
+    let mut description = String::new();
+    if let Some(deprecation) = &function.deprecated {
+        description.push_str(&format!("**{}**: {}\n\n", locale.deprecated, deprecation.join("\n")));
+    }
+    description.push_str(&function.desc.iter().flatten().cloned().collect::<Vec<String>>().join("\n"));
+
+    let mut fields = Vec::new();
+    for param in function.params.iter().flatten() {
+        fields.push((format!("{} - {} ({})", locale.parameter, param.type_name, param.name), param.desc.clone()));
+    }
+    if let Some(returns) = &function.returns {
+        fields.push((format!("{} - {}", locale.returns, returns.type_name), returns.desc.clone()));
+    }
+    if let Some(throws) = &function.throws {
+        let errors: Vec<String> = throws.iter().map(|throw| format!("`{}`", throw.text())).collect();
+        fields.push((format!("{} - {}", locale.throws, locale.error), errors.join("\n")));
+    }
+    let code: Vec<String> = examples.iter().map(|example| match example.output() {
+        Some(output) => format!("```kt\n{}\n```\n```text\n{}\n```", example.code().trim_end(), output.trim_end()),
+        None => format!("```kt\n{}\n```", example.code().trim_end())
+    }).collect();
+    let example = if examples.len() > 1 { &locale.examples } else { &locale.example };
+    fields.push((example.clone(), fit(&code.join("\n"), FIELD_VALUE_LIMIT)));
+
+    let title = truncate(&title, TITLE_LIMIT);
+    let fields: Vec<(String, String)> = fields.into_iter().take(FIELD_COUNT_LIMIT).map(|(name, value)| {
+        let value = if value.trim().is_empty() { "-".to_string() } else { truncate(&value, FIELD_VALUE_LIMIT) };
+        (truncate(&name, FIELD_NAME_LIMIT), value)
+    }).collect();
+
+    // The description gives way when everything together is too long
+    let used = title.chars().count() + fields.iter().map(|(name, value)| name.chars().count() + value.chars().count()).sum::<usize>();
+    let description = truncate(&description, DESCRIPTION_LIMIT.min(EMBED_LIMIT.saturating_sub(used)));
+
+    Some(json!({
+        "title": title,
+        "description": description,
+        "fields": fields.into_iter().map(|(name, value)| json!({ "name": name, "value": value })).collect::<Vec<Value>>()
+    }))
+}
+
+fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    text.chars().take(limit.saturating_sub(1)).chain(['…']).collect()
+}
+
+// Cuts the markdown at a line boundary so it is at most `limit` characters,
+// closing an example that was cut off
+fn fit(md: &str, limit: usize) -> String {
+    if md.chars().count() <= limit {
+        return md.to_string();
+    }
+
+    // Room for a closing fence and the ellipsis
+    let reserved = 5;
+    let mut fitted = String::new();
+    let mut length = 0;
+    let mut fenced = false;
+    for line in md.lines() {
+        let line_length = line.chars().count() + 1;
+        if length + line_length + reserved > limit {
+            break;
+        }
+        if line.starts_with("```") {
+            fenced = !fenced;
+        }
+        fitted.push_str(line);
+        fitted.push('\n');
+        length += line_length;
+    }
+
+    if fitted.is_empty() {
+        return md.chars().take(limit - 1).chain(['…']).collect();
+    }
+    if fenced {
+        fitted.push_str("```\n");
+    }
+    fitted.push('…');
+    fitted
+}
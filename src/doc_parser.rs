@@ -1,205 +1,768 @@
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Instant;
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
+use crate::assets;
+use crate::config::Badges;
+use crate::examples;
+use crate::glossary;
+use crate::hooks::RenderHooks;
+use crate::locale::Locale;
+use crate::log::{self, Level};
+use crate::metadata::Metadata;
+use crate::output::Newlines;
+use crate::progress::Progress;
+use crate::sources;
+use crate::text;
 
 pub struct DocParser {
-    json: Value
+    pub options: RenderOptions,
+    json: Value,
+    warnings: Mutex<Vec<String>>,
+    anchor_collisions: Mutex<Vec<String>>
 }
 
+#[derive(Default, Clone)]
+pub struct RenderOptions {
+    pub anchors: bool,
+    pub locale: Locale,
+    pub example_indent: Option<usize>,
+    pub example_max_width: Option<usize>,
+    pub jobs: usize,
+    pub callouts: Callouts,
+    // Template of the front matter added to every page
+    pub front_matter: Option<String>,
+    // Types that are documented classes become `[[Class]]` links
+    pub wikilinks: bool,
+    // Types that are documented classes link to their page
+    pub link_types: bool,
+    // Where `path:line` sources are linked to, such as a GitHub blob URL
+    pub source_url: Option<String>,
+    pub layout: Layout,
+    // Split pages of classes with a `module` go in a directory named after it
+    pub modules: bool,
+    pub newlines: Newlines,
+    // Descriptions are joined into paragraphs wrapped at this width, 0 doesn't wrap them
+    pub reflow: Option<usize>,
+    // Return types that mean nothing is returned, their Returns line is left out
+    pub void_types: Vec<String>,
+    // `getX`/`setX` method pairs are documented together as a property `x`
+    pub accessors: bool,
+    // Example output goes in a block of its own after the code, or in comments at the end of it
+    pub output_comments: bool,
+    pub badges: Badges,
+    // Assignable and read-only members are listed apart, read-only ones labelled as such
+    pub group_members: bool,
+    // Template of the anchor keys of items, such as `{class}-{name}-{arity}`
+    pub anchor_key: Option<String>,
+    // Called around each item rendered, in the order they were added
+    pub hooks: Vec<Arc<dyn RenderHooks>>,
+    pub instance_names: InstanceNames,
+    // Pages of classes and extensions are named in lowercase, for hosts with case-sensitive paths
+    pub lowercase_files: bool,
+    // Classes and extensions put first in this order, ahead of any weights
+    pub order: Vec<String>,
+    // Template of the paths of split pages, such as `docs/{kind}/{category}/{name}.md`
+    pub out: Option<String>,
+    // Ends markdown pages with a comment recording how they were generated
+    pub metadata: Option<Metadata>
+}
+
+impl RenderOptions {
+    // Everything that changes how a page is rendered, for telling whether a page written earlier is
+    // still current. Jobs only change how fast pages are rendered, and hooks can't be compared
+    pub fn fingerprint(&self) -> String {
+        format!(
+            "{} {} {:?} {:?} {:?} {:?} {} {} {:?} {:?} {} {:?} {:?} {:?} {} {} {} {} {:?} {:?} {} {:?} {:?} {}",
+            self.anchors, serde_json::to_string(&self.locale).unwrap(), self.example_indent, self.example_max_width,
+            self.callouts, self.front_matter, self.wikilinks, self.link_types, self.source_url, self.layout, self.modules,
+            self.newlines, self.reflow, self.void_types, self.accessors, self.output_comments,
+            serde_json::to_string(&self.badges).unwrap(), self.group_members, self.anchor_key, self.instance_names,
+            self.lowercase_files, self.order, self.out,
+            // Only whether there is a comment, its timestamp alone doesn't make a page stale
+            self.metadata.is_some()
+        )
+    }
+}
+
+// Where the pages of classes and extensions are written, so pages can link to each other
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum Layout {
+    // Classes.md and Extensions.md
+    #[default]
+    Single,
+    // classes/<Class>.md and extensions/<name>.md
+    Split,
+    // classes/<Class>.html and extensions/<name>.html
+    Site,
+    // Everything in one page, so links only need the fragment
+    Combined
+}
+
+// How warnings such as deprecations are set apart from the surrounding text
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum Callouts {
+    #[default]
+    Plain,
+    GitBook
+}
+
+// How the instance an instance method or member is called on is written in headings
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum InstanceNames {
+    // `<List>.get(index)`
+    #[default]
+    Brackets,
+    // `List.get(index)`
+    Class,
+    // `list.get(index)`
+    Variable
+}
+
+// A documented item a page links to, the item is its anchor key and heading
+// unless the class or extension itself is linked
+struct Target {
+    extension: bool,
+    owner: String,
+    item: Option<(String, String)>
+}
+
+// Return types that mean nothing is returned unless `--void-types` says otherwise
+pub const VOID_TYPES: &[&str] = &["Null", "Void"];
+
+// Marks anchor ids until they are made unique once a whole file is rendered
+const ANCHOR_MARK: char = '\u{1}';
+
+// Classes or extensions a rendering thread takes at a time
+const WORKER_BATCH: usize = 16;
+
+#[derive(Deserialize)]
+pub struct Class {
+    pub name: String,
+    pub desc: Option<Vec<String>>,
+    pub import_path: Option<String>,
+    pub import: Option<Import>,
+    pub category: Option<String>,
+    pub module: Option<String>,
+    // Lighter classes come first, those without a weight weigh 0
+    pub weight: Option<i64>,
+    pub tags: Option<Vec<String>>,
+    pub source: Option<String>,
+    pub see: Option<Vec<String>>,
+    pub notes: Option<Vec<String>>,
+    pub warnings: Option<Vec<String>>,
+    pub links: Option<Vec<Link>>,
+    pub assets: Option<Vec<String>>,
+    pub authors: Option<Vec<String>>,
+    pub experimental: Option<bool>,
+    pub static_members: Option<Vec<Member>>,
+    pub members: Option<Vec<Member>>,
+    pub constructors: Option<Vec<Constructor>>,
+    pub methods: Option<Vec<Function>>,
+    pub static_methods: Option<Vec<Function>>
+}
+
+// How a class is imported, for classes imported under a name other than their own
+#[derive(Deserialize)]
+pub struct Import {
+    pub path: String,
+    pub name: Option<String>
+}
+
+impl Class {
+    // The name a class is imported as and the path it's imported from, if it needs importing
+    pub fn import(&self) -> Option<(&str, &str)> {
+        match (&self.import, &self.import_path) {
+            (Some(import), _) => Some((import.name.as_deref().unwrap_or(&self.name), &import.path)),
+            (None, Some(path)) => Some((&self.name, path)),
+            (None, None) => None
+        }
+    }
+}
+
+// Another name for a type, listed on the type aliases page
+pub struct TypeAlias {
+    pub name: String,
+    pub type_name: String,
+    pub desc: Vec<String>
+}
+
+// A page elsewhere about a class, such as a tutorial or an example script
 #[derive(Deserialize)]
-struct Class {
-    name: String,
-    desc: Option<Vec<String>>,
-    import_path: Option<String>,
-    static_members: Option<Vec<Member>>,
-    members: Option<Vec<Member>>,
-    constructors: Option<Vec<Constructor>>,
-    methods: Option<Vec<Function>>,
-    static_methods: Option<Vec<Function>>
+pub struct Link {
+    pub title: String,
+    pub url: String
 }
 
 #[derive(Deserialize)]
-struct Function {
-    name: String,
-    deprecated: Option<Vec<String>>,
-    desc: Option<Vec<String>>,
-    params: Option<Vec<Param>>,
-    returns: Option<Return>,
-    throws: Option<Vec<String>>,
-    examples: Option<Vec<String>>
+pub struct Function {
+    pub name: String,
+    pub deprecated: Option<Vec<String>>,
+    pub desc: Option<Vec<String>>,
+    pub params: Option<Vec<Param>>,
+    pub returns: Option<Return>,
+    pub throws: Option<Vec<Throw>>,
+    pub assets: Option<Vec<String>>,
+    pub examples: Option<Vec<CodeExample>>,
+    pub source: Option<String>,
+    pub see: Option<Vec<String>>,
+    pub notes: Option<Vec<String>>,
+    pub warnings: Option<Vec<String>>,
+    pub experimental: Option<bool>
 }
 
+// An example, either just its code or the code with the output it prints
+// and the variant, such as `client` or `server`, it is for
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+pub enum CodeExample {
+    Code(String),
+    Detailed { code: String, output: Option<String>, variant: Option<String> }
+}
+
+impl CodeExample {
+    pub fn code(&self) -> &str {
+        match self {
+            CodeExample::Code(code) | CodeExample::Detailed { code, .. } => code
+        }
+    }
+
+    pub fn output(&self) -> Option<&str> {
+        match self {
+            CodeExample::Code(_) => None,
+            CodeExample::Detailed { output, .. } => output.as_deref()
+        }
+    }
+
+    pub fn variant(&self) -> Option<&str> {
+        match self {
+            CodeExample::Code(_) => None,
+            CodeExample::Detailed { variant, .. } => variant.as_deref()
+        }
+    }
+}
+
+// An error a function throws, either just its message or the error's class with
+// when it's thrown
 #[derive(Deserialize)]
-struct Constructor {
-    desc: Vec<String>,
-    params: Option<Vec<Param>>,
-    examples: Vec<String>
+#[serde(untagged)]
+pub enum Throw {
+    Message(String),
+    Error(ThrownError)
 }
 
 #[derive(Deserialize)]
-struct Member {
-    name: String,
-    assignable: Option<bool>,
-    desc: Option<Vec<String>>,
+pub struct ThrownError {
     #[serde(rename = "type")]
-    type_name: Option<String>,
-    examples: Option<Vec<String>>
+    pub type_name: String,
+    pub message: Option<String>,
+    pub condition: Option<String>
+}
+
+impl Throw {
+    // The error as a line of plain text
+    pub fn text(&self) -> String {
+        match self {
+            Throw::Message(message) => format!("'{}'", message),
+            Throw::Error(error) => {
+                let mut text = error.type_name.clone();
+                if let Some(message) = &error.message {
+                    text.push_str(&format!(" '{}'", message));
+                }
+                if let Some(condition) = &error.condition {
+                    text.push_str(&format!(" when {}", condition));
+                }
+                text
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Constructor {
+    pub desc: Vec<String>,
+    pub params: Option<Vec<Param>>,
+    pub examples: Vec<CodeExample>
 }
 
 #[derive(Deserialize)]
-struct Param {
-    name: String,
+pub struct Member {
+    pub name: String,
+    pub assignable: Option<bool>,
+    pub desc: Option<Vec<String>>,
     #[serde(rename = "type")]
-    type_name: String,
-    desc: String
+    pub type_name: Option<String>,
+    // The constant value of a static member, such as `3.141592653589793` for `Math.pi`
+    pub value: Option<Value>,
+    pub examples: Option<Vec<CodeExample>>
 }
 
 #[derive(Deserialize)]
-struct Return {
+pub struct Param {
+    pub name: String,
     #[serde(rename = "type")]
-    type_name: String,
-    desc: String
+    pub type_name: String,
+    pub desc: String
+}
+
+#[derive(Deserialize)]
+pub struct Return {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub desc: String
 }
 
 impl DocParser {
-    pub fn new(path: &str) -> DocParser {
-        let content = fs::read_to_string(path).unwrap();
+    // Reads the document at the path, failing with the path if it can't be read or isn't JSON
+    pub fn new(path: &str) -> Result<DocParser, String> {
+        let content = fs::read_to_string(path).map_err(|error| format!("Could not read {}: {}", path, error))?;
+        let json = serde_json::from_str(&content).map_err(|error| format!("{}: {}", path, error))?;
+        Ok(DocParser::from_json(json))
+    }
+
+    pub fn from_json(json: Value) -> DocParser {
         DocParser {
-            json: serde_json::from_str(&content).unwrap()
+            options: RenderOptions::default(),
+            json,
+            warnings: Mutex::new(Vec::new()),
+            anchor_collisions: Mutex::new(Vec::new())
         }
     }
 
-    pub fn parse_extensions(&self) -> String {
-        let extensions = self.json["extensions"].as_object().unwrap();
+    pub fn json(&self) -> &Value {
+        &self.json
+    }
 
-        let mut map = BTreeMap::new();
-        for (string, value) in extensions {
-            map.insert(string, value);
+    pub fn json_mut(&mut self) -> &mut Value {
+        &mut self.json
+    }
+
+    pub fn warn(&self, warning: String) {
+        self.warnings.lock().unwrap().push(warning);
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings.lock().unwrap().clone()
+    }
+
+    pub fn anchor_collisions(&self) -> Vec<String> {
+        self.anchor_collisions.lock().unwrap().clone()
+    }
+
+    // Those credited with the whole document
+    pub fn authors(&self) -> Vec<String> {
+        let authors = self.json["authors"].as_array().into_iter().flatten();
+        authors.filter_map(|author| author.as_str().map(str::to_string)).collect()
+    }
+
+    // Keeps only the deprecated functions, for a migration guide, or only those that aren't,
+    // for a clean reference. Extensions left empty are removed, as are classes without
+    // deprecated functions when only those are kept.
+    pub fn filter_deprecated(&mut self, deprecated: bool) {
+        let keep = |function: &Value| function["deprecated"].is_null() != deprecated;
+        let has_functions = |value: &Value| value.as_array().is_some_and(|functions| !functions.is_empty());
+        if let Some(classes) = self.json["classes"].as_object_mut() {
+            for class in classes.values_mut() {
+                for section in ["methods", "static_methods"] {
+                    if let Some(functions) = class.get_mut(section).and_then(Value::as_array_mut) {
+                        functions.retain(keep);
+                    }
+                }
+                // Only functions can be deprecated
+                if let Some(class) = class.as_object_mut().filter(|_| deprecated) {
+                    for section in ["static_members", "members", "constructors"] {
+                        class.remove(section);
+                    }
+                }
+            }
+            if deprecated {
+                classes.retain(|_, class| has_functions(&class["methods"]) || has_functions(&class["static_methods"]));
+            }
         }
+        if let Some(extensions) = self.json["extensions"].as_object_mut() {
+            for functions in extensions.values_mut().filter_map(Value::as_array_mut) {
+                functions.retain(keep);
+            }
+            extensions.retain(|_, functions| has_functions(functions));
+        }
+    }
 
-        let mut iter = map.iter().peekable();
-        let mut md = String::new();
+    // Removes experimental classes and functions, for documentation of the stable API
+    pub fn remove_experimental(&mut self) {
+        let experimental = |value: &Value| value["experimental"] == true;
+        if let Some(classes) = self.json["classes"].as_object_mut() {
+            classes.retain(|_, class| !experimental(class));
+            for class in classes.values_mut() {
+                for section in ["methods", "static_methods"] {
+                    if let Some(functions) = class.get_mut(section).and_then(Value::as_array_mut) {
+                        functions.retain(|function| !experimental(function));
+                    }
+                }
+            }
+        }
+        if let Some(extensions) = self.json["extensions"].as_object_mut() {
+            for functions in extensions.values_mut().filter_map(Value::as_array_mut) {
+                functions.retain(|function| !experimental(function));
+            }
+        }
+    }
+
+    // Leaves out the examples of variants other than those given, examples without a variant are kept
+    pub fn keep_variants(&mut self, variants: &[String]) {
+        fn retain(value: &mut Value, variants: &[String]) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(examples) = map.get_mut("examples").and_then(Value::as_array_mut) {
+                        examples.retain(|example| {
+                            example["variant"].as_str().is_none_or(|variant| variants.iter().any(|kept| kept == variant))
+                        });
+                    }
+                    map.values_mut().for_each(|child| retain(child, variants));
+                }
+                Value::Array(array) => array.iter_mut().for_each(|child| retain(child, variants)),
+                _ => { }
+            }
+        }
+        retain(&mut self.json, variants);
+    }
 
-        while let Some(extension) = iter.next() {
-            let name = extension.0;
-            let functions = extension.1.as_array().unwrap();
-            md.push_str(&DocParser::parse_extension(name, functions));
+    pub fn filter_classes(&mut self, include: &[String], exclude: &[String]) {
+        if let Some(classes) = self.json["classes"].as_object_mut() {
+            classes.retain(|name, _| {
+                (include.is_empty() || include.contains(name)) && !exclude.contains(name)
+            });
+        }
+    }
 
-            if iter.peek().is_some() {
-                md.push_str("\n\n");
+    // Each class, and each item within a class, is deserialized on its own
+    // so that one malformed entry only removes itself from the output
+    pub fn classes(&self) -> Vec<Class> {
+        let mut map = BTreeMap::new();
+        if let Some(classes) = self.json["classes"].as_object() {
+            for (string, value) in classes {
+                map.insert(string, value);
             }
         }
 
-        md
+        let mut parsed: Vec<Class> = Vec::new();
+        for (name, value) in map {
+            let path = format!("$.classes.{}", name);
+            let mut value = value.clone();
+
+            if value.is_object() {
+                self.retain_valid::<Member>(&mut value["static_members"], &format!("{}.static_members", path));
+                self.retain_valid::<Member>(&mut value["members"], &format!("{}.members", path));
+                self.retain_valid::<Constructor>(&mut value["constructors"], &format!("{}.constructors", path));
+                self.retain_valid::<Function>(&mut value["methods"], &format!("{}.methods", path));
+                self.retain_valid::<Function>(&mut value["static_methods"], &format!("{}.static_methods", path));
+            }
+
+            match serde_json::from_value(value) {
+                Ok(class) => parsed.push(class),
+                Err(error) => self.warn(format!("{}: {}", path, error))
+            }
+        }
+
+        // Classes of a category are kept together, those without one come last. Categories
+        // go in the order of their first class, and classes within them by their rank.
+        let mut categories: HashMap<Option<String>, (usize, i64)> = HashMap::new();
+        for class in &parsed {
+            let rank = self.rank(&class.name, class.weight);
+            categories.entry(class.category.clone()).and_modify(|first| *first = rank.min(*first)).or_insert(rank);
+        }
+        parsed.sort_by_cached_key(|class| {
+            (class.category.is_none(), categories[&class.category], class.category.clone(), self.rank(&class.name, class.weight))
+        });
+        parsed
     }
 
-    pub fn parse_classes(&self) -> String {
-        let classes = self.json["classes"].as_object().unwrap();
+    // Where a class or extension goes among the others, by the configured order and then
+    // its weight, with ties left in name order
+    fn rank(&self, name: &str, weight: Option<i64>) -> (usize, i64) {
+        let position = self.options.order.iter().position(|ordered| ordered == name);
+        (position.unwrap_or(usize::MAX), weight.unwrap_or(0))
+    }
 
+    fn retain_valid<T: DeserializeOwned>(&self, array: &mut Value, path: &str) {
+        if let Some(items) = array.as_array_mut() {
+            let mut i = 0;
+            items.retain(|item| {
+                let result = serde_json::from_value::<T>(item.clone());
+                if let Err(error) = &result {
+                    self.warn(format!("{}[{}]: {}", path, i, error));
+                }
+                i += 1;
+                result.is_ok()
+            });
+        }
+    }
+
+    pub fn extensions(&self) -> Vec<(String, Vec<Function>)> {
         let mut map = BTreeMap::new();
-        for (string, value) in classes {
-            map.insert(string, value);
+        if let Some(extensions) = self.json["extensions"].as_object() {
+            for (string, value) in extensions {
+                map.insert(string, value);
+            }
         }
 
-        let mut iter = map.values().peekable();
-        let mut md = String::new();
+        let mut parsed = Vec::new();
+        for (name, value) in map {
+            let path = format!("$.extensions.{}", name);
+            let mut value = value.clone();
+            if !value.is_array() {
+                self.warn(format!("{}: expected an array of functions", path));
+                continue;
+            }
 
-        while let Some(class) = iter.next() {
-            md.push_str(&DocParser::parse_class(class));
+            self.retain_valid::<Function>(&mut value, &path);
+            parsed.push((name.clone(), serde_json::from_value(value).unwrap()));
+        }
+
+        // Extensions have no fields of their own, so their weights are kept beside them
+        parsed.sort_by_cached_key(|(name, _)| self.rank(name, self.json["extension_weights"][name].as_i64()));
+        parsed
+    }
+
+    pub fn write_extensions(&self, out: &mut impl Write) -> io::Result<()> {
+        self.write_rendered(out, "Rendering extensions", self.extensions(), |(name, functions)| self.parse_extension(&name, &functions))
+    }
+
+    pub fn write_classes(&self, out: &mut impl Write) -> io::Result<()> {
+        self.write_rendered(out, "Rendering classes", self.classes(), |class| self.parse_class(class))
+    }
+
+    // Renders a single class, anchors are kept unique among those already in `used`
+    pub fn render_class(&self, class: Class, used: &mut HashMap<String, usize>) -> String {
+        let md = self.parse_class(class);
+        self.resolve_anchors(used, md)
+    }
+
+    pub fn render_extension(&self, name: &str, functions: &[Function], used: &mut HashMap<String, usize>) -> String {
+        let md = self.parse_extension(name, functions);
+        self.resolve_anchors(used, md)
+    }
+
+    // Renders a single function on its own, None if it would be left out of the page
+    pub fn render_function(&self, class_op: Option<&str>, function: &Function) -> Option<String> {
+        let mut md = String::new();
+        self.add_function(&mut md, class_op, &function.name, function)
+            .then(|| self.resolve_anchors(&mut HashMap::new(), md))
+    }
 
-            if iter.peek().is_some() {
-                md.push_str("\n\n");
+    // Renders the items on a fixed number of worker threads and writes each to the output as soon
+    // as those before it are written, so only the items finished out of order are held in memory
+    fn write_rendered<T: Send>(&self, out: &mut impl Write, phase: &'static str, items: Vec<T>, render: impl Fn(T) -> String + Sync) -> io::Result<()> {
+        let progress = Progress::new(phase, items.len());
+        let mut used = HashMap::new();
+        let mut write = |index: usize, md: String| {
+            if index > 0 {
+                out.write_all(b"\n\n")?;
+            }
+            out.write_all(self.resolve_anchors(&mut used, md).as_bytes())?;
+            progress.advance();
+            Ok::<_, io::Error>(())
+        };
+
+        let jobs = self.options.jobs.max(1).min(items.len());
+        if jobs <= 1 {
+            for (index, item) in items.into_iter().enumerate() {
+                write(index, render(item))?;
             }
+            return Ok(());
         }
 
-        md
+        let queue = Mutex::new(items.into_iter().enumerate());
+        let (sender, receiver) = mpsc::sync_channel(jobs);
+        let (queue, render) = (&queue, &render);
+        thread::scope(|scope| {
+            for _ in 0..jobs {
+                let sender = sender.clone();
+                scope.spawn(move || loop {
+                    // Taken a few at a time so the workers rarely wait on each other, and unlocked before rendering
+                    let batch: Vec<_> = queue.lock().unwrap().by_ref().take(WORKER_BATCH).collect();
+                    let Some(&(first, _)) = batch.first() else {
+                        break;
+                    };
+                    let rendered = batch.into_iter().map(|(_, item)| render(item)).collect::<Vec<_>>();
+                    // The receiver is only gone when writing failed
+                    if sender.send((first, rendered)).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut finished = BTreeMap::new();
+            let mut next = 0;
+            for (first, rendered) in receiver {
+                finished.extend((first..).zip(rendered));
+                while let Some(md) = finished.remove(&next) {
+                    write(next, md)?;
+                    next += 1;
+                }
+            }
+            Ok(())
+        })
     }
 
-    fn parse_extension(name: &str, functions: &Vec<Value>) -> String {
+    fn parse_extension(&self, name: &str, functions: &[Function]) -> String {
+        // Only timed when the time is logged, as the clock isn't available to WebAssembly
+        let started = log::enabled(Level::Debug).then(Instant::now);
         let mut md = String::new();
 
         md.push_str("## ");
         md.push_str(name);
         md.push_str("\n\n");
 
-        for i in 0..functions.len() {
-            let f: &Value = &functions[i];
-            let function: Function = serde_json::from_value(f.clone()).unwrap();
-            let func_s = DocParser::add_function(None, &function);
-            if func_s.is_none() {
+        for (i, function) in functions.iter().enumerate() {
+            let anchor = self.anchor_key(function.name.clone(), "function", name, &function.name, arity(&function.params));
+            if !self.add_function(&mut md, None, &anchor, function) {
                 continue;
             }
 
-            md.push_str(&func_s.unwrap());
-
             if i + 1 < functions.len() {
                 md.push('\n');
             }
         }
 
+        if let Some(started) = started {
+            log::debug(&format!("Rendered extension {} in {:.2?}", name, started.elapsed()));
+        }
         md
     }
 
-    fn parse_class(class_v: &Value) -> String {
+    fn parse_class(&self, class: Class) -> String {
+        // Only timed when the time is logged, as the clock isn't available to WebAssembly
+        let started = log::enabled(Level::Debug).then(Instant::now);
         let mut md = String::new();
+        for hooks in &self.options.hooks {
+            hooks.before_class(&class, &mut md);
+        }
 
-        let class: Class = serde_json::from_value(class_v.to_owned()).unwrap();
+        let locale = &self.options.locale;
 
         // Class name
+        self.add_anchor(&mut md, &class.name);
         md.push_str("# ");
-        md.push_str(&class.name);
-        md.push_str(" class\n");
-        md.push_str(&class.name);
-        md.push_str(" class for Arucas.\n\n");
+        md.push_str(&locale.class_heading.replace("{class}", &class.name));
+        md.push_str(&self.badges(false, class.experimental.unwrap_or(false), false));
+        md.push('\n');
+        md.push_str(&locale.class_summary.replace("{class}", &class.name));
+        md.push_str("\n\n");
 
         // Class description
-        if let Some(desc) = class.desc {
-            DocParser::add_from_string_array(&mut md, &desc);
+        if let Some(desc) = &class.desc {
+            self.add_text(&mut md, "", 0, &self.asset_links(desc, &class.assets));
             md.push('\n');
         }
+        self.add_images(&mut md, ("", "\n\n"), &class.assets, class.desc.as_deref().unwrap_or_default());
+
+        self.add_admonitions(&mut md, class.experimental.unwrap_or(false), &class.warnings, &class.notes);
 
         // Class import path (if needed)
-        if let Some(import_path) = class.import_path {
-            md.push_str("Import with `import ");
-            md.push_str(&class.name);
-            md.push_str(" from ");
-            md.push_str(&import_path);
-            md.push_str(";`\n\n");
+        if let Some((name, path)) = class.import() {
+            md.push_str(&locale.import.replace("{class}", name).replace("{path}", path));
+            md.push_str("\n\n");
         }
         else {
-            md.push_str("Class does not need to be imported.\n\n");
+            md.push_str(&locale.no_import);
+            md.push_str("\n\n");
         }
 
-        md.push_str("Fully Documented.\n\n");
+        if let Some(module) = &class.module {
+            md.push_str(&locale.module);
+            md.push_str(": `");
+            md.push_str(module);
+            md.push_str("`\n\n");
+        }
+
+        if let Some(category) = &class.category {
+            md.push_str(&locale.category);
+            md.push_str(": ");
+            md.push_str(category);
+            md.push_str("\n\n");
+        }
+
+        if let Some(tags) = class.tags.as_ref().filter(|tags| !tags.is_empty()) {
+            md.push_str(&locale.tags);
+            md.push_str(": ");
+            md.push_str(&tags.iter().map(|tag| format!("`{}`", tag)).collect::<Vec<String>>().join(", "));
+            md.push_str("\n\n");
+        }
+
+        if let Some(source) = &class.source {
+            md.push_str(&format!("[{}]({})", locale.view_source, sources::url(source, self.options.source_url.as_deref())));
+            md.push_str("\n\n");
+        }
+
+        if let Some(see) = class.see.as_ref().filter(|see| !see.is_empty()) {
+            md.push_str(&locale.see_also);
+            md.push_str(": ");
+            md.push_str(&self.see_also(&class.name, see));
+            md.push_str("\n\n");
+        }
+
+        md.push_str(&locale.fully_documented);
+        md.push_str("\n\n");
+
+        if let Some(links) = class.links.as_ref().filter(|links| !links.is_empty()) {
+            DocParser::add_section(&mut md, &locale.resources);
+            for link in links {
+                md.push_str(&format!("- [{}]({})\n", link.title, link.url));
+            }
+            md.push('\n');
+        }
 
         // Static members of the class
-        if let Some(mut statics) = class.static_members {
+        if let Some(statics) = &class.static_members {
             if !statics.is_empty() {
-                md.push_str("## Static Members\n\n");
-                DocParser::add_member(&mut md, &class.name, &mut statics);
+                DocParser::add_section(&mut md, &locale.static_members);
+                self.add_values(&mut md, &class.name, statics);
+                self.add_member(&mut md, &class.name, true, statics);
                 md.push('\n');
             }
         }
 
         // Instance members (wrappers)
-        if let Some(mut members) = class.members {
+        if let Some(members) = &class.members {
             if !members.is_empty() {
-                let member_class = String::new() + "<" + &class.name + ">";
-                md.push_str("## Members\n\n");
-                DocParser::add_member(&mut md, &member_class, &mut members);
+                DocParser::add_section(&mut md, &locale.members);
+                self.add_member(&mut md, &class.name, false, members);
                 md.push('\n');
             }
         }
 
         // Constructors
-        if let Some(constructors) = class.constructors {
+        if let Some(constructors) = &class.constructors {
             if !constructors.is_empty() {
-                md.push_str("## Constructors\n\n");
-
-                let mut iter = constructors.into_iter().peekable();
+                DocParser::add_section(&mut md, &locale.constructors);
+
+                // Overloads are easier to compare side by side before their details
+                if constructors.len() > 1 {
+                    for constructor in constructors {
+                        let params = constructor.params.as_deref().unwrap_or_default();
+                        let mut heading = format!("new {}(", class.name);
+                        DocParser::add_params_in_function(&mut heading, params);
+                        heading.push(')');
+                        let anchor = DocParser::constructor_anchor(&class.name, constructors.len(), params.len());
+                        let anchor = self.anchor_key(anchor, "constructor", &class.name, "new", params.len());
+                        let link = self.link(false, &class.name, Some((&anchor, &heading)));
+                        md.push_str(&format!("- [`{}`]({}{})\n", heading, self.root(), link));
+                    }
+                    md.push('\n');
+                }
 
-                while let Some(constructor) = iter.next() {
+                for constructor in constructors {
+                    let params = constructor.params.as_deref().unwrap_or_default();
+                    let anchor = DocParser::constructor_anchor(&class.name, constructors.len(), params.len());
+                    self.add_anchor(&mut md, &self.anchor_key(anchor, "constructor", &class.name, "new", params.len()));
                     md.push_str("### `new ");
                     md.push_str(&class.name);
                     md.push('(');
@@ -210,66 +773,163 @@ impl DocParser {
 
                     md.push_str(")`\n");
 
-                    DocParser::add_description(&mut md, &constructor.desc);
+                    self.add_description(&mut md, &constructor.desc);
 
                     if let Some(params) = &constructor.params {
-                        DocParser::add_params(&mut md, params);
+                        self.add_params(&mut md, params);
                     }
 
-                    DocParser::add_examples(&mut md, &constructor.examples);
+                    self.add_examples(&mut md, &constructor.examples);
                 }
-                md.push_str("\n");
+                md.push('\n');
             }
         }
 
         // Methods
-        if let Some(methods) = class.methods {
-            if !methods.is_empty() {
-                md.push_str("## Methods\n\n");
-                let member_class = String::new() + "<" + &class.name + ">";
-                let map = DocParser::order_functions(methods);
-                let mut iter = map.values().into_iter().peekable();
+        if let Some(methods) = &class.methods {
+            let member_class = String::new() + "<" + &class.name + ">";
+            let mut map = DocParser::order_functions(methods);
+
+            // Accessor pairs, taken out of the methods
+            let properties = if self.options.accessors { DocParser::take_accessors(&mut map) } else { Vec::new() };
+            if !properties.is_empty() {
+                DocParser::add_section(&mut md, &locale.properties);
+                let mut iter = properties.iter().peekable();
+                while let Some((name, getter, setter)) = iter.next() {
+                    self.add_property(&mut md, &class.name, &member_class, name, getter, setter);
+                    if iter.peek().is_some() {
+                        md.push('\n');
+                    }
+                }
+                md.push('\n');
+            }
+
+            if !map.is_empty() {
+                DocParser::add_section(&mut md, &locale.methods);
+                let mut iter = map.values().peekable();
                 while let Some(value) = iter.next() {
-                    let func_s = DocParser::add_function(Some(&member_class), value);
-                    if func_s.is_none() {
+                    let anchor = class.name.clone() + "-" + &value.name;
+                    let anchor = self.anchor_key(anchor, "method", &class.name, &value.name, arity(&value.params));
+                    if !self.add_function(&mut md, Some(&member_class), &anchor, value) {
                         continue;
                     }
 
-                    md.push_str(&func_s.unwrap());
-
                     if iter.peek().is_some() {
-                        md.push_str("\n");
+                        md.push('\n');
                     }
                 }
-                md.push_str("\n");
+                md.push('\n');
             }
         }
 
         // Static methods
-        if let Some(static_methods) = class.static_methods {
+        if let Some(static_methods) = &class.static_methods {
             if !static_methods.is_empty() {
-                md.push_str("## Static Methods\n\n");
+                DocParser::add_section(&mut md, &locale.static_methods);
                 let map = DocParser::order_functions(static_methods);
-                let mut iter = map.values().into_iter().peekable();
+                let mut iter = map.values().peekable();
                 while let Some(value) = iter.next() {
-                    let func_s = DocParser::add_function(Some(&class.name), value);
-                    if func_s.is_none() {
+                    let anchor = class.name.clone() + "-static-" + &value.name;
+                    let anchor = self.anchor_key(anchor, "static method", &class.name, &value.name, arity(&value.params));
+                    if !self.add_function(&mut md, Some(&class.name), &anchor, value) {
                         continue;
                     }
 
-                    md.push_str(&func_s.unwrap());
-
                     if iter.peek().is_some() {
-                        md.push_str("\n");
+                        md.push('\n');
                     }
                 }
             }
         }
 
+        // Attribution goes at the very end of the page
+        if let Some(authors) = class.authors.as_ref().filter(|authors| !authors.is_empty()) {
+            while !md.ends_with("\n\n") {
+                md.push('\n');
+            }
+            md.push_str(&locale.contributed_by.replace("{authors}", &authors.join(", ")));
+            md.push('\n');
+        }
+
+        for hooks in &self.options.hooks {
+            hooks.after_class(&class, &mut md);
+        }
+        if let Some(started) = started {
+            log::debug(&format!("Rendered class {} in {:.2?}", class.name, started.elapsed()));
+        }
         md
     }
 
-    fn order_functions(functions: Vec<Function>) -> BTreeMap<String, Function> {
+    // Removes the `getX` and `setX` methods that make up a property `x`, only those with nothing
+    // but a description and examples are paired so no documentation is lost
+    fn take_accessors<'a>(methods: &mut BTreeMap<String, &'a Function>) -> Vec<(String, &'a Function, &'a Function)> {
+        let plain = |function: &Function| {
+            function.examples.is_some() && function.deprecated.is_none() && !function.experimental.unwrap_or(false)
+                && function.throws.as_ref().is_none_or(Vec::is_empty)
+                && function.warnings.as_ref().is_none_or(Vec::is_empty)
+                && function.notes.as_ref().is_none_or(Vec::is_empty)
+        };
+
+        let names: Vec<String> = methods.keys().filter(|name| {
+            name.strip_prefix("get").and_then(|rest| rest.chars().next()).is_some_and(char::is_uppercase)
+        }).cloned().collect();
+
+        let mut properties = Vec::new();
+        for getter_name in names {
+            let setter_name = format!("set{}", &getter_name[3..]);
+            let (Some(getter), Some(setter)) = (methods.get(&getter_name), methods.get(&setter_name)) else {
+                continue;
+            };
+            let getter_params = getter.params.as_ref().map_or(0, Vec::len);
+            let setter_params = setter.params.as_ref().map_or(0, Vec::len);
+            if getter_params != 0 || setter_params != 1 || getter.returns.is_none() || !plain(getter) || !plain(setter) {
+                continue;
+            }
+
+            let mut chars = getter_name[3..].chars();
+            let name = chars.next().unwrap().to_lowercase().chain(chars).collect();
+            properties.push((name, methods.remove(&getter_name).unwrap(), methods.remove(&setter_name).unwrap()));
+        }
+        properties
+    }
+
+    // A getter and setter as a single property, anchors of both methods still lead to it
+    fn add_property(&self, md: &mut String, class: &str, member_class: &str, name: &str, getter: &Function, setter: &Function) {
+        let locale = &self.options.locale;
+
+        for function in [getter, setter] {
+            let anchor = format!("{}-{}", class, function.name);
+            self.add_anchor(md, &self.anchor_key(anchor, "method", class, &function.name, arity(&function.params)));
+        }
+        let member_class = self.owner_name(member_class);
+        md.push_str("### `");
+        md.push_str(&member_class);
+        md.push('.');
+        md.push_str(name);
+        md.push_str("`\n");
+
+        if let Some(returns) = &getter.returns {
+            md.push_str("- ");
+            md.push_str(&locale.type_name);
+            md.push_str(" - ");
+            md.push_str(&self.type_reference(&returns.type_name));
+            md.push_str(": ");
+            md.push_str(&returns.desc);
+            md.push('\n');
+        }
+
+        for (label, function) in [(&locale.get, getter), (&locale.set, setter)] {
+            let mut signature = String::new();
+            DocParser::add_params_in_function(&mut signature, function.params.as_deref().unwrap_or_default());
+            let prefix = format!("- {} - `{}.{}({})`: ", label, member_class, function.name, signature);
+            self.add_text(md, &prefix, 2, function.desc.as_deref().unwrap_or_default());
+        }
+
+        let examples: Vec<CodeExample> = getter.examples.iter().chain(&setter.examples).flatten().cloned().collect();
+        self.add_examples(md, &examples);
+    }
+
+    fn order_functions(functions: &[Function]) -> BTreeMap<String, &Function> {
         let mut map = BTreeMap::new();
         for function in functions {
             map.insert(function.name.to_string(), function);
@@ -277,62 +937,204 @@ impl DocParser {
         map
     }
 
-    fn add_function(class_op: Option<&str>, function: &Function) -> Option<String> {
+    // Writes the function straight into the page rather than a string of its own,
+    // returns false if it was left out
+    fn add_function(&self, md: &mut String, class_op: Option<&str>, anchor: &str, function: &Function) -> bool {
         // Every function should have an example
-        if function.examples.is_none() {
-            return None;
+        let Some(examples) = &function.examples else {
+            if log::enabled(Level::Debug) {
+                log::debug(&format!("{}: left out as it has no examples", DocParser::location(class_op, &function.name)));
+            }
+            return false;
+        };
+        if log::enabled(Level::Trace) {
+            log::trace(&format!("Rendering {}", DocParser::location(class_op, &function.name)));
         }
 
-        let mut md = String::new();
-
+        for hooks in &self.options.hooks {
+            hooks.before_function(class_op, function, md);
+        }
+        self.add_anchor(md, anchor);
         md.push_str("### `");
         if let Some(class) = class_op {
-            md.push_str(class);
+            md.push_str(&self.owner_name(class));
             md.push('.');
         }
         md.push_str(&function.name);
         md.push('(');
 
         if let Some(params) = &function.params {
-            DocParser::add_params_in_function(&mut md, params)
+            DocParser::add_params_in_function(md, params)
         }
 
-        md.push_str(")`\n");
+        md.push_str(")`");
+        md.push_str(&self.badges(function.deprecated.is_some(), function.experimental.unwrap_or(false), false));
+        md.push('\n');
+
+        let locale = &self.options.locale;
+
+        self.add_admonitions(md, function.experimental.unwrap_or(false), &function.warnings, &function.notes);
 
         if let Some(deprecation) = &function.deprecated {
-            md.push_str("- Deprecated: ");
-            DocParser::add_from_string_array(&mut md, deprecation);
+            match self.options.callouts {
+                Callouts::Plain => {
+                    md.push_str("- ");
+                    md.push_str(&locale.deprecated);
+                    md.push_str(": ");
+                    DocParser::add_from_string_array(md, deprecation);
+                }
+                Callouts::GitBook => {
+                    md.push_str("{% hint style=\"warning\" %}\n");
+                    md.push_str(&locale.deprecated);
+                    md.push_str(": ");
+                    DocParser::add_from_string_array(md, deprecation);
+                    md.push_str("{% endhint %}\n\n");
+                }
+            }
         }
 
-        DocParser::add_description(&mut md, &function.desc.as_ref().unwrap());
+        // Lenient input may leave out the description, the rest of the function is still shown
+        let desc = function.desc.as_deref().unwrap_or_default();
+        self.add_description(md, &self.asset_links(desc, &function.assets));
+        self.add_images(md, ("- ", "\n"), &function.assets, desc);
 
         if let Some(params) = &function.params {
-            DocParser::add_params(&mut md, params);
+            self.add_params(md, params);
         }
 
-        if let Some(returns) = &function.returns {
-            md.push_str("- Returns - ");
-            md.push_str(&returns.type_name);
-            md.push_str(": ");
-            md.push_str(&returns.desc);
-            md.push_str("\n");
+        let returns = function.returns.as_ref().filter(|returns| !self.options.void_types.contains(&returns.type_name));
+        if let Some(returns) = returns {
+            md.push_str("- ");
+            md.push_str(&locale.returns);
+            md.push_str(" - ");
+            match class_op {
+                Some(class) if DocParser::returns_this(class, returns) => {
+                    md.push('`');
+                    md.push_str(&self.owner_name(class));
+                    md.push_str("`: ");
+                    md.push_str(&locale.returns_this);
+                }
+                _ => {
+                    md.push_str(&self.type_reference(&returns.type_name));
+                    md.push_str(": ");
+                    md.push_str(&returns.desc);
+                }
+            }
+            md.push('\n');
         }
 
         if let Some(throws) = &function.throws {
-            md.push_str("- Throws - Error:\n");
-            for value in throws {
-                md.push_str("  - `'");
-                md.push_str(value);
-                md.push_str("'`\n");
+            // Bare messages are all thrown as the default error class
+            let typed = throws.iter().any(|throw| matches!(throw, Throw::Error(_)));
+            md.push_str("- ");
+            md.push_str(&locale.throws);
+            if !typed {
+                md.push_str(" - ");
+                md.push_str(&locale.error);
             }
+            md.push_str(":\n");
+            for throw in throws {
+                md.push_str("  - ");
+                match throw {
+                    Throw::Message(message) => {
+                        md.push_str("`'");
+                        md.push_str(message);
+                        md.push_str("'`");
+                    }
+                    Throw::Error(error) => {
+                        md.push_str(&self.type_reference(&error.type_name));
+                        if let Some(message) = &error.message {
+                            md.push_str(" (`'");
+                            md.push_str(message);
+                            md.push_str("'`)");
+                        }
+                        if let Some(condition) = &error.condition {
+                            md.push_str(": ");
+                            md.push_str(&locale.thrown_when.replace("{condition}", condition));
+                        }
+                    }
+                }
+                md.push('\n');
+            }
+        }
+
+        if let Some(source) = &function.source {
+            md.push_str(&format!("- [{}]({})\n", locale.view_source, sources::url(source, self.options.source_url.as_deref())));
+        }
+
+        if let Some(see) = function.see.as_ref().filter(|see| !see.is_empty()) {
+            md.push_str("- ");
+            md.push_str(&locale.see_also);
+            md.push_str(": ");
+            md.push_str(&self.see_also(&DocParser::location(class_op, &function.name), see));
+            md.push('\n');
+        }
+
+        self.add_examples(md, examples);
+
+        for hooks in &self.options.hooks {
+            hooks.after_function(class_op, function, md);
         }
+        true
+    }
 
-        DocParser::add_examples(&mut md, &function.examples.as_ref().unwrap());
+    // Where a function is in warnings and logs, such as `List.get`
+    fn location(class_op: Option<&str>, name: &str) -> String {
+        match class_op {
+            Some(class) => format!("{}.{}", class.trim_matches(['<', '>']), name),
+            None => name.to_string()
+        }
+    }
 
-        Some(md)
+    // Caveats are set apart from the description so they aren't missed
+    fn add_admonitions(&self, md: &mut String, experimental: bool, warnings: &Option<Vec<String>>, notes: &Option<Vec<String>>) {
+        let locale = &self.options.locale;
+        let experimental = experimental.then(|| vec![locale.experimental_desc.clone()]);
+        let admonitions = [
+            (&experimental, "danger", &locale.experimental),
+            (warnings, "warning", &locale.warning),
+            (notes, "info", &locale.note)
+        ];
+        for (lines, style, title) in admonitions {
+            let Some(lines) = lines.as_ref().filter(|lines| !lines.is_empty()) else {
+                continue;
+            };
+
+            match self.options.callouts {
+                Callouts::Plain => {
+                    md.push_str("> **");
+                    md.push_str(title);
+                    md.push_str(":**");
+                    for line in lines {
+                        md.push_str(if line.is_empty() { "\n>" } else { "\n> " });
+                        md.push_str(line);
+                    }
+                    md.push_str("\n\n");
+                }
+                Callouts::GitBook => {
+                    md.push_str("{% hint style=\"");
+                    md.push_str(style);
+                    md.push_str("\" %}\n");
+                    DocParser::add_from_string_array(md, lines);
+                    md.push_str("{% endhint %}\n\n");
+                }
+            }
+        }
     }
 
-    fn add_params_in_function(md: &mut String, params: &Vec<Param>) {
+    // Whether an instance method returns the instance it was called on, for chaining,
+    // going by its return type being its class and its description saying so
+    fn returns_this(class_op: &str, returns: &Return) -> bool {
+        let Some(class) = class_op.strip_prefix('<').and_then(|class| class.strip_suffix('>')) else {
+            return false;
+        };
+        let desc = returns.desc.trim().trim_end_matches('.').to_lowercase();
+        let class_name = class.to_lowercase();
+        returns.type_name == class
+            && (desc == "this" || desc == "itself" || desc == format!("the {}", class_name) || desc == format!("this {}", class_name))
+    }
+
+    fn add_params_in_function(md: &mut String, params: &[Param]) {
         for i in 0..params.len() {
             let param: &Param = &params[i];
             md.push_str(&param.name);
@@ -343,47 +1145,584 @@ impl DocParser {
         }
     }
 
-    fn add_member(md: &mut String, class_name: &str, members: &mut Vec<Member>) {
+    fn add_member(&self, md: &mut String, class: &str, statics: bool, members: &[Member]) {
+        let class_name = if statics { class.to_string() } else { format!("<{}>", class) };
+        let (anchor_prefix, kind) = if statics { (format!("{}-static", class), "static member") } else { (class.to_string(), "member") };
+        let group_members = self.options.group_members;
+        let mut members: Vec<&Member> = members.iter().collect();
         members.sort_by(|a, b| {
-            a.name.cmp(&b.name)
+            let group = |member: &Member| group_members && member.assignable == Some(false);
+            (group(a), &a.name).cmp(&(group(b), &b.name))
         });
 
+        // Groups are only labelled when there are both kinds of members
+        let labelled = group_members
+            && members.iter().any(|member| member.assignable == Some(true))
+            && members.iter().any(|member| member.assignable == Some(false));
+        let mut group = None;
+
+        let locale = &self.options.locale;
         for member in members {
             // Every member should have this field, otherwise invalid
-            if member.assignable.is_none() {
+            let Some(assignable) = member.assignable else {
+                log::debug(&format!("{}.{}: left out as it has no 'assignable'", class, member.name));
                 continue;
+            };
+            if log::enabled(Level::Trace) {
+                log::trace(&format!("Rendering {}.{}", class, member.name));
             }
 
+            if labelled && group != Some(assignable) {
+                if group.is_some() {
+                    md.push('\n');
+                }
+                md.push_str(&format!("**{}**\n\n", if assignable { &locale.assignable } else { &locale.read_only }));
+                group = Some(assignable);
+            }
+
+            for hooks in &self.options.hooks {
+                hooks.before_member(Some(&class_name), member, md);
+            }
+            let anchor = anchor_prefix.clone() + "-" + &member.name;
+            self.add_anchor(md, &self.anchor_key(anchor, kind, class, &member.name, 0));
             md.push_str("### `");
-            md.push_str(class_name);
-            md.push_str(".");
+            md.push_str(&self.owner_name(&class_name));
+            md.push('.');
             md.push_str(&member.name);
-            md.push_str("`\n");
+            md.push('`');
+            md.push_str(&self.badges(false, false, member.assignable == Some(false)));
+            md.push('\n');
 
-            DocParser::add_description(md, &member.desc.as_ref().unwrap());
+            self.add_description(md, member.desc.as_deref().unwrap_or_default());
 
-            md.push_str("- Type: ");
-            md.push_str(&member.type_name.as_ref().unwrap());
-            md.push('\n');
+            if let Some(type_name) = &member.type_name {
+                md.push_str("- ");
+                md.push_str(&locale.type_name);
+                md.push_str(": ");
+                md.push_str(&self.type_reference(type_name));
+                md.push('\n');
+            }
+
+            if let Some(value) = &member.value {
+                md.push_str(&format!("- {}: `{}`\n", locale.value, value));
+            }
+
+            if group_members && !assignable {
+                md.push_str("- ");
+                md.push_str(&locale.read_only);
+                md.push('\n');
+            } else {
+                md.push_str("- ");
+                md.push_str(&locale.assignable);
+                md.push_str(": ");
+                md.push_str(if assignable { "true" } else { "false" });
+                md.push('\n');
+            }
+
+            self.add_examples(md, member.examples.as_deref().unwrap_or_default());
+            for hooks in &self.options.hooks {
+                hooks.after_member(Some(&class_name), member, md);
+            }
+        }
+    }
+
+    // A table of the values of the constants, for comparing them at a glance
+    fn add_values(&self, md: &mut String, class_name: &str, members: &[Member]) {
+        let mut constants: Vec<(&Member, &Value)> = members.iter()
+            .filter_map(|member| member.value.as_ref().map(|value| (member, value)))
+            .collect();
+        if constants.len() < 2 {
+            return;
+        }
+        constants.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+
+        let locale = &self.options.locale;
+        md.push_str(&format!("| {} | {} |\n| --- | --- |\n", locale.name, locale.value));
+        for (member, value) in constants {
+            let value = value.to_string().replace('|', "\\|");
+            md.push_str(&format!("| `{}.{}` | `{}` |\n", class_name, member.name, value));
+        }
+        md.push('\n');
+    }
+
+    fn add_anchor(&self, md: &mut String, key: &str) {
+        if !self.options.anchors {
+            return;
+        }
+
+        md.push_str("<a id=\"");
+        md.push(ANCHOR_MARK);
+        md.push_str(&DocParser::anchor_id(key));
+        md.push(ANCHOR_MARK);
+        md.push_str("\"></a>\n");
+    }
+
+    // The id of the first anchor with the key, later ones are numbered
+    pub fn anchor_id(key: &str) -> String {
+        let mut id = String::new();
+        for c in key.chars() {
+            if c.is_ascii_alphanumeric() {
+                id.push(c.to_ascii_lowercase());
+            } else if !id.is_empty() && !id.ends_with('-') {
+                id.push('-');
+            }
+        }
+        while id.ends_with('-') {
+            id.pop();
+        }
+        id
+    }
+
+    // The key of an item's anchor from the `--anchor-key` template, or else the default key.
+    // Kinds are those of `diff::Item`, the class of an extension function is its extension.
+    pub fn anchor_key(&self, default: String, kind: &str, class: &str, name: &str, arity: usize) -> String {
+        match &self.options.anchor_key {
+            Some(template) => template.replace("{class}", class).replace("{kind}", kind)
+                .replace("{name}", name).replace("{arity}", &arity.to_string()),
+            None => default
+        }
+    }
+
+    // The anchor key of a constructor, overloads are told apart by their number of parameters
+    pub fn constructor_anchor(class: &str, overloads: usize, params: usize) -> String {
+        if overloads > 1 {
+            format!("{}-new-{}", class, params)
+        } else {
+            format!("{}-new", class)
+        }
+    }
+
+    // Replaces the marked anchor ids in order, numbering repeated ones
+    fn resolve_anchors(&self, used: &mut HashMap<String, usize>, md: String) -> String {
+        if !md.contains(ANCHOR_MARK) {
+            return md;
+        }
+
+        let mut resolved = String::with_capacity(md.len());
+        let mut parts = md.split(ANCHOR_MARK);
+        resolved.push_str(parts.next().unwrap());
+
+        while let (Some(id), Some(rest)) = (parts.next(), parts.next()) {
+            let count = used.entry(id.to_string()).or_insert(0);
+            *count += 1;
+            if *count > 1 {
+                let unique = format!("{}-{}", id, count);
+                self.anchor_collisions.lock().unwrap().push(
+                    format!("Anchor '{}' is used by multiple headings, renamed to '{}'", id, unique)
+                );
+                resolved.push_str(&unique);
+            } else {
+                resolved.push_str(id);
+            }
+            resolved.push_str(rest);
+        }
+
+        resolved
+    }
+
+    // The badges of an item's statuses, each after a space
+    pub fn badges(&self, deprecated: bool, experimental: bool, read_only: bool) -> String {
+        let badges = &self.options.badges;
+        [(deprecated, &badges.deprecated), (experimental, &badges.experimental), (read_only, &badges.read_only)].into_iter()
+            .filter_map(|(status, badge)| badge.as_ref().filter(|_| status))
+            .map(|badge| format!(" {}", badge))
+            .collect()
+    }
+
+    // Where a class or extension, or an item of one given by its anchor key and heading,
+    // is found relative to the top of the output
+    pub fn link(&self, extension: bool, owner: &str, item: Option<(&str, &str)>) -> String {
+        let directory = if extension { "extensions" } else { "classes" };
+        let page = match self.options.layout {
+            Layout::Single if extension => "Extensions.md".to_string(),
+            Layout::Single => "Classes.md".to_string(),
+            Layout::Split => self.page_path(extension, owner),
+            Layout::Site => format!("{}/{}.html", directory, self.file_name(owner)),
+            Layout::Combined => String::new()
+        };
+
+        let fragment = match item {
+            Some((anchor, _)) if self.options.anchors => DocParser::anchor_id(anchor),
+            Some((_, heading)) => glossary::heading_slug(heading),
+            None if !matches!(self.options.layout, Layout::Single | Layout::Combined) => return page,
+            None if self.options.anchors => DocParser::anchor_id(owner),
+            None if extension => glossary::heading_slug(owner),
+            None => {
+                let badges = self.badges(false, self.json["classes"][owner]["experimental"] == true, false);
+                glossary::heading_slug(&(self.options.locale.class_heading.replace("{class}", owner) + &badges))
+            }
+        };
+        format!("{}#{}", page, fragment)
+    }
+
+    // How an owner such as `<List>` is written in headings, static owners are left as they are
+    pub fn owner_name<'a>(&self, owner: &'a str) -> Cow<'a, str> {
+        let Some(class) = owner.strip_prefix('<').and_then(|class| class.strip_suffix('>')) else {
+            return Cow::Borrowed(owner);
+        };
+        match self.options.instance_names {
+            InstanceNames::Brackets => Cow::Borrowed(owner),
+            InstanceNames::Class => Cow::Borrowed(class),
+            InstanceNames::Variable => {
+                let mut chars = class.chars();
+                let first = chars.next().map(|first| first.to_lowercase().to_string()).unwrap_or_default();
+                Cow::Owned(first + chars.as_str())
+            }
+        }
+    }
+
+    // A signature such as `<List>.get(index)` with its owner written as in headings
+    pub fn signature(&self, signature: &str) -> String {
+        match signature.split_once('.') {
+            Some((owner, rest)) if !owner.contains('(') => format!("{}.{}", self.owner_name(owner), rest),
+            _ => signature.to_string()
+        }
+    }
+
+    // The name of the page of a class or extension, without its extension
+    pub fn file_name(&self, name: &str) -> String {
+        if self.options.lowercase_files { name.to_lowercase() } else { name.to_string() }
+    }
 
-            md.push_str("- Assignable: ");
-            md.push_str(&member.assignable.unwrap().to_string());
+    // The path of the split page of a class or extension relative to the top of the output,
+    // from the `--out` template if there is one
+    pub fn page_path(&self, extension: bool, owner: &str) -> String {
+        let Some(template) = &self.options.out else {
+            let directory = if extension { "extensions".to_string() } else { self.class_directory(owner) };
+            return format!("{}/{}.md", directory, self.file_name(owner));
+        };
+
+        // Each variable fills a single directory, so every page is as deep as the template
+        let entry = if extension { &Value::Null } else { &self.json["classes"][owner] };
+        let variable = |key: &str| entry[key].as_str().unwrap_or("other").replace(['/', '\\'], "-");
+        template.replace("{kind}", if extension { "extension" } else { "class" })
+            .replace("{category}", &variable("category"))
+            .replace("{module}", &variable("module"))
+            .replace("{name}", &self.file_name(owner).replace(['/', '\\'], "-"))
+    }
+
+    // The directory of a class's split page, its module's if it has one
+    pub fn class_directory(&self, class: &str) -> String {
+        match self.json["classes"][class]["module"].as_str() {
+            Some(module) if self.options.modules => module.to_string(),
+            _ => "classes".to_string()
+        }
+    }
+
+    // Whether `Class`, `Class.name`, `Class.new` or an extension function is documented
+    pub fn documents(&self, name: &str) -> bool {
+        self.find(name).is_some()
+    }
+
+    // Links to each of the names, those that aren't documented are left as code
+    fn see_also(&self, location: &str, names: &[String]) -> String {
+        let links: Vec<String> = names.iter().map(|name| match self.find(name) {
+            Some(target) => {
+                let item = target.item.as_ref().map(|(anchor, heading)| (anchor.as_str(), heading.as_str()));
+                format!("[`{}`]({}{})", name, self.root(), self.link(target.extension, &target.owner, item))
+            }
+            None => {
+                self.warn(format!("{}: see also '{}' is not documented", location, name));
+                format!("`{}`", name)
+            }
+        }).collect();
+        links.join(", ")
+    }
+
+    // Finds `Class`, `Class.name`, `Class.new` or an extension function
+    fn find(&self, name: &str) -> Option<Target> {
+        let classes = &self.json["classes"];
+        let Some((class, item)) = name.split_once('.') else {
+            if classes.get(name).is_some() {
+                return Some(Target { extension: false, owner: name.to_string(), item: None });
+            }
+            let extensions = self.json["extensions"].as_object()?;
+            return extensions.iter().find_map(|(owner, functions)| {
+                let function = functions.as_array()?.iter().find(|function| function["name"] == name)?;
+                let heading = format!("{}({})", name, param_names(function));
+                let arity = function["params"].as_array().map_or(0, Vec::len);
+                let anchor = self.anchor_key(name.to_string(), "function", owner, name, arity);
+                Some(Target { extension: true, owner: owner.clone(), item: Some((anchor, heading)) })
+            });
+        };
+
+        let value = classes.get(class)?;
+        let member_class = self.owner_name(&format!("<{}>", class)).into_owned();
+        let constructors = value["constructors"].as_array().map_or(0, Vec::len);
+        let arity = value["constructors"][0]["params"].as_array().map_or(0, Vec::len);
+        let sections = [
+            ("constructors", "constructor", DocParser::constructor_anchor(class, constructors, arity), format!("new {}", class)),
+            ("static_members", "static member", format!("{}-static-{}", class, item), format!("{}.{}", class, item)),
+            ("members", "member", format!("{}-{}", class, item), format!("{}.{}", member_class, item)),
+            ("methods", "method", format!("{}-{}", class, item), format!("{}.{}", member_class, item)),
+            ("static_methods", "static method", format!("{}-static-{}", class, item), format!("{}.{}", class, item))
+        ];
+        for (section, kind, anchor, heading) in sections {
+            let mut items = value[section].as_array().into_iter().flatten();
+            let found = if section == "constructors" {
+                items.next().filter(|_| item == "new")
+            } else {
+                items.find(|value| value["name"] == item)
+            };
+            if let Some(found) = found {
+                let arity = found["params"].as_array().map_or(0, Vec::len);
+                let anchor = self.anchor_key(anchor, kind, class, item, arity);
+                let heading = if section.ends_with("members") {
+                    heading + &self.badges(false, false, found["assignable"] == false)
+                } else if section == "constructors" {
+                    format!("{}({})", heading, param_names(found))
+                } else {
+                    let badges = self.badges(!found["deprecated"].is_null(), found["experimental"] == true, false);
+                    format!("{}({}){}", heading, param_names(found), badges)
+                };
+                return Some(Target { extension: false, owner: class.to_string(), item: Some((anchor, heading)) });
+            }
+        }
+        None
+    }
+
+    // A type as written in the page, each type of a union such as `String | Number?`
+    // is linked to its class if asked for
+    fn type_reference<'a>(&self, type_name: &'a str) -> Cow<'a, str> {
+        if !self.options.wikilinks && !self.options.link_types && !type_name.contains('|') {
+            return Cow::Borrowed(type_name);
+        }
+        Cow::Owned(self.linked_type(type_name, &self.root(), self.options.wikilinks || self.options.link_types))
+    }
+
+    // The type with each documented class in it linked relative to `root`, aliases link
+    // to the class they stand for
+    pub fn linked_type(&self, type_name: &str, root: &str, link: bool) -> String {
+        let parts: Vec<String> = type_name.split('|').map(|part| {
+            let part = part.trim();
+            let (name, nullable) = match part.strip_suffix('?') {
+                Some(name) => (name.trim_end(), "?"),
+                None => (part, "")
+            };
+            let target = self.alias_target(name).unwrap_or(name);
+            let documented = link && self.json["classes"].get(target).is_some();
+            if documented && self.options.wikilinks && target != name {
+                format!("[[{}|{}]]{}", target, name, nullable)
+            } else if documented && self.options.wikilinks {
+                format!("[[{}]]{}", name, nullable)
+            } else if documented {
+                format!("[{}]({}{}){}", name, root, self.link(false, target, None), nullable)
+            } else {
+                format!("{}{}", name, nullable)
+            }
+        }).collect();
+        parts.join(" | ")
+    }
+
+    // Values available without any class, from `globals` or the older `values`, sorted by name
+    pub fn globals(&self) -> Vec<Member> {
+        let key = if self.json.get("globals").is_some() { "globals" } else { "values" };
+        let mut value = self.json[key].clone();
+        self.retain_valid::<Member>(&mut value, &format!("$.{}", key));
+        let mut globals: Vec<Member> = serde_json::from_value(value).unwrap_or_default();
+        globals.sort_by(|a, b| a.name.cmp(&b.name));
+        globals
+    }
+
+    pub fn render_globals(&self, globals: &[Member], used: &mut HashMap<String, usize>) -> String {
+        let locale = &self.options.locale;
+        let mut md = String::new();
+        md.push_str("# ");
+        md.push_str(&locale.globals);
+        md.push('\n');
+        md.push_str(&locale.globals_summary);
+        md.push_str("\n\n");
+
+        for global in globals {
+            for hooks in &self.options.hooks {
+                hooks.before_member(None, global, &mut md);
+            }
+            self.add_anchor(&mut md, &global.name);
+            md.push_str("### `");
+            md.push_str(&global.name);
+            md.push_str("`\n");
+
+            self.add_description(&mut md, global.desc.as_deref().unwrap_or_default());
+            if let Some(type_name) = &global.type_name {
+                md.push_str("- ");
+                md.push_str(&locale.type_name);
+                md.push_str(": ");
+                md.push_str(&self.type_reference(type_name));
+                md.push('\n');
+            }
+            if let Some(value) = &global.value {
+                md.push_str(&format!("- {}: `{}`\n", locale.value, value));
+            }
+            if let Some(examples) = global.examples.as_ref().filter(|examples| !examples.is_empty()) {
+                self.add_examples(&mut md, examples);
+            }
+            for hooks in &self.options.hooks {
+                hooks.after_member(None, global, &mut md);
+            }
             md.push('\n');
+        }
+
+        self.resolve_anchors(used, md)
+    }
+
+    // Every alias in `type_aliases`, sorted by name
+    pub fn type_aliases(&self) -> Vec<TypeAlias> {
+        let mut aliases = Vec::new();
+        for (name, value) in self.json["type_aliases"].as_object().into_iter().flatten() {
+            let Some(type_name) = value["type"].as_str() else {
+                self.warn(format!("$.type_aliases.{}: missing type", name));
+                continue;
+            };
+            let desc = value["desc"].as_array().into_iter().flatten().filter_map(Value::as_str).map(str::to_string).collect();
+            aliases.push(TypeAlias { name: name.clone(), type_name: type_name.to_string(), desc });
+        }
+        aliases.sort_by(|a, b| a.name.cmp(&b.name));
+        aliases
+    }
+
+    // The class an alias stands for, unless it stands for a union
+    fn alias_target(&self, name: &str) -> Option<&str> {
+        let type_name = self.json["type_aliases"][name]["type"].as_str()?;
+        let mut names = DocParser::type_names(type_name);
+        let target = names.next()?;
+        names.next().is_none().then_some(target)
+    }
+
+    // The classes a type is made of, `String | Number?` is made of `String` and `Number`
+    pub fn type_names(type_name: &str) -> impl Iterator<Item = &str> {
+        type_name.split('|').map(|part| part.trim().trim_end_matches('?').trim_end()).filter(|name| !name.is_empty())
+    }
+
+    // Links from a page are relative to it, pages of their own are one directory down
+    fn root(&self) -> Cow<'static, str> {
+        match (self.options.layout, &self.options.out) {
+            (Layout::Single | Layout::Combined, _) => Cow::Borrowed(""),
+            (Layout::Split, Some(template)) => Cow::Owned("../".repeat(template.matches('/').count())),
+            _ => Cow::Borrowed("../")
+        }
+    }
+
+    // Where an asset is copied to, relative to the page
+    fn asset_url(&self, asset: &str) -> String {
+        format!("{}{}/{}", self.root(), assets::DIRECTORY, asset)
+    }
 
-            DocParser::add_examples(md, &member.examples.as_ref().unwrap());
+    // The lines with their links to assets pointing at the copies in the output
+    fn asset_links<'a>(&self, lines: &'a [String], assets: &Option<Vec<String>>) -> Cow<'a, [String]> {
+        let Some(assets) = assets.as_ref().filter(|assets| !assets.is_empty()) else {
+            return Cow::Borrowed(lines);
+        };
+        Cow::Owned(lines.iter().map(|line| assets.iter().fold(line.clone(), |line, asset| {
+            line.replace(&format!("]({})", asset), &format!("]({})", self.asset_url(asset)))
+        })).collect())
+    }
+
+    // Images of the assets the description doesn't already show, each between the
+    // prefix and suffix
+    fn add_images(&self, md: &mut String, (prefix, suffix): (&str, &str), assets: &Option<Vec<String>>, desc: &[String]) {
+        for asset in assets.iter().flatten() {
+            let link = format!("]({})", asset);
+            if desc.iter().any(|line| line.contains(&link)) {
+                continue;
+            }
+            let name = Path::new(asset).file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+            md.push_str(prefix);
+            md.push_str(&format!("![{}]({})", name, self.asset_url(asset)));
+            md.push_str(suffix);
         }
     }
 
-    fn add_description(md: &mut String, desc: &Vec<String>) {
-        md.push_str("- Description: ");
-        DocParser::add_from_string_array(md, desc);
+    fn add_section(md: &mut String, title: &str) {
+        md.push_str("## ");
+        md.push_str(title);
+        md.push_str("\n\n");
+    }
+
+    fn add_description(&self, md: &mut String, desc: &[String]) {
+        // Nothing is written for a missing description rather than an empty item
+        if desc.iter().all(|line| line.trim().is_empty()) {
+            return;
+        }
+        let prefix = format!("- {}: ", self.options.locale.description);
+        self.add_text(md, &prefix, 2, desc);
     }
 
-    fn add_params(md: &mut String, params: &Vec<Param>) {
+    // Writes the lines of a description after `prefix`, empty lines separate paragraphs.
+    // Paragraphs after the first are indented by `indent` to stay within a list item, and
+    // with `reflow` the lines of a paragraph are joined and wrapped. Code blocks are kept.
+    fn add_text(&self, md: &mut String, prefix: &str, indent: usize, lines: &[String]) {
+        let width = self.options.reflow.map(|width| if width == 0 { usize::MAX } else { width });
+        let continuation = " ".repeat(indent);
+        let mut first = Some(prefix);
+        let mut paragraph: Vec<&str> = Vec::new();
+        let mut code = false;
+        let mut paragraph_break = false;
+        let mut indented = false;
+
+        for line in lines {
+            let fence = line.trim_start().starts_with("```");
+            if !code && line.trim().is_empty() {
+                if let Some(width) = width {
+                    DocParser::add_paragraph(md, &mut paragraph, &mut first, indent, width);
+                }
+                paragraph_break = first.is_none();
+                continue;
+            }
+
+            if paragraph_break {
+                md.push('\n');
+                paragraph_break = false;
+                indented = true;
+            }
+
+            match width {
+                Some(_) if !code && !fence => paragraph.push(line),
+                _ => {
+                    if let Some(width) = width {
+                        DocParser::add_paragraph(md, &mut paragraph, &mut first, indent, width);
+                    }
+                    match first.take() {
+                        Some(prefix) => md.push_str(prefix),
+                        None if indented && !line.is_empty() => md.push_str(&continuation),
+                        None => {}
+                    }
+                    md.push_str(line);
+                    md.push('\n');
+                    code ^= fence;
+                }
+            }
+        }
+
+        if let Some(width) = width {
+            DocParser::add_paragraph(md, &mut paragraph, &mut first, indent, width);
+            if let Some(prefix) = first {
+                md.push_str(prefix.trim_end());
+                md.push('\n');
+            }
+        } else if let Some(prefix) = first {
+            md.push_str(prefix);
+        }
+    }
+
+    fn add_paragraph(md: &mut String, paragraph: &mut Vec<&str>, first: &mut Option<&str>, indent: usize, width: usize) {
+        if paragraph.is_empty() {
+            return;
+        }
+        let continuation = " ".repeat(indent);
+        let first = first.take().unwrap_or(&continuation);
+        md.push_str(&text::wrap(&paragraph.join(" "), first, indent, width));
+        paragraph.clear();
+    }
+
+    fn add_params(&self, md: &mut String, params: &[Param]) {
+        let locale = &self.options.locale;
+
         if params.len() == 1 {
             let param = &params[0];
-            md.push_str("- Parameter - ");
-            md.push_str(&param.type_name);
+            md.push_str("- ");
+            md.push_str(&locale.parameter);
+            md.push_str(" - ");
+            md.push_str(&self.type_reference(&param.type_name));
             md.push_str(" (`");
             md.push_str(&param.name);
             md.push_str("`): ");
@@ -392,10 +1731,12 @@ impl DocParser {
             return
         }
 
-        md.push_str("- Parameters:\n");
+        md.push_str("- ");
+        md.push_str(&locale.parameters);
+        md.push_str(":\n");
         for param in params {
             md.push_str("  - ");
-            md.push_str(&param.type_name);
+            md.push_str(&self.type_reference(&param.type_name));
             md.push_str(" (`");
             md.push_str(&param.name);
             md.push_str("`): ");
@@ -404,24 +1745,74 @@ impl DocParser {
         }
     }
 
-    fn add_examples(md: &mut String, examples: &Vec<String>) {
-        md.push_str(if examples.len() > 1 { "- Examples:\n" } else { "- Example:\n" });
-        for example in examples {
-            md.push_str("```kt\n");
-            md.push_str(&example.replace("\t", "    "));
+    // The code of an example as pages show it, with its indentation normalised
+    pub fn example_code<'a>(&self, example: &'a str) -> Cow<'a, str> {
+        match self.options.example_indent {
+            Some(indent) => Cow::Owned(examples::format(example, indent)),
+            None if example.contains('\t') => Cow::Owned(example.replace("\t", "    ")),
+            None => Cow::Borrowed(example)
+        }
+    }
+
+    fn add_examples(&self, md: &mut String, examples: &[CodeExample]) {
+        if examples.is_empty() {
+            return;
+        }
+        let locale = &self.options.locale;
+
+        md.push_str("- ");
+        md.push_str(if examples.len() > 1 { &locale.examples } else { &locale.example });
+        md.push_str(":\n");
+        for code_example in examples {
+            let example = self.example_code(code_example.code());
+
+            // The variant is the title of the block, as Docusaurus and Material for MkDocs show it
+            match code_example.variant() {
+                Some(variant) => md.push_str(&format!("```kt title=\"{}\"\n", variant)),
+                None => md.push_str("```kt\n")
+            }
+            md.push_str(&example);
 
             while md.ends_with("\n") {
                 md.remove(md.len() - 1);
             }
 
-            md.push_str("\n```\n");
+            let output = code_example.output().map(|output| output.trim_end_matches('\n'));
+            match output {
+                Some(output) if self.options.output_comments => {
+                    md.push_str("\n// ");
+                    md.push_str(&locale.output);
+                    md.push(':');
+                    for line in output.lines() {
+                        md.push_str("\n// ");
+                        md.push_str(line);
+                    }
+                    md.push_str("\n```\n");
+                }
+                Some(output) => {
+                    md.push_str("\n```\n```text\n");
+                    md.push_str(output);
+                    md.push_str("\n```\n");
+                }
+                None => md.push_str("\n```\n")
+            }
         }
     }
 
-    fn add_from_string_array(md: &mut String, array: &Vec<String>) {
+    fn add_from_string_array(md: &mut String, array: &[String]) {
         for value in array {
             md.push_str(value);
             md.push('\n');
         }
     }
-}
\ No newline at end of file
+}
+
+// The names of the parameters of a function or constructor as written in its heading
+fn param_names(value: &Value) -> String {
+    let params = value["params"].as_array().into_iter().flatten();
+    params.filter_map(|param| param["name"].as_str()).collect::<Vec<&str>>().join(", ")
+}
+
+fn arity(params: &Option<Vec<Param>>) -> usize {
+    params.as_ref().map_or(0, Vec::len)
+}
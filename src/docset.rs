@@ -0,0 +1,121 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use crate::doc_parser::{Class, DocParser, Function, Param};
+use crate::html;
+use crate::output::{create_dir, group_by_category, write_file};
+use crate::sqlite;
+
+pub const NAME: &str = "Arucas.docset";
+
+const INFO: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>CFBundleIdentifier</key>
+	<string>arucas</string>
+	<key>CFBundleName</key>
+	<string>Arucas</string>
+	<key>DocSetPlatformFamily</key>
+	<string>arucas</string>
+	<key>isDashDocset</key>
+	<true/>
+	<key>dashIndexFilePath</key>
+	<string>index.html</string>
+</dict>
+</plist>
+"#;
+
+// Writes a docset bundle for Dash and Zeal: an HTML page per class and extension
+// and a search index pointing at the anchor of every documented item. Returns
+// the number of index entries.
+pub fn write(parser: &DocParser, path: &Path) -> Result<usize, String> {
+    let documents = path.join("Contents").join("Resources").join("Documents");
+    for directory in ["classes", "extensions"] {
+        create_dir(&documents.join(directory))?;
+    }
+
+    write_file(&path.join("Contents").join("Info.plist"), INFO)?;
+    write_file(&documents.join("style.css"), html::STYLESHEET)?;
+
+    let mut entries: Vec<Vec<String>> = Vec::new();
+    let mut index = String::from("# Arucas\n");
+
+    for (title, classes) in group_by_category(parser.classes()) {
+        index.push_str(&format!("\n## {}\n\n", title));
+        for class in classes {
+            let page = format!("classes/{}.html", parser.file_name(&class.name));
+            index.push_str(&format!("- [{}]({})\n", class.name, page));
+            class_entries(parser, &mut entries, &class, &page);
+
+            let title = class.name.clone();
+            let md = parser.render_class(class, &mut HashMap::new());
+            write_file(&documents.join(&page), &html::page(&title, &html::from_markdown(&md), "../style.css"))?;
+        }
+    }
+
+    index.push_str("\n## Extensions\n\n");
+    for (name, functions) in parser.extensions() {
+        let page = format!("extensions/{}.html", parser.file_name(&name));
+        index.push_str(&format!("- [{}]({})\n", name, page));
+        for function in documented(&functions) {
+            let anchor = parser.anchor_key(function.name.clone(), "function", &name, &function.name, arity(&function.params));
+            entries.push(entry(&function.name, "Function", &page, &anchor));
+        }
+
+        let md = parser.render_extension(&name, &functions, &mut HashMap::new());
+        write_file(&documents.join(&page), &html::page(&name, &html::from_markdown(&md), "../style.css"))?;
+    }
+
+    write_file(&documents.join("index.html"), &html::page("Arucas", &html::from_markdown(&index), "style.css"))?;
+
+    let database = path.join("Contents").join("Resources").join("docSet.dsidx");
+    sqlite::write_table(&database, "searchIndex", &["name", "type", "path"], &entries)
+        .map_err(|error| format!("Could not write {}: {}", database.display(), error))?;
+    Ok(entries.len())
+}
+
+// Index entries for everything the class page renders, linked by anchor
+fn class_entries(parser: &DocParser, entries: &mut Vec<Vec<String>>, class: &Class, page: &str) {
+    let name = &class.name;
+    entries.push(vec![name.clone(), "Class".to_string(), page.to_string()]);
+
+    for member in class.static_members.iter().flatten().filter(|member| member.assignable.is_some()) {
+        let anchor = parser.anchor_key(format!("{}-static-{}", name, member.name), "static member", name, &member.name, 0);
+        entries.push(entry(&format!("{}.{}", name, member.name), "Constant", page, &anchor));
+    }
+    for member in class.members.iter().flatten().filter(|member| member.assignable.is_some()) {
+        let anchor = parser.anchor_key(format!("{}-{}", name, member.name), "member", name, &member.name, 0);
+        entries.push(entry(&format!("{}.{}", name, member.name), "Property", page, &anchor));
+    }
+    if let Some(constructors) = class.constructors.as_ref().filter(|constructors| !constructors.is_empty()) {
+        let params = constructors[0].params.as_ref().map_or(0, Vec::len);
+        let anchor = DocParser::constructor_anchor(name, constructors.len(), params);
+        let anchor = parser.anchor_key(anchor, "constructor", name, "new", params);
+        entries.push(entry(&format!("new {}", name), "Constructor", page, &anchor));
+    }
+    for method in documented(class.methods.iter().flatten()) {
+        let anchor = parser.anchor_key(format!("{}-{}", name, method.name), "method", name, &method.name, arity(&method.params));
+        entries.push(entry(&format!("{}.{}", name, method.name), "Method", page, &anchor));
+    }
+    for method in documented(class.static_methods.iter().flatten()) {
+        let anchor = parser.anchor_key(format!("{}-static-{}", name, method.name), "static method", name, &method.name, arity(&method.params));
+        entries.push(entry(&format!("{}.{}", name, method.name), "Method", page, &anchor));
+    }
+}
+
+// Functions that are rendered, those without examples are left out and overloads share a heading
+fn documented<'a>(functions: impl IntoIterator<Item = &'a Function>) -> Vec<&'a Function> {
+    let mut names = BTreeSet::new();
+    functions.into_iter()
+        .filter(|function| function.examples.is_some())
+        .filter(|function| names.insert(function.name.as_str()))
+        .collect()
+}
+
+fn arity(params: &Option<Vec<Param>>) -> usize {
+    params.as_ref().map_or(0, Vec::len)
+}
+
+fn entry(name: &str, kind: &str, page: &str, anchor: &str) -> Vec<String> {
+    vec![name.to_string(), kind.to_string(), format!("{}#{}", page, DocParser::anchor_id(anchor))]
+}
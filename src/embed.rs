@@ -0,0 +1,88 @@
+use serde::Deserialize;
+use crate::config::Badges;
+use crate::doc_parser::{DocParser, RenderOptions, VOID_TYPES};
+use crate::locale::Locale;
+use crate::{localize, migration, schema};
+
+// The rendering options embedders, such as the web playground, may give as JSON.
+// Anything left out renders as the command line would without flags.
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EmbedOptions {
+    pub anchors: bool,
+    pub link_types: bool,
+    pub accessors: bool,
+    pub group_members: bool,
+    pub output_comments: bool,
+    pub example_indent: Option<usize>,
+    pub reflow: Option<usize>,
+    pub void_types: Option<Vec<String>>,
+    pub anchor_key: Option<String>,
+    pub badges: Badges,
+    // The strings of a translation, missing ones fall back to English
+    pub locale: Option<Locale>,
+    // The language of descriptions given per language
+    pub language: Option<String>
+}
+
+impl EmbedOptions {
+    // Empty options are the defaults
+    pub fn parse(options: &str) -> Result<EmbedOptions, String> {
+        if options.trim().is_empty() {
+            return Ok(EmbedOptions::default());
+        }
+        serde_json::from_str(options).map_err(|error| format!("Invalid options: {}", error))
+    }
+
+    fn render_options(self) -> RenderOptions {
+        RenderOptions {
+            anchors: self.anchors || self.anchor_key.is_some(),
+            link_types: self.link_types,
+            accessors: self.accessors,
+            group_members: self.group_members,
+            output_comments: self.output_comments,
+            example_indent: self.example_indent,
+            reflow: self.reflow,
+            void_types: self.void_types.unwrap_or_else(|| VOID_TYPES.iter().map(|name| name.to_string()).collect()),
+            anchor_key: self.anchor_key,
+            badges: self.badges,
+            locale: self.locale.unwrap_or_default(),
+            jobs: 1,
+            ..RenderOptions::default()
+        }
+    }
+}
+
+// Reads, migrates and validates a document given as JSON text
+pub fn parse(json: &str, options: &str) -> Result<DocParser, String> {
+    let options = EmbedOptions::parse(options)?;
+    let mut json = serde_json::from_str(json).map_err(|error| format!("Could not parse documentation: {}", error))?;
+    migration::migrate(&mut json)?;
+    let language = options.language.as_deref().unwrap_or("en");
+    localize::localize(&mut json, language, "en");
+
+    let errors = [schema::validate(&json, false), schema::duplicates(&json)].concat();
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+
+    let mut parser = DocParser::from_json(json);
+    parser.options = options.render_options();
+    Ok(parser)
+}
+
+// The markdown of Classes.md for the document
+pub fn render_classes(json: &str, options: &str) -> Result<String, String> {
+    let parser = parse(json, options)?;
+    let mut md = Vec::new();
+    parser.write_classes(&mut md).map_err(|error| format!("Could not render documentation: {}", error))?;
+    Ok(String::from_utf8_lossy(&md).to_string())
+}
+
+// The markdown of Extensions.md for the document
+pub fn render_extensions(json: &str, options: &str) -> Result<String, String> {
+    let parser = parse(json, options)?;
+    let mut md = Vec::new();
+    parser.write_extensions(&mut md).map_err(|error| format!("Could not render documentation: {}", error))?;
+    Ok(String::from_utf8_lossy(&md).to_string())
+}
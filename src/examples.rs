@@ -0,0 +1,210 @@
+use std::{env, fs, process};
+use std::path::Path;
+use std::process::Command;
+use crate::doc_parser::{CodeExample, DocParser, Function};
+use crate::progress::Progress;
+
+pub struct Example {
+    pub location: String,
+    pub code: String,
+    // What the example should print, if given
+    pub output: Option<String>
+}
+
+pub fn collect(parser: &DocParser) -> Vec<Example> {
+    let mut examples = Vec::new();
+
+    for class in parser.classes() {
+        let members = class.static_members.iter().chain(class.members.iter()).flatten();
+        for member in members {
+            add(&mut examples, &format!("{}.{}", class.name, member.name), &member.examples);
+        }
+
+        if let Some(constructors) = &class.constructors {
+            for constructor in constructors {
+                add(&mut examples, &format!("new {}", class.name), &Some(constructor.examples.clone()));
+            }
+        }
+
+        let functions = class.methods.iter().chain(class.static_methods.iter()).flatten();
+        add_functions(&mut examples, Some(&class.name), functions);
+    }
+
+    for (_, functions) in parser.extensions() {
+        add_functions(&mut examples, None, functions.iter());
+    }
+
+    examples
+}
+
+fn add_functions<'a>(examples: &mut Vec<Example>, class: Option<&str>, functions: impl Iterator<Item = &'a Function>) {
+    for function in functions {
+        let location = match class {
+            Some(class) => format!("{}.{}", class, function.name),
+            None => function.name.clone()
+        };
+        add(examples, &location, &function.examples);
+    }
+}
+
+fn add(examples: &mut Vec<Example>, location: &str, codes: &Option<Vec<CodeExample>>) {
+    if let Some(codes) = codes {
+        for (i, code) in codes.iter().enumerate() {
+            examples.push(Example {
+                location: format!("{} (example {})", location, i + 1),
+                code: code.code().to_string(),
+                output: code.output().map(str::to_string)
+            });
+        }
+    }
+}
+
+// Runs each example through the interpreter, `{file}` in the command is
+// replaced with the script path, otherwise the path is appended.
+// Returns a description of every example that failed.
+pub fn run(examples: &[Example], interpreter: &str) -> Result<Vec<String>, String> {
+    let mut words = interpreter.split_whitespace();
+    let program = words.next().ok_or_else(|| "The interpreter command is empty".to_string())?;
+    let arguments: Vec<&str> = words.collect();
+
+    // One directory per run so runs at the same time don't overwrite each other's scripts,
+    // removed afterwards even when running an example fails
+    let directory = env::temp_dir().join(format!("arucas-md-examples-{}", process::id()));
+    fs::create_dir_all(&directory).map_err(|error| format!("Could not create temporary directory: {}", error))?;
+    let failures = run_in(&directory, examples, program, &arguments);
+    let _ = fs::remove_dir_all(&directory);
+    failures
+}
+
+fn run_in(directory: &Path, examples: &[Example], program: &str, arguments: &[&str]) -> Result<Vec<String>, String> {
+    let mut failures = Vec::new();
+    let progress = Progress::new("Running examples", examples.len());
+    for (i, example) in examples.iter().enumerate() {
+        progress.advance();
+        let script = directory.join(format!("example_{}.arucas", i));
+        fs::write(&script, &example.code).map_err(|error| format!("Could not write example script: {}", error))?;
+        let script = script.to_string_lossy().to_string();
+
+        let mut command = Command::new(program);
+        if arguments.contains(&"{file}") {
+            command.args(arguments.iter().map(|argument| argument.replace("{file}", &script)));
+        } else {
+            command.args(arguments).arg(&script);
+        }
+
+        let output = command.output().map_err(|error| format!("Could not run '{}': {}", program, error))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            failures.push(format!("{}: {}\n{}", example.location, output.status, stderr.trim_end()));
+            continue;
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if let Some(expected) = example.output.as_ref().filter(|expected| expected.trim_end() != stdout.trim_end()) {
+            failures.push(format!(
+                "{}: printed something else than its output\nexpected:\n{}\nprinted:\n{}",
+                example.location, expected.trim_end(), stdout.trim_end()
+            ));
+        }
+    }
+
+    Ok(failures)
+}
+
+// Strips the indentation shared by every line and rescales the remaining
+// indentation so each level is `indent` spaces wide
+pub fn format(code: &str, indent: usize) -> String {
+    let lines: Vec<String> = code.lines().map(|line| line.replace('\t', "    ").trim_end().to_string()).collect();
+
+    let leading = |line: &String| line.len() - line.trim_start().len();
+    let indented: Vec<usize> = lines.iter().filter(|line| !line.is_empty()).map(leading).collect();
+    let common = indented.iter().copied().min().unwrap_or(0);
+    let unit = indented.iter().map(|width| width - common).filter(|width| *width > 0).min().unwrap_or(indent);
+
+    let mut formatted: Vec<String> = lines.iter().map(|line| {
+        if line.is_empty() {
+            return String::new();
+        }
+        let level = (leading(line) - common).div_ceil(unit);
+        " ".repeat(level * indent) + line.trim_start()
+    }).collect();
+
+    while formatted.first().is_some_and(|line| line.is_empty()) {
+        formatted.remove(0);
+    }
+    while formatted.last().is_some_and(|line| line.is_empty()) {
+        formatted.pop();
+    }
+
+    formatted.join("\n")
+}
+
+// A lightweight lexer pass catching unbalanced brackets and unterminated
+// strings or comments, it does not otherwise understand Arucas syntax
+pub fn check_syntax(code: &str) -> Result<(), String> {
+    let mut stack: Vec<(char, usize)> = Vec::new();
+    let mut chars = code.chars().peekable();
+    let mut line = 1;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                let start = line;
+                chars.next();
+                let mut previous = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if previous == '*' => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            previous = c;
+                        }
+                        None => return Err(format!("unterminated comment starting on line {}", start))
+                    }
+                }
+            }
+            '"' | '\'' => {
+                let start = line;
+                loop {
+                    match chars.next() {
+                        Some('\\') => {
+                            chars.next();
+                        }
+                        Some(quote) if quote == c => break,
+                        Some('\n') | None => return Err(format!("unterminated string starting on line {}", start)),
+                        Some(_) => { }
+                    }
+                }
+            }
+            '(' | '[' | '{' => stack.push((c, line)),
+            ')' | ']' | '}' => {
+                let expected = match c {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{'
+                };
+                match stack.pop() {
+                    Some((open, _)) if open == expected => { }
+                    Some((open, opened)) => {
+                        return Err(format!("'{}' on line {} does not match '{}' on line {}", c, line, open, opened))
+                    }
+                    None => return Err(format!("unmatched '{}' on line {}", c, line))
+                }
+            }
+            _ => { }
+        }
+    }
+
+    match stack.pop() {
+        Some((open, opened)) => Err(format!("unclosed '{}' on line {}", open, opened)),
+        None => Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+// Exit codes, one for each outcome so scripts wrapping the converter can branch on them
+pub const FAILURE: i32 = 1;
+pub const USAGE: i32 = 2;
+pub const QUALITY: i32 = 3;
+pub const PARSE: i32 = 4;
+pub const INVALID: i32 = 5;
+pub const WARNINGS: i32 = 6;
+pub const CHANGED: i32 = 7;
+
+// What each code means, for the man page
+pub const CODES: &[(i32, &str)] = &[
+    (0, "The run succeeded"),
+    (FAILURE, "The run failed, such as when a file could not be read or written"),
+    (USAGE, "The arguments could not be parsed"),
+    (QUALITY, "The documentation is below a bar set with --min-coverage, --max-missing-descriptions or --max-missing-examples"),
+    (PARSE, "The input is not valid JSON or YAML, or is in a layout that can't be migrated"),
+    (INVALID, "The documentation has errors: it doesn't match the schema, links are broken or examples fail"),
+    (WARNINGS, "lint found problems, or with --fail-on warnings there were warnings while rendering"),
+    (CHANGED, "summary found changes")
+];
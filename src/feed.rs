@@ -0,0 +1,70 @@
+use crate::date;
+use crate::diff::{Change, ChangeKind};
+use crate::html;
+
+// The changes between one recorded snapshot and the one before it
+pub struct Release {
+    pub id: String,
+    pub seconds: u64,
+    pub changes: Vec<Change>
+}
+
+// An Atom feed with an entry per release listing the APIs it added and changed,
+// newest first
+pub fn atom(releases: &[Release], updated: u64) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str("  <id>urn:arucas-md:feed</id>\n");
+    xml.push_str("  <title>What's new in Arucas scripting</title>\n");
+    xml.push_str(&format!("  <updated>{}</updated>\n", date::timestamp(updated)));
+
+    for release in releases {
+        let added = release.changes.iter().filter(|change| change.kind == ChangeKind::Added).count();
+        let changed = release.changes.iter().filter(|change| change.kind == ChangeKind::Changed).count();
+        let mut parts = Vec::new();
+        if added > 0 {
+            parts.push(format!("{} added", added));
+        }
+        if changed > 0 {
+            parts.push(format!("{} changed", changed));
+        }
+
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <id>urn:arucas-md:{}</id>\n", html::escape(&release.id)));
+        xml.push_str(&format!(
+            "    <title>API changes of {}: {}</title>\n",
+            &date::timestamp(release.seconds)[..10], parts.join(", ")
+        ));
+        xml.push_str(&format!("    <updated>{}</updated>\n", date::timestamp(release.seconds)));
+        xml.push_str("    <author><name>Arucas</name></author>\n");
+        xml.push_str(&format!("    <content type=\"html\">{}</content>\n", html::escape(&html::from_markdown(&content(&release.changes)))));
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+fn content(changes: &[Change]) -> String {
+    let mut md = String::new();
+    for (kind, title) in [(ChangeKind::Added, "Added"), (ChangeKind::Changed, "Changed")] {
+        let matching: Vec<&Change> = changes.iter().filter(|change| change.kind == kind).collect();
+        if matching.is_empty() {
+            continue;
+        }
+
+        md.push_str("### ");
+        md.push_str(title);
+        md.push('\n');
+        for change in matching {
+            md.push_str("- ");
+            md.push_str(change.item.kind);
+            md.push_str(" `");
+            md.push_str(&change.item.signature);
+            md.push_str("`\n");
+        }
+        md.push('\n');
+    }
+    md
+}
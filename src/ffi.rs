@@ -0,0 +1,101 @@
+// The C API for embedding the converter, such as in the Arucas Gradle build through JNI or Panama.
+// `build.rs` generates `include/arucas_md.h` from the functions below, so each signature is kept
+// on one line and only uses the types it knows.
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+use crate::doc_parser::DocParser;
+use crate::embed;
+
+// A parsed document, opaque to C
+pub struct Document {
+    parser: DocParser
+}
+
+/// Parses documentation given as JSON with the options of `renderClasses` in the WebAssembly API,
+/// `options` may be null. Returns null if the document is invalid and, if `error` isn't null, points
+/// it at a message to be freed with `arucas_md_free_string`.
+///
+/// # Safety
+/// `json` and `options` must be null-terminated UTF-8, `error` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn arucas_md_parse(json: *const c_char, options: *const c_char, error: *mut *mut c_char) -> *mut Document {
+    let options = if options.is_null() { Ok("") } else { CStr::from_ptr(options).to_str() };
+    let result = match (CStr::from_ptr(json).to_str(), options) {
+        (Ok(json), Ok(options)) => embed::parse(json, options),
+        _ => Err("Input is not valid UTF-8".to_string())
+    };
+
+    match result {
+        Ok(parser) => Box::into_raw(Box::new(Document { parser })),
+        Err(message) => {
+            if !error.is_null() {
+                *error = CString::new(message.replace('\0', "")).unwrap().into_raw();
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Renders the markdown of Classes.md, null-terminated with its length, not counting the
+/// terminator, written to `length`. Free it with `arucas_md_free_buffer`.
+///
+/// # Safety
+/// `document` must come from `arucas_md_parse` and `length` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn arucas_md_render_classes(document: *const Document, length: *mut usize) -> *mut c_char {
+    // Writing to memory can't fail
+    let mut md = Vec::new();
+    let _ = (*document).parser.write_classes(&mut md);
+    buffer(md, length)
+}
+
+/// Renders the markdown of Extensions.md, as `arucas_md_render_classes`.
+///
+/// # Safety
+/// `document` must come from `arucas_md_parse` and `length` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn arucas_md_render_extensions(document: *const Document, length: *mut usize) -> *mut c_char {
+    let mut md = Vec::new();
+    let _ = (*document).parser.write_extensions(&mut md);
+    buffer(md, length)
+}
+
+/// Frees markdown returned by a render function, given the length it was returned with.
+///
+/// # Safety
+/// `buffer` must come from a render function and not be freed before.
+#[no_mangle]
+pub unsafe extern "C" fn arucas_md_free_buffer(buffer: *mut c_char, length: usize) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buffer as *mut u8, length + 1)));
+    }
+}
+
+/// Frees an error message from `arucas_md_parse`.
+///
+/// # Safety
+/// `string` must come from `arucas_md_parse` and not be freed before.
+#[no_mangle]
+pub unsafe extern "C" fn arucas_md_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Frees a parsed document.
+///
+/// # Safety
+/// `document` must come from `arucas_md_parse` and not be freed before.
+#[no_mangle]
+pub unsafe extern "C" fn arucas_md_free(document: *mut Document) {
+    if !document.is_null() {
+        drop(Box::from_raw(document));
+    }
+}
+
+// The markdown may hold NUL characters of its own, so its length is given as well
+unsafe fn buffer(mut md: Vec<u8>, length: *mut usize) -> *mut c_char {
+    *length = md.len();
+    md.push(0);
+    Box::into_raw(md.into_boxed_slice()) as *mut c_char
+}
@@ -0,0 +1,11 @@
+// Front matter for Hugo and Jekyll, `{title}`, `{weight}` and `{categories}` are
+// filled in for every page
+pub const TEMPLATE: &str = "---\ntitle: \"{title}\"\nweight: {weight}\ncategories: [\"{categories}\"]\n---\n\n";
+
+// The front matter of a page, empty if none was asked for
+pub fn page(template: Option<&str>, title: &str, weight: usize, category: &str) -> String {
+    let Some(template) = template else {
+        return String::new();
+    };
+    template.replace("{title}", title).replace("{weight}", &weight.to_string()).replace("{categories}", category)
+}
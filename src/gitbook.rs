@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+use std::path::Path;
+use crate::doc_parser::DocParser;
+use crate::output::{create_dir, group_by_category, write_file};
+
+pub const DIRECTORY: &str = "gitbook";
+
+const INTRODUCTION: &str = "# Arucas\n\nDocumentation for the built-in classes and extensions of Arucas.\n";
+
+// Writes a GitBook (or Honkit) book: a chapter per class and extension with
+// SUMMARY.md listing them, returns the number of chapters
+pub fn write(parser: &DocParser, path: &Path) -> Result<usize, String> {
+    for directory in ["classes", "extensions"] {
+        create_dir(&path.join(directory))?;
+    }
+
+    let mut summary = String::from("# Summary\n\n* [Introduction](README.md)\n");
+    let mut chapters = 0;
+
+    for (title, classes) in group_by_category(parser.classes()) {
+        summary.push_str(&format!("\n## {}\n\n", title));
+        for class in classes {
+            let chapter = format!("classes/{}.md", parser.file_name(&class.name));
+            summary.push_str(&format!("* [{}]({})\n", class.name, chapter));
+            write_file(&path.join(&chapter), &parser.render_class(class, &mut HashMap::new()))?;
+            chapters += 1;
+        }
+    }
+
+    summary.push_str("\n## Extensions\n\n");
+    for (name, functions) in parser.extensions() {
+        let chapter = format!("extensions/{}.md", parser.file_name(&name));
+        summary.push_str(&format!("* [{}]({})\n", name, chapter));
+        write_file(&path.join(&chapter), &parser.render_extension(&name, &functions, &mut HashMap::new()))?;
+        chapters += 1;
+    }
+
+    write_file(&path.join("README.md"), INTRODUCTION)?;
+    write_file(&path.join("SUMMARY.md"), &summary)?;
+    Ok(chapters)
+}
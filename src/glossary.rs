@@ -0,0 +1,124 @@
+use std::collections::{BTreeMap, BTreeSet};
+use crate::doc_parser::{Class, DocParser, Function};
+
+// Where a class or extension is rendered, given whether it is an extension and its name
+pub type Link<'a> = &'a dyn Fn(bool, &str) -> String;
+
+// Glossary.md: every type used in a signature and every class, with the class that
+// documents it and the classes and extensions that use it or mention it
+pub fn glossary(parser: &DocParser, link: Link) -> String {
+    let classes = parser.classes();
+    let extensions = parser.extensions();
+    let documented: BTreeSet<&str> = classes.iter().map(|class| class.name.as_str()).collect();
+
+    // Term to the (is extension, name) of everything that uses it
+    let mut terms: BTreeMap<String, BTreeSet<(bool, String)>> = BTreeMap::new();
+    for name in &documented {
+        terms.entry(name.to_string()).or_default();
+    }
+    for class in &classes {
+        for type_name in class_types(class) {
+            terms.entry(type_name).or_default().insert((false, class.name.clone()));
+        }
+    }
+    for (name, functions) in &extensions {
+        for type_name in functions.iter().flat_map(function_types) {
+            terms.entry(type_name).or_default().insert((true, name.clone()));
+        }
+    }
+
+    // Descriptions mentioning a term by name count as using it
+    let names: Vec<String> = terms.keys().cloned().collect();
+    let mut mention = |owner: (bool, &str), text: &str| {
+        let words: BTreeSet<&str> = text.split(|c: char| !c.is_alphanumeric()).collect();
+        for name in names.iter().filter(|name| words.contains(name.as_str())) {
+            terms.get_mut(name).unwrap().insert((owner.0, owner.1.to_string()));
+        }
+    };
+    for class in &classes {
+        for text in class_descriptions(class) {
+            mention((false, &class.name), text);
+        }
+    }
+    for (name, functions) in &extensions {
+        for text in functions.iter().flat_map(function_descriptions) {
+            mention((true, name), text);
+        }
+    }
+
+    let mut md = String::from("# Glossary\n");
+    for (term, users) in terms {
+        md.push_str("\n## ");
+        md.push_str(&term);
+        md.push_str("\n\n");
+        if documented.contains(term.as_str()) {
+            md.push_str(&format!("Documented in [{}]({}).\n", term, link(false, &term)));
+        } else {
+            md.push_str("Not documented as a class.\n");
+        }
+
+        let users: Vec<String> = users.iter()
+            .filter(|(extension, name)| *extension || *name != term)
+            .map(|(extension, name)| format!("[{}]({})", name, link(*extension, name)))
+            .collect();
+        if !users.is_empty() {
+            md.push_str("\nUsed by ");
+            md.push_str(&users.join(", "));
+            md.push_str(".\n");
+        }
+    }
+    md
+}
+
+fn class_types(class: &Class) -> Vec<String> {
+    let mut types = Vec::new();
+    for member in class.static_members.iter().chain(&class.members).flatten() {
+        types.extend(member.type_name.iter().flat_map(|type_name| split(type_name)));
+    }
+    for constructor in class.constructors.iter().flatten() {
+        types.extend(constructor.params.iter().flatten().flat_map(|param| split(&param.type_name)));
+    }
+    for function in class.methods.iter().chain(&class.static_methods).flatten() {
+        types.extend(function_types(function));
+    }
+    types
+}
+
+fn function_types(function: &Function) -> Vec<String> {
+    let mut types: Vec<String> = function.params.iter().flatten().flat_map(|param| split(&param.type_name)).collect();
+    types.extend(function.returns.iter().flat_map(|returns| split(&returns.type_name)));
+    types
+}
+
+fn split(type_name: &str) -> Vec<String> {
+    DocParser::type_names(type_name).map(str::to_string).collect()
+}
+
+fn class_descriptions(class: &Class) -> Vec<&str> {
+    let mut texts: Vec<&str> = class.desc.iter().flatten().map(String::as_str).collect();
+    for member in class.static_members.iter().chain(&class.members).flatten() {
+        texts.extend(member.desc.iter().flatten().map(String::as_str));
+    }
+    for constructor in class.constructors.iter().flatten() {
+        texts.extend(constructor.desc.iter().map(String::as_str));
+    }
+    for function in class.methods.iter().chain(&class.static_methods).flatten() {
+        texts.extend(function_descriptions(function));
+    }
+    texts
+}
+
+fn function_descriptions(function: &Function) -> Vec<&str> {
+    let mut texts: Vec<&str> = function.desc.iter().flatten().map(String::as_str).collect();
+    texts.extend(function.params.iter().flatten().map(|param| param.desc.as_str()));
+    texts.extend(function.returns.iter().map(|returns| returns.desc.as_str()));
+    texts
+}
+
+// The id GitHub gives a heading
+pub fn heading_slug(heading: &str) -> String {
+    heading.to_lowercase().chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_' || *c == ' ')
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
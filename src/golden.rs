@@ -0,0 +1,156 @@
+use std::{env, fs, process};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::doc_parser::{DocParser, RenderOptions};
+use crate::localize;
+use crate::migration;
+use crate::output;
+use crate::snapshot;
+
+// Renders every `<name>.json` fixture in the directory and compares the output with the goldens
+// `<name>/Classes.md` and `<name>/Extensions.md` beside it, panicking with a diff of any that differ.
+// Set `UPDATE_GOLDEN=1` to write the current output as the goldens instead.
+pub fn assert_render_matches(directory: impl AsRef<Path>) {
+    assert_render_matches_with(directory, &RenderOptions::default());
+}
+
+// As `assert_render_matches`, rendering with the given options
+pub fn assert_render_matches_with(directory: impl AsRef<Path>, options: &RenderOptions) {
+    if let Err(message) = render_matches(directory.as_ref(), options) {
+        panic!("{}", message);
+    }
+}
+
+// Each fixture is rendered as the converter writes Classes.md and Extensions.md: migrated to the
+// current layout, in English, with the newline policy and metadata of the options, and with the
+// hand-written regions of the goldens kept
+pub fn render_matches(directory: &Path, options: &RenderOptions) -> Result<(), String> {
+    let update = env::var_os("UPDATE_GOLDEN").is_some_and(|value| value != "0");
+    let entries = fs::read_dir(directory).map_err(|error| format!("Could not read {}: {}", directory.display(), error))?;
+    let mut fixtures: Vec<_> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect();
+    fixtures.sort();
+    if fixtures.is_empty() {
+        return Err(format!("No fixtures in {}", directory.display()));
+    }
+
+    let mut failures = Vec::new();
+    for fixture in fixtures {
+        let content = fs::read_to_string(&fixture).map_err(|error| format!("Could not read {}: {}", fixture.display(), error))?;
+        let mut json = serde_json::from_str(&content).map_err(|error| format!("Could not parse {}: {}", fixture.display(), error))?;
+        migration::migrate(&mut json).map_err(|message| format!("{}: {}", fixture.display(), message))?;
+        localize::localize(&mut json, "en", "en");
+        let mut parser = DocParser::from_json(json);
+        parser.options = options.clone();
+
+        // Rendered beside copies of the goldens, which is where their regions are kept from
+        let goldens = fixture.with_extension("");
+        let output = env::temp_dir().join(format!("arucas-md-golden-{}-{}", process::id(), RUNS.fetch_add(1, Ordering::Relaxed)));
+        let rendered = render(&parser, &goldens, &output);
+        let _ = fs::remove_dir_all(&output);
+
+        for (name, actual) in rendered.map_err(|message| format!("Could not render {}: {}", fixture.display(), message))? {
+            let golden = goldens.join(name);
+            if update {
+                fs::create_dir_all(&goldens).and_then(|_| fs::write(&golden, &actual))
+                    .map_err(|error| format!("Could not write {}: {}", golden.display(), error))?;
+                continue;
+            }
+
+            let actual = String::from_utf8_lossy(&actual);
+            match fs::read_to_string(&golden) {
+                Ok(expected) if expected == actual => { }
+                Ok(expected) => failures.push(format!("{} differs:\n{}", golden.display(), diff(&expected, &actual))),
+                Err(_) => failures.push(format!("{} is missing, run with UPDATE_GOLDEN=1 to create it", golden.display()))
+            }
+        }
+    }
+
+    if failures.is_empty() { Ok(()) } else { Err(failures.join("\n")) }
+}
+
+// Fixtures may be rendered by several tests at once, each in a directory of its own
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+fn render(parser: &DocParser, goldens: &Path, output: &Path) -> Result<Vec<(&'static str, Vec<u8>)>, String> {
+    fs::create_dir_all(output).map_err(|error| format!("Could not create {}: {}", output.display(), error))?;
+    for name in PAGES {
+        if let Ok(golden) = fs::read(goldens.join(name)) {
+            fs::write(output.join(name), golden).map_err(|error| format!("Could not copy {}: {}", name, error))?;
+        }
+    }
+
+    let input = parser.options.metadata.as_ref()
+        .map(|_| snapshot::hash(&serde_json::to_string(parser.json()).unwrap()))
+        .unwrap_or_default();
+    output::write_markdown(parser, output, &input, "")?;
+    PAGES.iter().map(|name| {
+        fs::read(output.join(name)).map(|page| (*name, page)).map_err(|error| format!("Could not read {}: {}", name, error))
+    }).collect()
+}
+
+const PAGES: [&str; 2] = ["Classes.md", "Extensions.md"];
+
+// Lines only in the golden start with `-` and lines only in the output with `+`,
+// with a few unchanged lines around each change
+pub fn diff(expected: &str, actual: &str) -> String {
+    const CONTEXT: usize = 2;
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // Only the part between the common start and end needs comparing
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    // Longest common subsequence of the remaining lines
+    let mut lengths = vec![vec![0usize; new_middle.len() + 1]; old_middle.len() + 1];
+    for i in (0..old_middle.len()).rev() {
+        for j in (0..new_middle.len()).rev() {
+            lengths[i][j] = if old_middle[i] == new_middle[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines: Vec<(char, usize, &str)> = old[..prefix].iter().enumerate().map(|(i, line)| (' ', i + 1, *line)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < old_middle.len() || j < new_middle.len() {
+        if i < old_middle.len() && j < new_middle.len() && old_middle[i] == new_middle[j] {
+            lines.push((' ', prefix + i + 1, old_middle[i]));
+            i += 1;
+            j += 1;
+        } else if i < old_middle.len() && (j == new_middle.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            lines.push(('-', prefix + i + 1, old_middle[i]));
+            i += 1;
+        } else {
+            lines.push(('+', prefix + j + 1, new_middle[j]));
+            j += 1;
+        }
+    }
+    let end = old.len() - suffix;
+    lines.extend(old[end..].iter().enumerate().map(|(i, line)| (' ', end + i + 1, *line)));
+
+    let changed: Vec<usize> = lines.iter().enumerate().filter(|(_, line)| line.0 != ' ').map(|(i, _)| i).collect();
+    let mut diff = String::new();
+    let mut last = None;
+    for (i, (mark, number, line)) in lines.iter().enumerate() {
+        if !changed.iter().any(|change| change.abs_diff(i) <= CONTEXT) {
+            continue;
+        }
+        if last.is_some_and(|last| last + 1 != i) {
+            diff.push_str("...\n");
+        }
+        diff.push_str(&format!("{}{:>5} | {}\n", mark, number, line));
+        last = Some(i);
+    }
+    if diff.is_empty() {
+        // Only the line endings or the final newline differ
+        diff.push_str("  (the lines are equal, their endings differ)\n");
+    }
+    diff
+}
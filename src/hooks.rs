@@ -0,0 +1,20 @@
+use crate::doc_parser::{Class, Function, Member};
+
+// Called around each class, function and member as it is rendered, with the markdown written
+// so far. What a hook pushes before an item goes above its anchor and heading, and what it pushes
+// after goes below its examples. Every hook does nothing unless implemented.
+pub trait RenderHooks: Send + Sync {
+    fn before_class(&self, _class: &Class, _md: &mut String) { }
+
+    fn after_class(&self, _class: &Class, _md: &mut String) { }
+
+    // The owner is the class for static methods, `<Class>` for methods, and none for extensions
+    fn before_function(&self, _owner: Option<&str>, _function: &Function, _md: &mut String) { }
+
+    fn after_function(&self, _owner: Option<&str>, _function: &Function, _md: &mut String) { }
+
+    // The owner is as for functions, and none for globals
+    fn before_member(&self, _owner: Option<&str>, _member: &Member, _md: &mut String) { }
+
+    fn after_member(&self, _owner: Option<&str>, _member: &Member, _md: &mut String) { }
+}
@@ -0,0 +1,334 @@
+use std::mem;
+
+// How code blocks and anchors are written, other markup is plain (X)HTML
+pub struct Flavour {
+    pub code_block: fn(language: &str, title: Option<&str>, code: &str) -> String,
+    pub anchor: fn(id: &str) -> String,
+    // Adjacent titled code blocks, such as the variants of an example, become tabs
+    pub tabs: bool
+}
+
+pub const HTML: Flavour = Flavour {
+    code_block: |language, title, code| {
+        let title = match title {
+            Some(title) => format!("<p class=\"code-title\">{}</p>\n", escape(title)),
+            None => String::new()
+        };
+        let class = if language.is_empty() { String::new() } else { format!(" class=\"language-{}\"", escape(language)) };
+        format!("{}<pre><code{}>{}</code></pre>\n", title, class, escape(code))
+    },
+    anchor: |id| format!("<a id=\"{}\"></a>\n", escape(id)),
+    tabs: false
+};
+
+pub fn from_markdown(md: &str) -> String {
+    convert(md, &HTML)
+}
+
+// Converts the markdown written by the renderer. Only what the renderer produces
+// is understood: headings, nested lists, fenced code, paragraphs, blockquotes, tables,
+// inline code, bold text, links and the anchor tags added by `--anchors`.
+pub fn convert(md: &str, flavour: &Flavour) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut depth = 0;
+    let mut code: Option<(&str, Option<&str>, String)> = None;
+    let mut tabs: Vec<(&str, String)> = Vec::new();
+    let mut tab_groups = 0;
+    let mut table: Vec<&str> = Vec::new();
+    let mut quote = false;
+    // A blank line in a list, which ends it unless the next line is indented to continue the item
+    let mut list_break = false;
+
+    for line in md.lines() {
+        if let Some((language, title, block)) = &mut code {
+            if line.trim_end() == "```" {
+                match title {
+                    Some(title) if flavour.tabs => tabs.push((title, (flavour.code_block)(language, None, block))),
+                    _ => html.push_str(&(flavour.code_block)(language, *title, block))
+                }
+                code = None;
+            } else {
+                block.push_str(line);
+                block.push('\n');
+            }
+            continue;
+        }
+
+        if !tabs.is_empty() && fence_title(line).is_none() {
+            close_tabs(&mut html, &mut tabs, &mut tab_groups);
+        }
+
+        if line.starts_with('|') {
+            close_paragraph(&mut html, &mut paragraph);
+            close_lists(&mut html, &mut depth, 0);
+            table.push(line);
+            continue;
+        }
+        close_table(&mut html, &mut table);
+
+        // Quoted lines are the paragraphs of a blockquote
+        let quoted = line.strip_prefix('>').map(|rest| rest.strip_prefix(' ').unwrap_or(rest));
+        if quoted.is_some() != quote {
+            close_paragraph(&mut html, &mut paragraph);
+            close_lists(&mut html, &mut depth, 0);
+            html.push_str(if quote { "</blockquote>\n" } else { "<blockquote>\n" });
+            quote = !quote;
+        }
+        if let Some(quoted) = quoted {
+            if quoted.trim().is_empty() {
+                close_paragraph(&mut html, &mut paragraph);
+            } else {
+                paragraph.push(quoted);
+            }
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let list_depth = if trimmed.starts_with("- ") { (line.len() - trimmed.len()) / 2 + 1 } else { 0 };
+
+        if depth > 0 && line.trim().is_empty() {
+            list_break = true;
+            continue;
+        }
+        if mem::take(&mut list_break) && list_depth == 0 {
+            if line.starts_with("  ") && !is_block_start(trimmed) {
+                html.push_str("\n<p>");
+                html.push_str(&inline(trimmed));
+                html.push_str("</p>");
+                continue;
+            }
+            close_lists(&mut html, &mut depth, 0);
+        }
+
+        // Lines directly after a list item continue it
+        if list_depth == 0 && depth > 0 && !line.trim().is_empty() && !is_block_start(line) {
+            html.push('\n');
+            html.push_str(&inline(line));
+            continue;
+        }
+
+        if list_depth == 0 && !line.trim().is_empty() && !is_block_start(line) {
+            paragraph.push(line);
+            continue;
+        }
+
+        close_paragraph(&mut html, &mut paragraph);
+        if list_depth == 0 {
+            close_lists(&mut html, &mut depth, 0);
+        }
+
+        if let Some(info) = line.strip_prefix("```") {
+            let language = info.split_whitespace().next().unwrap_or_default();
+            code = Some((language, fence_title(line), String::new()));
+        } else if let Some((level, title)) = heading(line) {
+            html.push_str(&format!("<h{}>{}</h{}>\n", level, inline(title), level));
+        } else if let Some(id) = anchor(line) {
+            html.push_str(&(flavour.anchor)(id));
+        } else if list_depth > 0 {
+            if list_depth > depth {
+                while depth < list_depth {
+                    html.push_str("<ul>\n");
+                    depth += 1;
+                }
+            } else {
+                close_lists(&mut html, &mut depth, list_depth);
+                html.push_str("</li>\n");
+            }
+            html.push_str("<li>");
+            html.push_str(&inline(&trimmed[2..]));
+        }
+    }
+
+    close_paragraph(&mut html, &mut paragraph);
+    close_lists(&mut html, &mut depth, 0);
+    close_tabs(&mut html, &mut tabs, &mut tab_groups);
+    close_table(&mut html, &mut table);
+    if quote {
+        html.push_str("</blockquote>\n");
+    }
+    if let Some((language, title, block)) = code {
+        html.push_str(&(flavour.code_block)(language, title, &block));
+    }
+    html
+}
+
+// A complete page around the converted body
+pub fn page(title: &str, body: &str, stylesheet: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+        <link rel=\"stylesheet\" href=\"{}\">\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape(title), escape(stylesheet), body
+    )
+}
+
+pub const STYLESHEET: &str = "body { font-family: sans-serif; max-width: 60em; margin: 0 auto; padding: 1em; line-height: 1.5; }
+code { background: #f3f3f3; padding: 0 0.2em; border-radius: 3px; }
+pre { background: #f3f3f3; padding: 0.8em; overflow-x: auto; border-radius: 4px; }
+pre code { padding: 0; }
+img { max-width: 100%; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #cccccc; padding: 0.2em 0.6em; text-align: left; }
+blockquote { border-left: 4px solid #cccccc; margin: 1em 0; padding: 0 1em; }
+.code-title { margin: 0.5em 0 0; font-weight: bold; }
+h3 { margin-top: 1.8em; }
+";
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+        Some((level, line[level + 1..].trim()))
+    } else {
+        None
+    }
+}
+
+pub fn anchor(line: &str) -> Option<&str> {
+    line.strip_prefix("<a id=\"")?.strip_suffix("\"></a>")
+}
+
+fn is_block_start(line: &str) -> bool {
+    line.starts_with("```") || anchor(line).is_some() || heading(line).is_some()
+}
+
+// The title of a code block opened with ```kt title="..."
+pub fn fence_title(line: &str) -> Option<&str> {
+    let info = line.strip_prefix("```")?;
+    info.split_once("title=\"")?.1.split('"').next()
+}
+
+// Radio buttons switch between the tabs, so no script is needed
+fn close_tabs(html: &mut String, tabs: &mut Vec<(&str, String)>, groups: &mut usize) {
+    if tabs.is_empty() {
+        return;
+    }
+    *groups += 1;
+    html.push_str("<div class=\"tabs\">\n");
+    for (i, (title, block)) in tabs.drain(..).enumerate() {
+        let id = format!("tabs-{}-{}", groups, i);
+        let checked = if i == 0 { " checked" } else { "" };
+        html.push_str(&format!(
+            "<input type=\"radio\" name=\"tabs-{}\" id=\"{}\"{}><label for=\"{}\">{}</label>\n<div class=\"tab\">{}</div>\n",
+            groups, id, checked, id, escape(title), block.trim_end()
+        ));
+    }
+    html.push_str("</div>\n");
+}
+
+// The first row is the header, the row of dashes below it is left out
+fn close_table(html: &mut String, table: &mut Vec<&str>) {
+    if table.is_empty() {
+        return;
+    }
+    html.push_str("<table>\n");
+    for (i, row) in table.drain(..).enumerate() {
+        if i == 1 && row.chars().all(|c| matches!(c, '|' | '-' | ':' | ' ')) {
+            continue;
+        }
+        let cell = if i == 0 { "th" } else { "td" };
+        html.push_str("<tr>");
+        for value in cells(row) {
+            html.push_str(&format!("<{}>{}</{}>", cell, inline(&value), cell));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+}
+
+// The cells of a table row, `\|` is a pipe within a cell
+fn cells(row: &str) -> Vec<String> {
+    let row = row.trim().trim_start_matches('|');
+    let row = row.strip_suffix('|').filter(|rest| !rest.ends_with('\\')).unwrap_or(row);
+    let mut cells = vec![String::new()];
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => cells.last_mut().unwrap().push(chars.next().unwrap()),
+            '|' => cells.push(String::new()),
+            c => cells.last_mut().unwrap().push(c)
+        }
+    }
+    cells.iter().map(|cell| cell.trim().to_string()).collect()
+}
+
+fn close_paragraph(html: &mut String, paragraph: &mut Vec<&str>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    html.push_str("<p>");
+    html.push_str(&paragraph.iter().map(|line| inline(line)).collect::<Vec<String>>().join("\n"));
+    html.push_str("</p>\n");
+    paragraph.clear();
+}
+
+fn close_lists(html: &mut String, depth: &mut usize, target: usize) {
+    while *depth > target {
+        html.push_str("</li>\n</ul>\n");
+        *depth -= 1;
+    }
+}
+
+// Inline code, bold text, images and links, everything else is escaped
+fn inline(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(['`', '[', '!']) {
+        html.push_str(&bold(&rest[..start]));
+        rest = &rest[start..];
+
+        if let Some((alt, source, end)) = rest.strip_prefix('!').and_then(link_at) {
+            html.push_str(&format!("<img src=\"{}\" alt=\"{}\">", escape(source), escape(alt)));
+            rest = &rest[end + 1..];
+        } else if let Some(text) = rest.strip_prefix('!') {
+            html.push('!');
+            rest = text;
+        } else if let Some(code) = rest.strip_prefix('`') {
+            let end = code.find('`').unwrap_or(code.len());
+            html.push_str("<code>");
+            html.push_str(&escape(&code[..end]));
+            html.push_str("</code>");
+            rest = code.get(end + 1..).unwrap_or_default();
+        } else if let Some((label, target, end)) = link_at(rest) {
+            // Labels may be code themselves, such as links to other items
+            html.push_str(&format!("<a href=\"{}\">{}</a>", escape(target), inline(label)));
+            rest = &rest[end..];
+        } else {
+            html.push('[');
+            rest = &rest[1..];
+        }
+    }
+    html.push_str(&bold(rest));
+    html
+}
+
+// A `[label](target)` link at the start of the text, with the length it takes up
+pub fn link_at(text: &str) -> Option<(&str, &str, usize)> {
+    if !text.starts_with('[') {
+        return None;
+    }
+    let close = text.find("](")?;
+    let end = close + text[close..].find(')')?;
+    Some((&text[1..close], &text[close + 2..end], end + 1))
+}
+
+fn bold(text: &str) -> String {
+    if text.matches("**").count() % 2 == 1 {
+        return escape(text);
+    }
+
+    let mut html = String::new();
+    for (i, part) in text.split("**").enumerate() {
+        if i % 2 == 1 {
+            html.push_str("<strong>");
+            html.push_str(&escape(part));
+            html.push_str("</strong>");
+        } else {
+            html.push_str(&escape(part));
+        }
+    }
+    html
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::metadata;
+
+// Marks a region written by hand in a generated page, kept as it is whenever the page is regenerated
+pub const START: &str = "<!-- arucas-md:keep:start -->";
+pub const END: &str = "<!-- arucas-md:keep:end -->";
+
+// A region of a page to keep, with where it was so it can be put back in the same place
+pub struct Region {
+    // The lines of the region with its markers, as they were written
+    lines: Vec<String>,
+    // The last heading before the region that only appears once in the page, such as a class's
+    heading: Option<String>,
+    // The last line with something on it before the region, and how many times it appeared
+    // before that since the heading. None if nothing comes before the region
+    after: Option<(String, usize)>
+}
+
+// The regions to keep from the page currently at the path, none if there is no page yet
+pub fn read(path: &Path) -> Vec<Region> {
+    fs::read_to_string(path).map(|content| regions(&content)).unwrap_or_default()
+}
+
+pub fn regions(content: &str) -> Vec<Region> {
+    let mut headings: HashMap<&str, usize> = HashMap::new();
+    for line in content.split('\n').map(str::trim).filter(|line| line.starts_with('#')) {
+        *headings.entry(line).or_default() += 1;
+    }
+
+    let mut regions = Vec::new();
+    let (mut heading, mut after) = (None, None);
+    // How many times each line appeared since the heading
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    let mut lines = content.split('\n');
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed == START {
+            // A region that isn't ended runs to the end of the page, rather than losing what's in it
+            let mut region = vec![line.to_string()];
+            for line in lines.by_ref() {
+                region.push(line.to_string());
+                if line.trim() == END {
+                    break;
+                }
+            }
+            regions.push(Region { lines: region, heading: heading.clone(), after: after.clone() });
+        } else if !trimmed.is_empty() && !metadata::is_comment(trimmed) {
+            if headings.get(trimmed) == Some(&1) {
+                heading = Some(trimmed.to_string());
+                seen.clear();
+            }
+            let count = seen.entry(trimmed).or_default();
+            after = Some((trimmed.to_string(), *count));
+            *count += 1;
+        }
+    }
+    regions
+}
+
+// The newly generated page with the regions put back. Each goes after the line it followed
+// under its heading, or right after the heading if that line is gone, or at the end of the
+// page if the heading is too
+pub fn merge(page: &str, regions: &[Region]) -> String {
+    if regions.is_empty() {
+        return page.to_string();
+    }
+
+    let lines: Vec<&str> = page.split('\n').collect();
+    // Where the page ends, before the empty string after its last newline
+    let end = if lines.last() == Some(&"") { lines.len() - 1 } else { lines.len() };
+
+    let mut insertions: Vec<(usize, &Region)> = Vec::new();
+    for region in regions {
+        let heading = match &region.heading {
+            Some(heading) => lines[..end].iter().position(|line| line.trim() == heading),
+            None => Some(0)
+        };
+        let position = match (&region.after, heading) {
+            (None, _) => Some(0),
+            (Some(_), None) => None,
+            (Some((after, count)), Some(heading)) => lines[heading..end].iter().enumerate()
+                .filter(|(_, line)| line.trim() == after)
+                .nth(*count)
+                .map(|(i, _)| heading + i + 1)
+                .or_else(|| region.heading.as_ref().map(|_| heading + 1))
+        };
+        insertions.push((position.unwrap_or(end), region));
+    }
+    // Stable, so regions that followed the same line stay in order
+    insertions.sort_by_key(|(position, _)| *position);
+
+    let blank = if page.contains("\r\n") { "\r" } else { "" };
+    let mut merged: Vec<&str> = Vec::new();
+    let mut insertions = insertions.into_iter().peekable();
+    for (i, line) in lines.iter().enumerate() {
+        while let Some((_, region)) = insertions.next_if(|(position, _)| *position == i) {
+            // Set apart from the generated lines by blank lines
+            if i > 0 {
+                merged.push(blank);
+            }
+            merged.extend(region.lines.iter().map(String::as_str));
+            if i < end && !line.trim().is_empty() && insertions.peek().is_none_or(|(position, _)| *position != i) {
+                merged.push(blank);
+            }
+        }
+        merged.push(line);
+    }
+    merged.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge, regions};
+
+    const KEPT: &str = "<!-- arucas-md:keep:start -->\nWritten by hand\n<!-- arucas-md:keep:end -->";
+
+    #[test]
+    fn puts_regions_back_after_the_line_they_followed() {
+        let old = format!("# List class\n\n## Methods\n\n### `<List>.get(index)`\n{}\n\n### `<List>.size()`\n", KEPT);
+        let new = "# List class\nA list.\n\n## Methods\n\n### `<List>.add(value)`\n\n### `<List>.get(index)`\n\n### `<List>.size()`\n";
+        assert_eq!(
+            merge(new, &regions(&old)),
+            format!("# List class\nA list.\n\n## Methods\n\n### `<List>.add(value)`\n\n### `<List>.get(index)`\n\n{}\n\n### `<List>.size()`\n", KEPT)
+        );
+    }
+
+    #[test]
+    fn counts_repeated_lines_since_the_heading() {
+        let old = format!("# Page\n\n## A\n- Example:\n- Example:\n{}\n\n## B\n- Example:\n", KEPT);
+        let new = "# Page\n\n## A\n- Example:\n- Example:\n- Example:\n\n## B\n- Example:\n";
+        assert_eq!(
+            merge(new, &regions(&old)),
+            format!("# Page\n\n## A\n- Example:\n- Example:\n\n{}\n\n- Example:\n\n## B\n- Example:\n", KEPT)
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_heading_then_the_end() {
+        let old = format!("# Page\n\n## A\nGone\n{}\n\n## B\nText\n{}\n", KEPT, KEPT);
+        let new = "# Page\n\n## A\nNew\n";
+        assert_eq!(merge(new, &regions(&old)), format!("# Page\n\n## A\n\n{}\n\nNew\n\n{}\n", KEPT, KEPT));
+    }
+
+    #[test]
+    fn leaves_pages_without_regions_alone() {
+        let page = "# Page\r\n\r\nText\r\n";
+        assert!(regions(page).is_empty());
+        assert_eq!(merge(page, &regions(page)), page);
+        assert_eq!(merge(page, &regions(&format!("{}\r\n# Page\r\n", KEPT))), format!("{}\r\n\r\n# Page\r\n\r\nText\r\n", KEPT));
+    }
+}
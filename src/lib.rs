@@ -0,0 +1,63 @@
+// The converter as a library, the binary is the command line around it
+pub mod algolia;
+pub mod aliases;
+pub mod all_methods;
+pub mod annotation;
+pub mod assets;
+pub mod bbcode;
+pub mod browse;
+pub mod config;
+pub mod confluence;
+pub mod contributors;
+pub mod date;
+pub mod diagram;
+pub mod diff;
+pub mod discord;
+pub mod doc_parser;
+pub mod docset;
+pub mod embed;
+pub mod examples;
+pub mod feed;
+pub mod ffi;
+pub mod front_matter;
+pub mod gitbook;
+pub mod glossary;
+pub mod golden;
+pub mod hooks;
+pub mod html;
+pub mod keep;
+pub mod links;
+pub mod lint;
+pub mod locale;
+pub mod localize;
+pub mod log;
+pub mod metadata;
+pub mod migration;
+pub mod notion;
+pub mod obsidian;
+pub mod output;
+pub mod overlay;
+pub mod pdf;
+pub mod plugin;
+pub mod progress;
+pub mod quality;
+pub mod reference;
+pub mod run_report;
+pub mod sample;
+pub mod schema;
+pub mod search;
+pub mod show;
+pub mod site;
+pub mod snapshot;
+pub mod sources;
+pub mod spelling;
+pub mod split;
+pub mod sqlite;
+pub mod stream;
+pub mod text;
+pub mod toml;
+pub mod wiki;
+pub mod yaml;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
+pub mod zip;
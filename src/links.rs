@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::doc_parser::DocParser;
+use crate::glossary;
+use crate::html;
+use crate::progress::Progress;
+
+// Checks every relative link in the pages leads to a file that exists and, if it has
+// a fragment, to a heading or anchor within it. Returns each broken link with the
+// page and line it is on.
+pub fn check(pages: &[PathBuf]) -> Vec<String> {
+    let mut anchors: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+    let mut broken = Vec::new();
+
+    let progress = Progress::new("Checking links", pages.len());
+    for page in pages {
+        progress.advance();
+        let Ok(content) = fs::read_to_string(page) else {
+            continue;
+        };
+        let directory = page.parent().unwrap_or(Path::new(""));
+
+        let mut code = false;
+        for (i, line) in content.lines().enumerate() {
+            if line.starts_with("```") {
+                code = !code;
+            }
+            if code {
+                continue;
+            }
+
+            for target in links(line) {
+                if target.contains("://") || target.starts_with("mailto:") {
+                    continue;
+                }
+                let (file, fragment) = target.split_once('#').unwrap_or((target, ""));
+                let path = if file.is_empty() { page.clone() } else { directory.join(file) };
+                let resolves = path.exists() && (fragment.is_empty() || anchors.entry(path.clone())
+                    .or_insert_with(|| page_anchors(&path))
+                    .contains(fragment));
+                if !resolves {
+                    broken.push(format!("{}:{}: broken link '{}'", page.display(), i + 1, target));
+                }
+            }
+        }
+    }
+    broken
+}
+
+// Deprecations pointing at a replacement such as `<List>.addAll(collection)` that isn't documented
+pub fn check_replacements(parser: &DocParser) -> Vec<String> {
+    let mut broken = Vec::new();
+    let mut check = |location: String, deprecated: &Option<Vec<String>>| {
+        let text = deprecated.iter().flatten().cloned().collect::<Vec<String>>().join(" ");
+        for (i, span) in text.split('`').enumerate() {
+            let Some((name, _)) = span.split_once('(').filter(|_| i % 2 == 1) else {
+                continue;
+            };
+            let name = name.trim_start_matches('<').replacen(">.", ".", 1);
+            if !parser.documents(&name) {
+                broken.push(format!("{}: deprecation refers to '{}', which is not documented", location, name));
+            }
+        }
+    };
+
+    for class in parser.classes() {
+        for function in class.methods.iter().chain(class.static_methods.iter()).flatten() {
+            check(format!("{}.{}", class.name, function.name), &function.deprecated);
+        }
+    }
+    for (_, functions) in parser.extensions() {
+        for function in &functions {
+            check(function.name.clone(), &function.deprecated);
+        }
+    }
+    broken
+}
+
+// The targets of the links and images on a line
+fn links(line: &str) -> Vec<&str> {
+    let mut targets = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        rest = &rest[start..];
+        match html::link_at(rest) {
+            Some((_, target, end)) => {
+                targets.push(target);
+                rest = &rest[end..];
+            }
+            None => rest = &rest[1..]
+        }
+    }
+    targets
+}
+
+// The ids a page can be linked to: its `<a id>` anchors and the slugs of its headings,
+// repeated slugs numbered as GitHub does
+fn page_anchors(path: &Path) -> HashSet<String> {
+    let mut anchors = HashSet::new();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut code = false;
+    for line in fs::read_to_string(path).unwrap_or_default().lines() {
+        if line.starts_with("```") {
+            code = !code;
+        } else if code {
+            continue;
+        } else if let Some(id) = html::anchor(line) {
+            anchors.insert(id.to_string());
+        } else if let Some((_, title)) = html::heading(line) {
+            let slug = glossary::heading_slug(title);
+            let count = counts.entry(slug.clone()).or_insert(0);
+            anchors.insert(if *count == 0 { slug } else { format!("{}-{}", slug, count) });
+            *count += 1;
+        }
+    }
+    anchors
+}
@@ -0,0 +1,152 @@
+use serde::Serialize;
+use crate::config::Config;
+use crate::doc_parser::{DocParser, Function, Param};
+use crate::examples;
+use crate::spelling::Words;
+
+// Every rule with whether it is enabled when the config doesn't mention it
+pub const RULES: &[(&str, bool)] = &[
+    ("example-syntax", true),
+    ("description-empty", true),
+    ("description-period", true),
+    ("param-description", true),
+    ("deprecated-replacement", true),
+    ("type-unknown", false),
+    ("spelling", false)
+];
+
+#[derive(Serialize)]
+pub struct Problem {
+    pub rule: &'static str,
+    pub location: String,
+    pub message: String
+}
+
+struct Linter<'a> {
+    config: &'a Config,
+    // Names of the documented classes and type aliases, which are the types that can be used
+    classes: Vec<String>,
+    // Only loaded when the spelling rule is enabled
+    words: Option<Words>,
+    problems: Vec<Problem>
+}
+
+pub fn lint(parser: &DocParser, config: &Config) -> Result<Vec<Problem>, String> {
+    let classes = parser.classes();
+    let aliases = parser.type_aliases();
+    let names = classes.iter().map(|class| class.name.clone()).chain(aliases.iter().map(|alias| alias.name.clone())).collect();
+    let words = match config.lint.get("spelling").copied().unwrap_or(false) {
+        true => Some(Words::load(&config.spelling, parser)?),
+        false => None
+    };
+    let mut linter = Linter { config, classes: names, words, problems: Vec::new() };
+
+    for class in classes {
+        linter.check_description(&class.name, &class.desc);
+
+        let members = class.static_members.iter().chain(class.members.iter()).flatten();
+        for member in members {
+            let location = format!("{}.{}", class.name, member.name);
+            linter.check_description(&location, &member.desc);
+            if let Some(type_name) = &member.type_name {
+                linter.check_type(&location, type_name);
+            }
+        }
+
+        if let Some(constructors) = &class.constructors {
+            for constructor in constructors {
+                let location = format!("new {}", class.name);
+                linter.check_description(&location, &Some(constructor.desc.clone()));
+                linter.check_params(&location, &constructor.params);
+            }
+        }
+
+        let functions = class.methods.iter().chain(class.static_methods.iter()).flatten();
+        for function in functions {
+            linter.check_function(&format!("{}.{}", class.name, function.name), function);
+        }
+    }
+
+    for (_, functions) in parser.extensions() {
+        for function in &functions {
+            linter.check_function(&function.name, function);
+        }
+    }
+
+    for alias in &aliases {
+        linter.check_type(&alias.name, &alias.type_name);
+    }
+
+    for example in examples::collect(parser) {
+        if let Err(message) = examples::check_syntax(&example.code) {
+            linter.report("example-syntax", &example.location, message);
+        }
+    }
+
+    Ok(linter.problems)
+}
+
+impl Linter<'_> {
+    fn report(&mut self, rule: &'static str, location: &str, message: String) {
+        let default = RULES.iter().find(|(name, _)| *name == rule).is_some_and(|(_, enabled)| *enabled);
+        if self.config.lint.get(rule).copied().unwrap_or(default) {
+            self.problems.push(Problem { rule, location: location.to_string(), message });
+        }
+    }
+
+    fn check_function(&mut self, location: &str, function: &Function) {
+        self.check_description(location, &function.desc);
+        self.check_params(location, &function.params);
+        if let Some(returns) = &function.returns {
+            self.check_type(location, &returns.type_name);
+        }
+
+        if let Some(deprecated) = &function.deprecated {
+            // A replacement is expected to be referenced as code
+            let text = deprecated.join(" ");
+            if !text.contains(['`', '\'', '(']) {
+                self.report("deprecated-replacement", location, "deprecation does not name a replacement".to_string());
+            }
+        }
+    }
+
+    fn check_description(&mut self, location: &str, desc: &Option<Vec<String>>) {
+        self.check_spelling(location, desc.as_deref().unwrap_or_default());
+        let lines: Vec<&str> = desc.iter().flatten().map(|line| line.trim()).filter(|line| !line.is_empty()).collect();
+        match lines.last() {
+            None => self.report("description-empty", location, "description is empty".to_string()),
+            Some(last) if !last.ends_with(['.', '!', '?', ':']) && !last.ends_with("```") => {
+                self.report("description-period", location, "description does not end with a period".to_string());
+            }
+            _ => { }
+        }
+    }
+
+    fn check_params(&mut self, location: &str, params: &Option<Vec<Param>>) {
+        for param in params.iter().flatten() {
+            if param.desc.trim().is_empty() {
+                self.report("param-description", location, format!("parameter '{}' has no description", param.name));
+            }
+            self.check_spelling(location, std::slice::from_ref(&param.desc));
+            self.check_type(location, &param.type_name);
+        }
+    }
+
+    fn check_spelling(&mut self, location: &str, lines: &[String]) {
+        let Some(words) = &self.words else {
+            return;
+        };
+        for word in words.misspelled(lines) {
+            self.report("spelling", location, format!("'{}' may be misspelled", word));
+        }
+    }
+
+    // Each type of a union must be a documented class or alias
+    fn check_type(&mut self, location: &str, type_name: &str) {
+        for name in DocParser::type_names(type_name) {
+            if !self.classes.iter().any(|class| class == name) {
+                self.report("type-unknown", location, format!("type '{}' is not a documented class or alias", name));
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::fs;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use crate::toml;
+
+// Fixed strings used in the generated documentation, any missing
+// from a translation file fall back to English. The derived functions need every string,
+// they are only called by the impls below once English has filled in the missing ones
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(remote = "Self")]
+pub struct Locale {
+    pub class_heading: String,
+    pub class_summary: String,
+    pub import: String,
+    pub no_import: String,
+    pub fully_documented: String,
+    pub category: String,
+    pub tags: String,
+    pub static_members: String,
+    pub members: String,
+    pub constructors: String,
+    pub methods: String,
+    pub static_methods: String,
+    pub deprecated: String,
+    pub description: String,
+    pub parameter: String,
+    pub parameters: String,
+    pub returns: String,
+    pub throws: String,
+    pub error: String,
+    pub type_name: String,
+    pub assignable: String,
+    pub example: String,
+    pub examples: String,
+    pub view_source: String,
+    pub see_also: String,
+    pub note: String,
+    pub warning: String,
+    pub resources: String,
+    pub contributed_by: String,
+    pub experimental: String,
+    pub experimental_desc: String,
+    pub globals: String,
+    pub globals_summary: String,
+    pub module: String,
+    pub returns_this: String,
+    pub thrown_when: String,
+    pub properties: String,
+    pub get: String,
+    pub set: String,
+    pub output: String,
+    pub read_only: String,
+    pub name: String,
+    pub value: String
+}
+
+// English is the translation file in `locales/`, the single place its strings are written
+const ENGLISH: &str = include_str!("../locales/en.toml");
+
+impl Default for Locale {
+    fn default() -> Locale {
+        Locale::with_strings(Value::Null).expect("locales/en.toml has every string")
+    }
+}
+
+// A translation only needs the strings it translates, so it is read on top of English
+impl<'de> Deserialize<'de> for Locale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Locale, D::Error> {
+        Locale::with_strings(Value::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Locale {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Locale::serialize(self, serializer)
+    }
+}
+
+impl Locale {
+    // English with the strings of the table in place of its own
+    pub fn with_strings(strings: Value) -> Result<Locale, String> {
+        let mut english = toml::parse(ENGLISH)?;
+        match (english.as_object_mut(), strings) {
+            (_, Value::Null) => { }
+            (Some(english), Value::Object(strings)) => english.extend(strings),
+            (_, other) => return Err(format!("expected a table of strings, found {}", other))
+        }
+        Locale::deserialize(english).map_err(|error| error.to_string())
+    }
+
+    // Accepts either a path to a translation file or the name of one in `locales/`
+    pub fn load(name: &str) -> Result<Locale, String> {
+        let path = if name.ends_with(".toml") { name.to_string() } else { format!("locales/{}.toml", name) };
+        let content = fs::read_to_string(&path).map_err(|error| format!("Could not read locale '{}': {}", path, error))?;
+        let value = toml::parse(&content).map_err(|error| format!("Could not parse locale '{}': {}", path, error))?;
+        serde_json::from_value(value).map_err(|error| format!("Invalid locale '{}': {}", path, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::Locale;
+
+    #[test]
+    fn english_has_every_string() {
+        let english = Locale::default();
+        assert_eq!(english.class_heading, "{class} class");
+        assert_eq!(serde_json::to_value(&english).unwrap(), super::toml::parse(super::ENGLISH).unwrap());
+    }
+
+    #[test]
+    fn translations_fall_back_to_english() {
+        let locale: Locale = serde_json::from_value(json!({ "class_heading": "Klasse {class}" })).unwrap();
+        assert_eq!(locale.class_heading, "Klasse {class}");
+        assert_eq!(locale.globals, Locale::default().globals);
+        assert!(Locale::with_strings(json!(["not", "a", "table"])).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use serde_json::Value;
+
+// Fields which may be given as a map of language to text
+const LOCALIZED_FIELDS: &[&str] = &["desc", "deprecated"];
+
+#[derive(Default)]
+pub struct Coverage {
+    pub translated: usize,
+    pub total: usize
+}
+
+impl Coverage {
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            return 100.0;
+        }
+        self.translated as f64 * 100.0 / self.total as f64
+    }
+}
+
+// Replaces every per-language map with the text for the given language,
+// falling back to the default language for untranslated entries. Only the
+// fields the schema allows translations in are resolved, so a class or
+// parameter that happens to be named `desc` is left alone
+pub fn localize(json: &mut Value, language: &str, default: &str) -> Coverage {
+    let mut coverage = Coverage::default();
+    let mut localize = |item: &mut Value| {
+        for key in LOCALIZED_FIELDS {
+            if let Some(text) = item.get_mut(key).filter(|text| !text.is_null()) {
+                resolve(text, language, default, &mut coverage);
+            }
+        }
+    };
+    // Items, with the parameters and return of those that are functions
+    let mut item = |item: &mut Value| {
+        localize(item);
+        for param in item.get_mut("params").and_then(Value::as_array_mut).into_iter().flatten() {
+            localize(param);
+        }
+        if let Some(returns) = item.get_mut("returns") {
+            localize(returns);
+        }
+    };
+
+    for class in values(json, "classes") {
+        item(class);
+        for section in ["static_members", "members", "constructors", "methods", "static_methods"] {
+            class.get_mut(section).and_then(Value::as_array_mut).into_iter().flatten().for_each(&mut item);
+        }
+    }
+    for functions in values(json, "extensions") {
+        functions.as_array_mut().into_iter().flatten().for_each(&mut item);
+    }
+    for key in ["globals", "values", "type_aliases"] {
+        match json.get_mut(key) {
+            Some(Value::Array(items)) => items.iter_mut().for_each(&mut item),
+            Some(Value::Object(items)) => items.values_mut().for_each(&mut item),
+            _ => { }
+        }
+    }
+    coverage
+}
+
+// The entries of a map at the root of the document
+fn values<'a>(json: &'a mut Value, key: &str) -> impl Iterator<Item = &'a mut Value> {
+    json.get_mut(key).and_then(Value::as_object_mut).into_iter().flat_map(|map| map.values_mut())
+}
+
+fn resolve(value: &mut Value, language: &str, default: &str, coverage: &mut Coverage) {
+    coverage.total += 1;
+
+    let Value::Object(translations) = value else {
+        // Plain text is written in the default language
+        if language == default {
+            coverage.translated += 1;
+        }
+        return;
+    };
+
+    let text = match translations.get(language) {
+        Some(text) => {
+            coverage.translated += 1;
+            text.clone()
+        }
+        None => translations.get(default).or_else(|| translations.values().next()).cloned().unwrap_or(Value::Null)
+    };
+    *value = text;
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::localize;
+
+    #[test]
+    fn resolves_descriptions_where_the_schema_allows_translations() {
+        let translated = json!({ "en": ["English"], "de": ["Deutsch"] });
+        let mut json = json!({
+            "classes": {
+                "desc": {
+                    "name": "desc",
+                    "desc": translated,
+                    "methods": [{
+                        "name": "get",
+                        "deprecated": { "en": ["Old"] },
+                        "params": [{ "name": "desc", "type": "String", "desc": { "en": "The key.", "de": "Der Schlüssel." } }],
+                        "returns": { "type": "String", "desc": { "de": "Der Wert." } }
+                    }]
+                }
+            },
+            "extensions": { "desc": [{ "name": "desc", "desc": translated }] },
+            "globals": [{ "name": "PI", "desc": translated }],
+            "type_aliases": { "Key": { "type": "String", "desc": translated } }
+        });
+        let coverage = localize(&mut json, "de", "en");
+
+        let class = &json["classes"]["desc"];
+        assert_eq!(class["name"], "desc");
+        assert_eq!(class["desc"], json!(["Deutsch"]));
+        let method = &class["methods"][0];
+        assert_eq!(method["deprecated"], json!(["Old"]));
+        assert_eq!(method["params"][0]["desc"], "Der Schlüssel.");
+        assert_eq!(method["returns"]["desc"], "Der Wert.");
+        assert_eq!(json["extensions"]["desc"][0]["desc"], json!(["Deutsch"]));
+        assert_eq!(json["globals"][0]["desc"], json!(["Deutsch"]));
+        assert_eq!(json["type_aliases"]["Key"]["desc"], json!(["Deutsch"]));
+        assert_eq!((coverage.translated, coverage.total), (6, 7));
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::OnceLock;
+use std::time::Instant;
+use serde_json::json;
+
+// How much is reported on stderr, each level includes those before it
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    // Skipped entries and how long each class took, with `-v`
+    Debug,
+    // Every item as it is rendered, with `-vv`
+    Trace
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+static JSON: AtomicBool = AtomicBool::new(false);
+static STARTED: OnceLock<Instant> = OnceLock::new();
+
+// Sets the most detailed level reported and whether messages are written as JSON lines
+pub fn init(level: Level, json: bool) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+    JSON.store(json, Ordering::Relaxed);
+    STARTED.get_or_init(Instant::now);
+}
+
+// Whether messages of the level are reported, to skip building ones that aren't
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+pub fn log(level: Level, message: &str) {
+    if !enabled(level) {
+        return;
+    }
+
+    if JSON.load(Ordering::Relaxed) {
+        let name = match level {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace"
+        };
+        let elapsed = STARTED.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0;
+        eprintln!("{}", json!({ "level": name, "message": message, "elapsed_ms": elapsed }));
+        return;
+    }
+
+    match level {
+        Level::Error | Level::Info => eprintln!("{}", message),
+        Level::Warn => eprintln!("Warning: {}", message),
+        Level::Debug => eprintln!("[debug] {}", message),
+        Level::Trace => eprintln!("[trace] {}", message)
+    }
+}
+
+pub fn error(message: &str) {
+    log(Level::Error, message);
+}
+
+pub fn warn(message: &str) {
+    log(Level::Warn, message);
+}
+
+pub fn info(message: &str) {
+    log(Level::Info, message);
+}
+
+pub fn debug(message: &str) {
+    log(Level::Debug, message);
+}
+
+pub fn trace(message: &str) {
+    log(Level::Trace, message);
+}
@@ -1,11 +1,1071 @@
-mod doc_parser;
+mod args;
+mod completions;
+mod exit;
+mod man;
 
-use std::fs;
-use crate::doc_parser::DocParser;
+use std::{env, fs, process, thread};
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Instant, SystemTime};
+use serde_json::{json, Value};
+use crate::args::{Args, Command, FailOn, Format};
+use json_to_md::{
+    algolia, aliases, all_methods, annotation, assets, bbcode, browse, confluence, contributors, date,
+    diagram, diff, discord, docset, examples, feed, front_matter, gitbook, glossary, keep, links, lint, localize,
+    migration, notion, obsidian, overlay, pdf, plugin, progress, quality, reference, run_report, sample, schema,
+    search, show, site, snapshot, sources, split, stream, text, wiki, yaml, zip
+};
+use json_to_md::config::Config;
+use json_to_md::doc_parser::{Callouts, DocParser, Layout, RenderOptions, VOID_TYPES};
+use json_to_md::locale::Locale;
+use json_to_md::log::{self, Level};
+use json_to_md::metadata::Metadata;
+use json_to_md::output::{self, normalize, replace, AtomicFile, NewlineWriter, Newlines};
+use json_to_md::stream::Entry;
+
+const INPUT: &str = "AllDocs.json";
+
+// When the run started, for the timings of the report
+static STARTED: OnceLock<Instant> = OnceLock::new();
 
 fn main() {
-    let parser: DocParser = doc_parser::DocParser::new("AllDocs.json");
+    STARTED.get_or_init(Instant::now);
+    let given: Vec<String> = env::args().skip(1).collect();
+    let arguments = Args::environment(&given).map(|flags| flags.into_iter().chain(given));
+    let args = arguments.and_then(Args::parse).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(exit::USAGE)
+    });
+    let level = match (args.quiet, args.verbose) {
+        (true, _) => Level::Error,
+        (false, 0) => Level::Info,
+        (false, 1) => Level::Debug,
+        (false, _) => Level::Trace
+    };
+    log::init(level, args.log_json);
+    // Bars would be broken up by the lines of detailed or JSON logs
+    progress::enable(!args.no_progress && level == Level::Info && !args.log_json);
+
+    match args.command {
+        Command::Generate => generate(&args),
+        Command::Schema => println!("{}", serde_json::to_string_pretty(&schema::schema()).unwrap()),
+        Command::TestExamples => test_examples(&args),
+        Command::Lint => lint(&args),
+        Command::Summary => summary(&args),
+        Command::Changelog => changelog(&args),
+        Command::GenSample => println!("{}", sample::generate(args.classes.unwrap_or(1000))),
+        Command::Scaffold => println!("{}", serde_json::to_string_pretty(&sample::scaffold()).unwrap()),
+        Command::Feed => write_feed(&args),
+        Command::Publish => publish(&args),
+        Command::Show => show(&args),
+        Command::Search => search(&args),
+        Command::Browse => browse::browse(&load_default(&args)).unwrap_or_else(|message| fail(message)),
+        Command::Completions => print!("{}", completions::script(args.shell.as_deref().unwrap_or("bash"), env!("CARGO_BIN_NAME"))),
+        Command::Man => print!("{}", man::page(env!("CARGO_BIN_NAME")))
+    }
+}
+
+fn create(path: &Path) -> AtomicFile {
+    AtomicFile::create(path).unwrap_or_else(|error| fail(format!("Could not create {}: {}", path.display(), error)))
+}
+
+fn fail(message: String) -> ! {
+    fail_with(exit::FAILURE, message)
+}
+
+fn fail_with(code: i32, message: String) -> ! {
+    log::error(&message);
+    process::exit(code)
+}
+
+// Whether what was found fails the run. Warnings only fail it with `--fail-on warnings` and
+// errors with anything but `--fail-on never`, `default` is used when the flag isn't given
+fn fails(args: &Args, default: FailOn, warning: bool) -> bool {
+    match args.fail_on.unwrap_or(default) {
+        FailOn::Warnings => true,
+        FailOn::Errors => !warning,
+        FailOn::Never => false
+    }
+}
+
+// The input path, `AllDocs.json` unless given with `--input`
+fn input(args: &Args) -> &str {
+    args.input.as_deref().unwrap_or(INPUT)
+}
+
+// Whether the input is YAML, by `--input-format` or else the file extension
+fn is_yaml(args: &Args) -> bool {
+    match args.input_format.as_deref() {
+        Some("yaml" | "yml") => true,
+        Some("json") => false,
+        Some(format) => fail(format!("Unknown input format '{}', expected 'json' or 'yaml'", format)),
+        None => Path::new(input(args)).extension().is_some_and(|extension| extension == "yaml" || extension == "yml")
+    }
+}
+
+// `mod.jar!AllDocs.json` is the entry inside the archive, only when the path before the last
+// `!` is a jar or zip file so that other paths containing `!` are read as they are
+fn archive_entry(path: &str) -> Option<(&str, &str)> {
+    path.rsplit_once('!').filter(|(archive, _)| {
+        let archive = Path::new(archive);
+        let extension = archive.extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+        (extension.eq_ignore_ascii_case("jar") || extension.eq_ignore_ascii_case("zip")) && archive.is_file()
+    })
+}
+
+fn read_input(args: &Args) -> Value {
+    let path = input(args);
+
+    let content = match archive_entry(path) {
+        Some((archive, entry)) => zip::read_entry(archive, entry).unwrap_or_else(|message| fail(message)),
+        None => fs::read_to_string(path).unwrap_or_else(|error| fail(format!("Could not read {}: {}", path, error)))
+    };
+    let result = if is_yaml(args) {
+        yaml::parse(&content)
+    } else {
+        serde_json::from_str(&content).map_err(|error| error.to_string())
+    };
+    result.unwrap_or_else(|message| fail_with(exit::PARSE, format!("{}: {}", path, message)))
+}
+
+// Reads the input and brings it into the current layout with any overlays applied
+fn load(args: &Args) -> DocParser {
+    let mut parser = DocParser::from_json(read_input(args));
+
+    if let Err(message) = migration::migrate(parser.json_mut()) {
+        fail_with(exit::PARSE, message);
+    }
+
+    for directory in &args.fragments {
+        let fragments = overlay::load_fragments(directory).unwrap_or_else(|message| fail(message));
+        for fragment in fragments {
+            overlay::apply(parser.json_mut(), &fragment);
+        }
+    }
+
+    for path in &args.overlays {
+        match overlay::load(path) {
+            Ok(value) => overlay::apply(parser.json_mut(), &value),
+            Err(message) => fail(message)
+        }
+    }
+
+    if let Some(path) = &args.sources {
+        let mapping = overlay::load(path).unwrap_or_else(|message| fail(message));
+        for name in sources::apply(parser.json_mut(), &mapping) {
+            log::warn(&format!("{}: '{}' is not documented", path, name));
+        }
+    }
+
+    if args.stable {
+        parser.remove_experimental();
+    }
+    if let Some(deprecated) = args.deprecated {
+        parser.filter_deprecated(deprecated);
+    }
+    if let Some(variants) = &args.variants {
+        parser.keep_variants(variants);
+    }
+
+    parser
+}
+
+// Reads a previous version of the documentation to compare against
+fn load_previous(args: &Args, path: &str) -> DocParser {
+    let mut parser = DocParser::new(path).unwrap_or_else(|message| fail(message));
+    if let Err(message) = migration::migrate(parser.json_mut()) {
+        fail_with(exit::PARSE, format!("{}: {}", path, message));
+    }
+
+    let default_language = args.default_language.as_deref().unwrap_or("en");
+    localize::localize(parser.json_mut(), default_language, default_language);
+    parser.filter_classes(&args.include, &args.exclude);
+    if args.stable {
+        parser.remove_experimental();
+    }
+    if let Some(deprecated) = args.deprecated {
+        parser.filter_deprecated(deprecated);
+    }
+    if let Some(variants) = &args.variants {
+        parser.keep_variants(variants);
+    }
+    parser
+}
+
+// Loads the input with every description in the default language
+fn load_default(args: &Args) -> DocParser {
+    let mut parser = load(args);
+    let default_language = args.default_language.as_deref().unwrap_or("en");
+    localize::localize(parser.json_mut(), default_language, default_language);
+    parser
+}
+
+fn generate(args: &Args) {
+    if args.mermaid && args.split {
+        fail("'--mermaid' embeds the diagram in Classes.md which '--split' doesn't write, use '--mermaid-file' instead".to_string());
+    }
+    if args.stream {
+        generate_streaming(args);
+        return;
+    }
+
+    let mut parser = load(args);
+
+    let default_language = args.default_language.as_deref().unwrap_or("en");
+    if args.archive {
+        let mut normalized = parser.json().clone();
+        localize::localize(&mut normalized, default_language, default_language);
+        archive(args, &normalized);
+    }
+
+    if args.languages.is_empty() {
+        localize::localize(parser.json_mut(), default_language, default_language);
+        render(args, parser, Path::new("."));
+        return;
+    }
+
+    // One output directory per language
+    for language in &args.languages {
+        let mut localized = DocParser::from_json(parser.json().clone());
+        let coverage = localize::localize(localized.json_mut(), language, default_language);
+        log::info(&format!(
+            "{}: {}/{} descriptions translated ({:.1}%)",
+            language, coverage.translated, coverage.total, coverage.percentage()
+        ));
+
+        let directory = Path::new(language);
+        fs::create_dir_all(directory).unwrap_or_else(|error| fail(format!("Could not create language directory: {}", error)));
+        render(args, localized, directory);
+    }
+}
+
+// Renders every class as soon as it is read, each is processed as a document of its own
+// so that memory stays proportional to a single class. A first pass keeps an outline of the
+// document without descriptions or examples, for links to entries further on and for the
+// globals and type aliases
+fn generate_streaming(args: &Args) {
+    let diagrams = args.mermaid || args.mermaid_file.is_some() || args.dot_file.is_some();
+    let needs_document = args.split || args.archive || args.emit_json.is_some() || !args.fragments.is_empty() || args.metadata
+        || args.glossary || args.contributors || args.all_methods;
+    if diagrams || needs_document || !args.languages.is_empty() {
+        fail("'--stream' can't be combined with diagrams, '--split', '--archive', '--emit-json', '--languages', '--fragments', \
+            '--metadata', '--glossary', '--contributors' or '--all-methods'".to_string());
+    }
+    if is_yaml(args) || archive_entry(input(args)).is_some() {
+        fail("'--stream' only reads JSON files outside of archives".to_string());
+    }
+    // Pages are written as they are rendered, there is no point where the regions could be put back
+    if ["Classes.md", "Extensions.md"].iter().any(|page| !keep::read(Path::new(page)).is_empty()) {
+        fail("'--stream' can't keep the hand-written regions of Classes.md and Extensions.md".to_string());
+    }
+
+    let overlays: Vec<Value> = args.overlays.iter()
+        .map(|path| overlay::load(path).unwrap_or_else(|message| fail(message)))
+        .collect();
+    let default_language = args.default_language.as_deref().unwrap_or("en");
+    let options = render_options(args, Path::new("."));
+
+    let mut outline = json!({ "classes": {}, "extensions": {} });
+    stream::read(input(args), |entry| {
+        match entry {
+            Entry::Class(name, value) => outline["classes"][name] = outline_of(value),
+            Entry::Extension(name, value) => outline["extensions"][name] = outline_of(value),
+            Entry::Other(key, value) => outline[key] = value
+        }
+        Ok(())
+    }).unwrap_or_else(|message| fail_with(exit::PARSE, message));
+    // Each entry is migrated from the version of the input
+    let version = outline["version"].clone();
+    migration::migrate(&mut outline).unwrap_or_else(|message| fail_with(exit::PARSE, message));
+    for overlay in &overlays {
+        overlay::apply(&mut outline, overlay);
+    }
+    localize::localize(&mut outline, default_language, default_language);
+    let mut index = DocParser::from_json(outline);
+    index.filter_classes(&args.include, &args.exclude);
+    if args.stable {
+        index.remove_experimental();
+    }
+    if let Some(deprecated) = args.deprecated {
+        index.filter_deprecated(deprecated);
+    }
+    if let Some(variants) = &args.variants {
+        index.keep_variants(variants);
+    }
+    index.options = options.clone();
+
+    let mut classes = NewlineWriter::new(create(Path::new("Classes.md")), options.newlines);
+    let mut extensions = NewlineWriter::new(create(Path::new("Extensions.md")), options.newlines);
+    let template = options.front_matter.as_deref();
+    if let Err(error) = classes.write_all(front_matter::page(template, "Classes", 1, "classes").as_bytes())
+        .and_then(|_| extensions.write_all(front_matter::page(template, "Extensions", 2, "extensions").as_bytes())) {
+        fail(format!("Could not write output: {}", error));
+    }
+    let mut class_anchors = HashMap::new();
+    let mut extension_anchors = HashMap::new();
+    let (mut first_class, mut first_extension) = (true, true);
+    let mut warnings = 0;
+    let mut long_example_lines = Vec::new();
+    let mut invalid = false;
+
+    let result = stream::read(input(args), |entry| {
+        let (section, name, value) = match entry {
+            Entry::Class(name, value) => ("classes", name, value),
+            Entry::Extension(name, value) => ("extensions", name, value),
+            Entry::Other(..) => return Ok(())
+        };
+
+        let mut json = single_entry(section, &name, value);
+        json["version"] = version.clone();
+        migration::migrate(&mut json)?;
+
+        // Only the part of an overlay for this entry is applied
+        for overlay in &overlays {
+            if let Some(part) = overlay.get(section).and_then(|entries| entries.get(&name)) {
+                overlay::apply(&mut json, &single_entry(section, &name, part.clone()));
+            }
+        }
+
+        localize::localize(&mut json, default_language, default_language);
+        if !validate(args, &json) {
+            invalid = true;
+            return Err(format!("'{}' is invalid", name));
+        }
+
+        let mut parser = DocParser::from_json(json);
+        parser.filter_classes(&args.include, &args.exclude);
+        if args.stable {
+            parser.remove_experimental();
+        }
+        if let Some(deprecated) = args.deprecated {
+            parser.filter_deprecated(deprecated);
+        }
+        if let Some(variants) = &args.variants {
+            parser.keep_variants(variants);
+        }
+        parser.options = options.clone();
+
+        let written = if section == "classes" {
+            parser.classes().into_iter().try_for_each(|class| {
+                if !first_class {
+                    classes.write_all(b"\n\n")?;
+                }
+                first_class = false;
+                let md = index.render_class(class, &mut class_anchors);
+                classes.write_all(md.as_bytes())
+            })
+        } else {
+            parser.extensions().into_iter().try_for_each(|(name, functions)| {
+                if !first_extension {
+                    extensions.write_all(b"\n\n")?;
+                }
+                first_extension = false;
+                let md = index.render_extension(&name, &functions, &mut extension_anchors);
+                extensions.write_all(md.as_bytes())
+            })
+        };
+
+        warnings += report(args, &parser);
+        long_example_lines.extend(quality::long_example_lines(&parser));
+        written.map_err(|error| format!("Could not write output: {}", error))
+    });
+
+    if invalid {
+        // The unfinished files are deleted as they are dropped, leaving the previous output in place
+        drop((classes, extensions));
+        process::exit(exit::INVALID);
+    }
+    if let Err(message) = result.and_then(|_| {
+        classes.finish().and_then(AtomicFile::commit)
+            .and_then(|_| extensions.finish().and_then(AtomicFile::commit))
+            .map_err(|error| format!("Could not write output: {}", error))
+    }) {
+        fail(message);
+    }
+    write_globals(&index, Path::new("."));
+    warnings += report(args, &index);
+    fail_quality(args, long_examples(args, &long_example_lines).into_iter().collect());
+    check_warnings(args, warnings);
+}
+
+// The class or extension without the descriptions and examples of it and its items,
+// which is all rendering the other entries needs of it
+fn outline_of(mut value: Value) -> Value {
+    let without_text = |item: &mut Value| {
+        if let Some(item) = item.as_object_mut() {
+            item.remove("desc");
+            item.remove("examples");
+        }
+    };
+    for section in ["static_members", "members", "constructors", "methods", "static_methods"] {
+        value.get_mut(section).and_then(Value::as_array_mut).into_iter().flatten().for_each(without_text);
+    }
+    // Extensions are arrays of functions
+    value.as_array_mut().into_iter().flatten().for_each(without_text);
+    without_text(&mut value);
+    value
+}
+
+// A document holding nothing but the given class or extension
+fn single_entry(section: &str, name: &str, value: Value) -> Value {
+    let mut json = serde_json::json!({ "classes": {}, "extensions": {} });
+    json[section][name] = value;
+    json
+}
+
+// Globals.md and TypeAliases.md, for documents that have them
+fn write_globals(parser: &DocParser, directory: &Path) {
+    let newlines = parser.options.newlines;
+    let globals = parser.globals();
+    if !globals.is_empty() {
+        replace(directory.join("Globals.md"), normalize(&parser.render_globals(&globals, &mut HashMap::new()), newlines))
+            .unwrap_or_else(|error| fail(format!("Could not write globals: {}", error)));
+    }
+
+    if !parser.type_aliases().is_empty() {
+        replace(directory.join(aliases::FILE), normalize(&aliases::aliases(parser), newlines))
+            .unwrap_or_else(|error| fail(format!("Could not write type aliases: {}", error)));
+    }
+}
+
+fn render(args: &Args, mut parser: DocParser, directory: &Path) {
+    let rendering = (Instant::now(), SystemTime::now());
+    if !validate(args, parser.json()) {
+        process::exit(exit::INVALID);
+    }
+    parser.filter_classes(&args.include, &args.exclude);
+    parser.options = render_options(args, directory);
+
+    // The exact document the markdown is generated from, for other tools to consume
+    if let Some(path) = &args.emit_json {
+        let json = serde_json::to_string_pretty(parser.json()).unwrap();
+        replace(directory.join(path), json + "\n").unwrap_or_else(|error| fail(format!("Could not write JSON: {}", error)));
+    }
+
+    if let Some(path) = &args.dot_file {
+        replace(directory.join(path), diagram::dot(&parser.classes()))
+            .unwrap_or_else(|error| fail(format!("Could not write dot graph: {}", error)));
+    }
+
+    let mermaid = (args.mermaid || args.mermaid_file.is_some()).then(|| diagram::mermaid(&parser.classes()));
+    if let (Some(path), Some(diagram)) = (&args.mermaid_file, &mermaid) {
+        replace(directory.join(path), diagram).unwrap_or_else(|error| fail(format!("Could not write mermaid diagram: {}", error)));
+    }
+
+    let newlines = parser.options.newlines;
+    let mut unchanged = 0;
+    // The input of the single pages is the whole document
+    let input_hash = parser.options.metadata.as_ref()
+        .map(|_| snapshot::hash(&serde_json::to_string(parser.json()).unwrap()))
+        .unwrap_or_default();
+    if args.glossary && args.format == Format::Markdown {
+        let link = |extension: bool, name: &str| parser.link(extension, name, None);
+        replace(directory.join("Glossary.md"), normalize(&glossary::glossary(&parser, &link), newlines))
+            .unwrap_or_else(|error| fail(format!("Could not write glossary: {}", error)));
+    }
+
+    if args.format == Format::Markdown {
+        write_globals(&parser, directory);
+    }
+
+    if args.contributors && args.format == Format::Markdown {
+        replace(directory.join("Contributors.md"), normalize(&contributors::contributors(&parser), newlines))
+            .unwrap_or_else(|error| fail(format!("Could not write contributors: {}", error)));
+    }
+
+    if args.all_methods && args.format == Format::Markdown {
+        let link = |extension: bool, owner: &str, item: &diff::Item| {
+            let heading = parser.signature(&item.signature) + &parser.badges(item.deprecated, item.experimental, false);
+            parser.link(extension, owner, Some((&item.anchor, &heading)))
+        };
+        replace(directory.join("AllMethods.md"), normalize(&all_methods::all_methods(&parser, &link), newlines))
+            .unwrap_or_else(|error| fail(format!("Could not write method index: {}", error)));
+    }
+
+    match args.format {
+        Format::Markdown if args.split => {
+            let outcome = split::write(&parser, directory, args.prune).unwrap_or_else(|message| fail(message));
+            log::info(&format!("Regenerated {} files, skipped {} unchanged", outcome.regenerated, outcome.skipped));
+            if outcome.pruned > 0 {
+                log::info(&format!("Pruned {} left from classes and extensions no longer documented", diff::plural(outcome.pruned, "file")));
+            } else if outcome.stale > 0 {
+                log::info(&format!("{} left from classes and extensions no longer documented, use '--prune' to delete them", diff::plural(outcome.stale, "file")));
+            }
+            unchanged = outcome.skipped;
+        }
+        Format::Markdown => {
+            let diagram = mermaid.filter(|_| args.mermaid).map(|diagram| format!("```mermaid\n{}```\n\n", diagram));
+            output::write_markdown(&parser, directory, &input_hash, &diagram.unwrap_or_default()).unwrap_or_else(|message| fail(message));
+        }
+        Format::Docset => {
+            let path = directory.join(docset::NAME);
+            let entries = docset::write(&parser, &path).unwrap_or_else(|message| fail(message));
+            log::info(&format!("Wrote {} with {} index entries", path.display(), entries));
+        }
+        Format::Confluence => {
+            let path = directory.join(confluence::DIRECTORY);
+            let pages = confluence::write(&parser, &path).unwrap_or_else(|message| fail(message));
+            log::info(&format!("Wrote {} with {} pages", path.display(), pages));
+        }
+        Format::GitBook => {
+            let path = directory.join(gitbook::DIRECTORY);
+            let chapters = gitbook::write(&parser, &path).unwrap_or_else(|message| fail(message));
+            log::info(&format!("Wrote {} with {} chapters", path.display(), chapters));
+        }
+        Format::BBCode => bbcode::write(&parser, directory).unwrap_or_else(|message| fail(message)),
+        Format::Discord => {
+            let path = directory.join(discord::FILE);
+            let snippets = discord::write(&parser, &path).unwrap_or_else(|message| fail(message));
+            log::info(&format!("Wrote {} with {} snippets", path.display(), snippets));
+        }
+        Format::DiscordEmbeds => {
+            let path = directory.join(discord::EMBEDS_FILE);
+            let functions = discord::write_embeds(&parser, &path).unwrap_or_else(|message| fail(message));
+            log::info(&format!("Wrote {} with embeds for {} functions", path.display(), functions));
+        }
+        Format::Obsidian => {
+            let path = directory.join(obsidian::DIRECTORY);
+            let notes = obsidian::write(&parser, &path).unwrap_or_else(|message| fail(message));
+            log::info(&format!("Wrote {} with {} notes", path.display(), notes));
+        }
+        Format::Html => {
+            let theme = site::Theme::load(args.theme.as_deref()).unwrap_or_else(|message| fail(message));
+            let path = directory.join(site::DIRECTORY);
+            let pages = site::write(&parser, &path, &theme, args.base_url.as_deref(), args.variant_tabs).unwrap_or_else(|message| fail(message));
+            log::info(&format!("Wrote {} with {} pages", path.display(), pages));
+        }
+        Format::Algolia => {
+            let path = directory.join(algolia::FILE);
+            let records = algolia::write(&parser, &path, args.base_url.as_deref().unwrap_or(""))
+                .unwrap_or_else(|message| fail(message));
+            log::info(&format!("Wrote {} with {} records", path.display(), records));
+        }
+        Format::Text => {
+            let width = args.width.unwrap_or(text::DEFAULT_WIDTH);
+            text::write(&parser, directory, width).unwrap_or_else(|message| fail(message));
+        }
+        Format::Notion => {
+            let path = directory.join(notion::DIRECTORY);
+            let pages = notion::write(&parser, &path).unwrap_or_else(|message| fail(message));
+            log::info(&format!("Wrote {} with {} pages", path.display(), pages.len()));
+            if let Some(parent) = &args.notion_page {
+                let uploaded = notion::upload(&pages, parent).unwrap_or_else(|message| fail(message));
+                log::info(&format!("Uploaded {} pages to Notion", uploaded));
+            }
+        }
+        Format::Reference => {
+            let path = directory.join(reference::FILE);
+            let template = parser.options.front_matter.as_deref();
+            output::write_page(&parser, &path, "reference", &input_hash, |out| {
+                out.write_all(front_matter::page(template, "Reference", 1, "reference").as_bytes())?;
+                out.write_all(reference::reference(&parser).as_bytes())
+            }).unwrap_or_else(|error| fail(format!("Could not write reference: {}", error)));
+            log::info(&format!("Wrote {}", path.display()));
+        }
+        Format::ReferenceHtml => {
+            let path = directory.join(reference::HTML_FILE);
+            replace(&path, reference::html(&parser)).unwrap_or_else(|error| fail(format!("Could not write reference: {}", error)));
+            log::info(&format!("Wrote {}", path.display()));
+        }
+        Format::Pdf => {
+            let path = directory.join(pdf::FILE);
+            let engine = pdf::write(&parser, &path, args.pdf_engine.as_deref()).unwrap_or_else(|message| fail(message));
+            log::info(&format!("Wrote {} with {}", path.display(), engine));
+        }
+        Format::Plugin => {
+            let command = args.plugin.as_deref().unwrap_or_default();
+            plugin::run(&parser, command, directory).unwrap_or_else(|message| fail(message));
+        }
+    }
+
+    // Formats whose pages can show images get a copy of the assets beside them
+    let pages = match args.format {
+        Format::Markdown | Format::Reference | Format::ReferenceHtml | Format::Pdf => Some(directory.to_path_buf()),
+        Format::GitBook => Some(directory.join(gitbook::DIRECTORY)),
+        Format::Obsidian => Some(directory.join(obsidian::DIRECTORY)),
+        Format::Html => Some(directory.join(site::DIRECTORY)),
+        Format::Docset => Some(directory.join(docset::NAME).join("Contents").join("Resources").join("Documents")),
+        _ => None
+    };
+    if let Some(pages) = pages {
+        let input = archive_entry(input(args)).map_or(input(args), |(archive, _)| archive);
+        let source = Path::new(input).parent().unwrap_or(Path::new(""));
+        assets::copy(parser.json(), source, &pages).unwrap_or_else(|message| fail(message));
+    }
+
+    let warnings = report(args, &parser);
+    if let Some(path) = &args.report {
+        write_report(args, &parser, directory, path, rendering, unchanged);
+    }
+
+    if args.check_links {
+        check_links(args, &parser, directory);
+    }
+    check_quality(args, &parser);
+    check_warnings(args, warnings);
+}
+
+// Fails with exit code 6 if there were warnings and `--fail-on warnings` is given
+fn check_warnings(args: &Args, warnings: usize) {
+    if warnings > 0 && fails(args, FailOn::Errors, true) {
+        fail_with(exit::WARNINGS, format!("Failing on {} warnings", warnings));
+    }
+}
+
+// Fails with exit code 3 when the documentation is less complete than the thresholds allow,
+// or examples are wider than `--example-max-width`, after the output is written so it can
+// still be looked at
+fn check_quality(args: &Args, parser: &DocParser) {
+    let mut failures = Vec::new();
+    if args.min_coverage.is_some() || args.max_missing_descriptions.is_some() || args.max_missing_examples.is_some() {
+        let quality = quality::measure(parser);
+        if let Some(minimum) = args.min_coverage.filter(|minimum| quality.coverage() < *minimum) {
+            failures.push(format!(
+                "Coverage is {:.1}% ({} of {} items complete), below the minimum of {}%",
+                quality.coverage(), quality.complete, quality.items, minimum
+            ));
+        }
+        let missing = [
+            ("descriptions", args.max_missing_descriptions, &quality.missing_descriptions),
+            ("examples", args.max_missing_examples, &quality.missing_examples)
+        ];
+        for (kind, maximum, items) in missing {
+            if let Some(maximum) = maximum.filter(|maximum| items.len() > *maximum) {
+                failures.push(format!("{} items are missing {}, more than the maximum of {}: {}", items.len(), kind, maximum, items.join(", ")));
+            }
+        }
+    }
+    failures.extend(long_examples(args, &quality::long_example_lines(parser)));
+    fail_quality(args, failures);
+}
+
+fn long_examples(args: &Args, lines: &[String]) -> Option<String> {
+    let max_width = args.example_max_width.filter(|_| !lines.is_empty())?;
+    Some(format!("{} example lines are longer than {} characters:\n{}", lines.len(), max_width, lines.join("\n")))
+}
+
+fn fail_quality(args: &Args, failures: Vec<String>) {
+    if failures.is_empty() {
+        return;
+    }
+    for failure in failures {
+        log::error(&failure);
+    }
+    if fails(args, FailOn::Errors, false) {
+        process::exit(exit::QUALITY);
+    }
+}
+
+// Reports the links in the markdown just written that lead nowhere, and deprecations
+// naming replacements that aren't documented
+fn check_links(args: &Args, parser: &DocParser, directory: &Path) {
+    let mut pages = Vec::new();
+    match args.format {
+        Format::Markdown => {
+            let files = ["Classes.md", "Extensions.md", "Globals.md", aliases::FILE, "Contributors.md", "Glossary.md", "AllMethods.md"];
+            pages.extend(files.iter().map(|file| directory.join(file)).filter(|path| path.exists()));
+            if args.split {
+                for name in split_directories(parser) {
+                    let path = directory.join(name);
+                    if path.is_dir() {
+                        markdown_files(&path, &mut pages);
+                    } else if path.exists() && !pages.contains(&path) {
+                        pages.push(path);
+                    }
+                }
+            }
+        }
+        Format::GitBook => markdown_files(&directory.join(gitbook::DIRECTORY), &mut pages),
+        Format::Reference => pages.push(directory.join(reference::FILE)),
+        _ => fail("Links can only be checked for the markdown, gitbook and reference formats".to_string())
+    }
+
+    let mut broken = links::check(&pages);
+    broken.extend(links::check_replacements(parser));
+    for problem in &broken {
+        log::error(problem);
+    }
+    if !broken.is_empty() {
+        let message = format!("Found {} broken links", broken.len());
+        if fails(args, FailOn::Errors, false) {
+            fail_with(exit::INVALID, message);
+        }
+        log::error(&message);
+    }
+}
+
+// Where split pages are written: `classes`, `extensions` and the directories of modules, or
+// the top directories of the `--out` template, or the pages themselves if it has none
+fn split_directories(parser: &DocParser) -> Vec<String> {
+    let pages = parser.classes().into_iter().map(|class| parser.page_path(false, &class.name))
+        .chain(parser.extensions().into_iter().map(|(name, _)| parser.page_path(true, &name)));
+    let mut directories: Vec<String> = pages.map(|page| page.split('/').next().unwrap_or_default().to_string()).collect();
+    directories.sort();
+    directories.dedup();
+    directories
+}
+
+// Writes `--report <path>`: the files written, what was rendered and skipped, warnings and
+// timings, for scripts to act on without reading the log
+fn write_report(args: &Args, parser: &DocParser, directory: &Path, path: &str, (started, since): (Instant, SystemTime), unchanged: usize) {
+    let mut paths: Vec<PathBuf> = match args.format {
+        Format::Markdown => {
+            let files = ["Classes.md", "Extensions.md", "Globals.md", aliases::FILE, "Contributors.md", "Glossary.md", "AllMethods.md"];
+            let directories = if args.split { split_directories(parser) } else { Vec::new() };
+            files.iter().map(|file| file.to_string()).chain(directories).map(|name| directory.join(name)).collect()
+        }
+        Format::Docset => vec![directory.join(docset::NAME)],
+        Format::Confluence => vec![directory.join(confluence::DIRECTORY)],
+        Format::GitBook => vec![directory.join(gitbook::DIRECTORY)],
+        Format::BBCode => vec![directory.join("Classes.bbcode"), directory.join("Extensions.bbcode")],
+        Format::Discord => vec![directory.join(discord::FILE)],
+        Format::DiscordEmbeds => vec![directory.join(discord::EMBEDS_FILE)],
+        Format::Obsidian => vec![directory.join(obsidian::DIRECTORY)],
+        Format::Html => vec![directory.join(site::DIRECTORY)],
+        Format::Algolia => vec![directory.join(algolia::FILE)],
+        Format::Text => vec![directory.join("Classes.txt"), directory.join("Extensions.txt")],
+        Format::Notion => vec![directory.join(notion::DIRECTORY)],
+        Format::Reference => vec![directory.join(reference::FILE)],
+        Format::ReferenceHtml => vec![directory.join(reference::HTML_FILE)],
+        Format::Pdf => vec![directory.join(pdf::FILE)],
+        // What a plugin writes is up to it
+        Format::Plugin => Vec::new()
+    };
+    paths.extend([&args.emit_json, &args.dot_file, &args.mermaid_file].into_iter().flatten().map(|file| directory.join(file)));
+
+    let files = run_report::written(&paths, since);
+    let loading = STARTED.get().map_or(0, |start| started.duration_since(*start).as_millis());
+    let rendering = started.elapsed().as_millis();
+    let mut warnings = parser.warnings();
+    warnings.extend(parser.anchor_collisions());
+
+    let report = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "files": files.iter().map(|(file, bytes)| json!({ "path": file.display().to_string(), "bytes": bytes })).collect::<Vec<Value>>(),
+        "bytes": files.iter().map(|(_, bytes)| bytes).sum::<u64>(),
+        "counts": run_report::counts(parser),
+        "warnings": warnings,
+        "skipped": {
+            "without_examples": run_report::without_examples(parser),
+            "unchanged": unchanged
+        },
+        "timings": {
+            "load_ms": loading,
+            "render_ms": rendering,
+            "total_ms": loading + rendering
+        }
+    });
+    let path = directory.join(path);
+    replace(&path, serde_json::to_string_pretty(&report).unwrap() + "\n")
+        .unwrap_or_else(|error| fail(format!("Could not write {}: {}", path.display(), error)));
+}
+
+// Every markdown file in the directory and those below it
+fn markdown_files(directory: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            markdown_files(&path, files);
+        } else if path.extension().is_some_and(|extension| extension == "md") {
+            files.push(path);
+        }
+    }
+}
+
+// Reports what makes the documentation invalid, returning whether it is valid. Lenient runs
+// skip validation and recover from malformed entries while rendering
+fn validate(args: &Args, json: &Value) -> bool {
+    let errors = if args.lenient { Vec::new() } else { [schema::validate(json, args.strict), schema::duplicates(json)].concat() };
+    if !errors.is_empty() {
+        for error in errors {
+            if args.annotations {
+                println!("{}", annotation::annotation("error", input(args), Some("Invalid documentation"), &error));
+            } else {
+                log::error(&error);
+            }
+        }
+        return false;
+    }
+    true
+}
+
+fn render_options(args: &Args, directory: &Path) -> RenderOptions {
+    if args.out.is_some() && args.format != Format::Markdown {
+        fail("'--out' only places the pages of the markdown format".to_string());
+    }
+    let config = Config::load(args.config.as_deref()).unwrap_or_else(|message| fail(message));
+    let mut options = RenderOptions {
+        // Docsets and sites link into pages by anchor
+        anchors: args.anchors || args.anchor_key.is_some() || matches!(args.format, Format::Docset | Format::Html | Format::ReferenceHtml | Format::Pdf),
+        example_indent: args.example_indent,
+        example_max_width: args.example_max_width,
+        jobs: args.jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, |jobs| jobs.get())),
+        callouts: if args.format == Format::GitBook { Callouts::GitBook } else { Callouts::Plain },
+        wikilinks: args.format == Format::Obsidian,
+        link_types: args.link_types,
+        source_url: args.source_url.clone(),
+        layout: match args.format {
+            Format::Html | Format::Docset => Layout::Site,
+            Format::GitBook | Format::Obsidian => Layout::Split,
+            Format::Reference | Format::ReferenceHtml | Format::Pdf => Layout::Combined,
+            _ if args.split => Layout::Split,
+            _ => Layout::Single
+        },
+        // A template puts pages where it says, modules included
+        modules: args.split && args.format == Format::Markdown && args.out.is_none(),
+        newlines: Newlines {
+            crlf: args.crlf,
+            max_blank_lines: args.max_blank_lines.unwrap_or(Newlines::default().max_blank_lines)
+        },
+        reflow: args.reflow,
+        void_types: args.void_types.clone().unwrap_or_else(|| VOID_TYPES.iter().map(|name| name.to_string()).collect()),
+        accessors: args.accessors,
+        group_members: args.group_members,
+        anchor_key: args.anchor_key.clone(),
+        instance_names: args.instance_names,
+        lowercase_files: args.lowercase_files,
+        badges: config.badges,
+        order: config.order,
+        out: args.out.clone(),
+        metadata: args.metadata.then(|| Metadata { generated: args.metadata_timestamp.then(|| date::timestamp(date::now_seconds())) }),
+        output_comments: args.output_comments,
+        ..RenderOptions::default()
+    };
+
+    if let Some(path) = &args.front_matter_template {
+        let template = fs::read_to_string(path).unwrap_or_else(|error| fail(format!("Could not read {}: {}", path, error)));
+        options.front_matter = Some(template);
+    } else if args.front_matter {
+        options.front_matter = Some(front_matter::TEMPLATE.to_string());
+    }
+
+    // A translation file named after the output language takes priority
+    let language_locale = directory.file_name().map(|name| format!("locales/{}.toml", name.to_string_lossy()));
+    if let Some(path) = language_locale.filter(|path| Path::new(path).exists()) {
+        options.locale = Locale::load(&path).unwrap_or_else(|message| fail(message));
+    } else if let Some(name) = &args.locale {
+        options.locale = Locale::load(name).unwrap_or_else(|message| fail(message));
+    }
+
+    options
+}
+
+// Prints the warnings collected while rendering, returning how many there were
+fn report(args: &Args, parser: &DocParser) -> usize {
+    let warnings = parser.warnings();
+    for warning in &warnings {
+        if args.annotations {
+            println!("{}", annotation::annotation("warning", input(args), None, warning));
+        } else {
+            log::warn(warning);
+        }
+    }
+
+    for collision in parser.anchor_collisions() {
+        log::debug(&collision);
+    }
+    warnings.len()
+}
+
+fn test_examples(args: &Args) {
+    let mut parser = load_default(args);
+    parser.filter_classes(&args.include, &args.exclude);
+
+    let Some(interpreter) = &args.interpreter else {
+        fail("Missing '--interpreter <command>' to run the examples with".to_string());
+    };
+
+    let examples = examples::collect(&parser);
+    let failures = examples::run(&examples, interpreter).unwrap_or_else(|message| fail(message));
+
+    for failure in &failures {
+        println!("FAILED {}", failure);
+    }
+    println!("{}/{} examples passed", examples.len() - failures.len(), examples.len());
+
+    if !failures.is_empty() && fails(args, FailOn::Errors, false) {
+        process::exit(exit::INVALID);
+    }
+}
+
+fn lint(args: &Args) {
+    let mut parser = load_default(args);
+    parser.filter_classes(&args.include, &args.exclude);
+
+    let config = Config::load(args.config.as_deref()).unwrap_or_else(|message| fail(message));
+    let problems = lint::lint(&parser, &config).unwrap_or_else(|message| fail(message));
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&problems).unwrap());
+    } else if args.annotations {
+        for problem in &problems {
+            let message = format!("{}: {}", problem.location, problem.message);
+            println!("{}", annotation::annotation("warning", input(args), Some(problem.rule), &message));
+        }
+    } else {
+        for problem in &problems {
+            println!("{}: {} [{}]", problem.location, problem.message, problem.rule);
+        }
+    }
+
+    // Problems are what lint is run for, so they fail it unless `--fail-on` says otherwise
+    if !problems.is_empty() && fails(args, FailOn::Warnings, true) {
+        process::exit(exit::WARNINGS);
+    }
+}
+
+fn summary(args: &Args) {
+    let mut parser = load_default(args);
+    let previous = args.old.clone().or_else(|| {
+        snapshot::previous(parser.json()).map(|path| path.to_string_lossy().to_string())
+    });
+    let Some(previous) = previous else {
+        fail("No previous snapshot to compare against, use '--old <path>'".to_string());
+    };
+    parser.filter_classes(&args.include, &args.exclude);
+
+    let changes = diff::diff(&load_previous(args, &previous), &parser);
+    print!("{}", diff::summary(&changes));
+
+    // Like `git diff --exit-code`, for scripts only acting when something changed. Changes aren't
+    // a problem with the documentation, so `--fail-on` doesn't affect this
+    if !changes.is_empty() {
+        process::exit(exit::CHANGED);
+    }
+}
+
+fn changelog(args: &Args) {
+    let path = args.changelog.as_deref().unwrap_or("CHANGELOG.md");
+
+    let mut parser = load_default(args);
+    let previous = args.old.clone().or_else(|| args.snapshot.clone().filter(|snapshot| Path::new(snapshot).exists())).or_else(|| {
+        snapshot::previous(parser.json()).map(|path| path.to_string_lossy().to_string())
+    });
+    archive(args, parser.json());
+    parser.filter_classes(&args.include, &args.exclude);
+
+    if let Some(previous) = previous {
+        let changes = diff::diff(&load_previous(args, &previous), &parser);
+        if changes.is_empty() {
+            println!("No documentation changes since the previous snapshot");
+        } else {
+            let version = args.release.as_deref().unwrap_or("Unreleased");
+            let section = diff::changelog_section(&changes, version, &date::today());
+            let existing = fs::read_to_string(path).unwrap_or_default();
+            replace(path, diff::insert_section(&existing, &section))
+                .unwrap_or_else(|error| fail(format!("Could not write changelog: {}", error)));
+            println!("Added {} to {}", diff::plural(changes.len(), "change"), path);
+        }
+    } else {
+        println!("No previous snapshot, recording the current documentation");
+    }
+
+    if let Some(snapshot) = &args.snapshot {
+        replace(snapshot, serde_json::to_string(parser.json()).unwrap())
+            .unwrap_or_else(|error| fail(format!("Could not write snapshot: {}", error)));
+    }
+}
+
+// Prints a single class, member or function, colored when printing to a terminal
+fn show(args: &Args) {
+    let parser = load_default(args);
+    let query = args.query.as_deref().unwrap_or_default();
+    let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    print!("{}", show::show(&parser, query, color).unwrap_or_else(|message| fail(message)));
+}
+
+// Lists every item matching the query with where it is rendered, fails if there are none
+fn search(args: &Args) {
+    let parser = load_default(args);
+    let query = args.query.as_deref().unwrap_or_default();
+    let items = search::search(&parser, query, args.kind.as_deref(), args.class.as_deref());
+    if items.is_empty() {
+        fail(format!("No matches for '{}'", query));
+    }
+
+    for item in &items {
+        println!("{} ({}) - {}", item.signature, item.kind, search::location(item));
+    }
+}
+
+// Regenerates the pages in a clone of the wiki and pushes them, unless nothing changed
+fn publish(args: &Args) {
+    let Some(url) = &args.wiki_repo else {
+        fail("Publishing needs the wiki to publish to, use '--wiki-repo <git-url>'".to_string());
+    };
+    let directory = Path::new(snapshot::STATE_DIR).join(wiki::DIRECTORY);
+    wiki::checkout(url, &directory).unwrap_or_else(|message| fail(message));
+
+    let mut parser = load(args);
+    let default_language = args.default_language.as_deref().unwrap_or("en");
+    localize::localize(parser.json_mut(), default_language, default_language);
+    render(args, parser, &directory);
+
+    let changes = wiki::changes(&directory).unwrap_or_else(|message| fail(message));
+    if changes.is_empty() {
+        println!("The wiki is already up to date");
+        return;
+    }
+    for change in &changes {
+        println!("{}", change);
+    }
+
+    let template = args.message.as_deref().unwrap_or("Update documentation {date}");
+    let message = template.replace("{date}", &date::today()).replace("{pages}", &changes.len().to_string());
+    if args.dry_run {
+        println!("Dry run, would commit {} pages as '{}', the pages are in {}", changes.len(), message, directory.display());
+        return;
+    }
+    wiki::publish(&directory, &message).unwrap_or_else(|message| fail(message));
+    println!("Published {} pages to {}", changes.len(), url);
+}
+
+// Records the current documentation and writes a feed entry for every snapshot that
+// added or changed APIs since the one before it
+fn write_feed(args: &Args) {
+    let path = args.feed.as_deref().unwrap_or("feed.xml");
+    archive(args, load_default(args).json());
+
+    let snapshots = snapshot::list();
+    let parsers: Vec<DocParser> = snapshots.iter().map(|path| load_previous(args, &path.to_string_lossy())).collect();
+    let mut releases = Vec::new();
+    for (i, pair) in parsers.windows(2).enumerate() {
+        let changes: Vec<diff::Change> = diff::diff(&pair[0], &pair[1]).into_iter()
+            .filter(|change| matches!(change.kind, diff::ChangeKind::Added | diff::ChangeKind::Changed))
+            .collect();
+        if changes.is_empty() {
+            continue;
+        }
+
+        let snapshot = &snapshots[i + 1];
+        releases.push(feed::Release {
+            id: snapshot::hash_of(snapshot).unwrap_or_default().to_string(),
+            seconds: snapshot::seconds_of(snapshot).unwrap_or(0),
+            changes
+        });
+    }
+    releases.reverse();
+
+    let updated = releases.first().map_or_else(date::now_seconds, |release| release.seconds);
+    replace(path, feed::atom(&releases, updated)).unwrap_or_else(|error| fail(format!("Could not write {}: {}", path, error)));
+    log::info(&format!("Wrote {} with {}", path, diff::plural(releases.len(), "entry")));
+}
+
+// Records the normalized documentation in the state directory
+fn archive(args: &Args, json: &Value) {
+    if let Err(message) = snapshot::archive(json) {
+        fail(message);
+    }
 
-    fs::write("Classes.md", parser.parse_classes()).expect("Could not write classes");
-    fs::write("Extensions.md", parser.parse_extensions()).expect("Could not write extensions")
+    if let Some(keep) = args.keep_snapshots {
+        let removed = snapshot::prune(keep);
+        if removed > 0 {
+            log::debug(&format!("Pruned {}", diff::plural(removed, "snapshot")));
+        }
+    }
 }
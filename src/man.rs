@@ -0,0 +1,60 @@
+use crate::args::{COMMANDS, FLAGS};
+use crate::exit;
+
+// Backslashes and dashes are escaped, and lines can't start with a control character
+fn escape(text: &str) -> String {
+    let text = text.replace('\\', "\\e").replace('-', "\\-");
+    if text.starts_with('.') || text.starts_with('\'') {
+        format!("\\&{}", text)
+    } else {
+        text
+    }
+}
+
+// The man page of the tool in roff, generated from the commands and flags `parse` knows
+pub fn page(bin: &str) -> String {
+    let mut page = String::new();
+    page.push_str(&format!(".TH {} 1 \"\" \"{} {}\" \"User Commands\"\n", escape(&bin.to_uppercase()), escape(bin), env!("CARGO_PKG_VERSION")));
+
+    page.push_str(".SH NAME\n");
+    page.push_str(&format!("{} \\- converts Arucas documentation in JSON into Markdown and other formats\n", escape(bin)));
+
+    page.push_str(".SH SYNOPSIS\n");
+    page.push_str(&format!(".B {}\n[\\fICOMMAND\\fR] [\\fIOPTIONS\\fR]\n", escape(bin)));
+
+    page.push_str(".SH DESCRIPTION\n");
+    page.push_str("Without a command, reads \\fIAllDocs.json\\fR and writes \\fIClasses.md\\fR and \\fIExtensions.md\\fR, ");
+    page.push_str("or the output of the format given with \\fB\\-\\-format\\fR. ");
+    page.push_str("Settings are also read from \\fIarucas\\-md.toml\\fR if it exists.\n");
+
+    page.push_str(".SH COMMANDS\n");
+    for command in COMMANDS {
+        page.push_str(".TP\n");
+        match command.value {
+            Some(value) => page.push_str(&format!("\\fB{}\\fR \\fI{}\\fR\n", escape(command.name), escape(value))),
+            None => page.push_str(&format!("\\fB{}\\fR\n", escape(command.name)))
+        }
+        page.push_str(&format!("{}\n", escape(command.help)));
+    }
+
+    page.push_str(".SH OPTIONS\n");
+    for flag in FLAGS {
+        page.push_str(".TP\n");
+        let names = flag.names.iter().map(|name| format!("\\fB{}\\fR", escape(name))).collect::<Vec<_>>().join(", ");
+        match flag.value {
+            Some(value) => page.push_str(&format!("{} \\fI{}\\fR\n", names, escape(value))),
+            None => page.push_str(&format!("{}\n", names))
+        }
+        page.push_str(&escape(flag.help));
+        if !flag.choices.is_empty() {
+            page.push_str(&format!(", one of {}", escape(&flag.choices.join(", "))));
+        }
+        page.push('\n');
+    }
+
+    page.push_str(".SH EXIT STATUS\n");
+    for (code, meaning) in exit::CODES {
+        page.push_str(&format!(".TP\n\\fB{}\\fR\n{}\n", code, escape(meaning)));
+    }
+    page
+}
@@ -0,0 +1,72 @@
+use std::io::{self, Write};
+use crate::output::Newlines;
+use crate::snapshot::{self, Fnv};
+
+// What goes in the comment at the end of generated pages, so tooling can tell stale pages, whose
+// input hash no longer matches, from hand-edited ones, whose page hash no longer matches
+#[derive(Clone, Default)]
+pub struct Metadata {
+    // When the pages were generated, left out for reproducible output
+    pub generated: Option<String>
+}
+
+// The comment recording how a page was made. `input` is the hash of the input it was generated
+// from, `page` the hash of everything before the comment's line
+pub fn comment(metadata: &Metadata, input: &str, source: &str, page: &str) -> String {
+    let mut comment = format!(
+        "<!-- {} version={} input={} source={} page={}",
+        env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), input, source, page
+    );
+    if let Some(generated) = &metadata.generated {
+        comment.push_str(&format!(" generated={}", generated));
+    }
+    comment.push_str(" -->");
+    comment
+}
+
+// Whether the line is a metadata comment, which is left out when comparing pages
+pub fn is_comment(line: &str) -> bool {
+    line.starts_with(&format!("<!-- {} version=", env!("CARGO_PKG_NAME")))
+}
+
+// The page with a blank line and the comment added at its end, the page is expected to end with
+// a newline as normalized markdown does
+pub fn append(metadata: &Metadata, page: String, input: &str, source: &str, newlines: Newlines) -> String {
+    let newline = if newlines.crlf { "\r\n" } else { "\n" };
+    let page = page + newline;
+    let comment = comment(metadata, input, source, &snapshot::hash(&page));
+    page + &comment + newline
+}
+
+// Passes everything written on while hashing it, for pages written a piece at a time
+pub struct HashingWriter<W: Write> {
+    out: W,
+    hash: Fnv
+}
+
+impl<W: Write> HashingWriter<W> {
+    pub fn new(out: W) -> HashingWriter<W> {
+        HashingWriter { out, hash: Fnv::default() }
+    }
+
+    // The hash of everything written so far
+    pub fn hash(&self) -> String {
+        self.hash.finish()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.out.write(buf)?;
+        self.hash.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
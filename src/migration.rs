@@ -0,0 +1,98 @@
+use serde_json::{json, Value};
+
+// The layout version this converter renders, documents without
+// a version field are assumed to be the original layout
+pub const CURRENT_VERSION: u64 = 2;
+
+type Migration = fn(&mut Value);
+
+// Adapters upgrading a document from the given version to the next,
+// added whenever the exporter changes the layout of the documentation
+const MIGRATIONS: &[(u64, Migration)] = &[(1, renamed_fields)];
+
+// Version 2 renamed the global `values` to `globals`, and replaced the `import_path` of classes
+// with an `import` object that can also name the class it is imported as
+fn renamed_fields(json: &mut Value) {
+    if let Some(object) = json.as_object_mut() {
+        if let Some(values) = object.remove("values") {
+            object.entry("globals").or_insert(values);
+        }
+    }
+
+    // Documents that aren't objects are left for validation to report
+    for class in json.get_mut("classes").and_then(Value::as_object_mut).into_iter().flat_map(|classes| classes.values_mut()) {
+        let Some(class) = class.as_object_mut() else {
+            continue;
+        };
+        match class.remove("import_path") {
+            Some(Value::String(path)) if class.get("import").is_none_or(Value::is_null) => {
+                class.insert("import".to_string(), json!({ "path": path }));
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn migrate(json: &mut Value) -> Result<(), String> {
+    let mut version = match &json["version"] {
+        Value::Null => 1,
+        Value::Number(number) => number.as_u64().filter(|version| *version > 0).ok_or_else(|| {
+            format!("Invalid documentation version {}, expected a positive integer", number)
+        })?,
+        other => return Err(format!("Invalid documentation version {}, expected a positive integer", other))
+    };
+
+    if version > CURRENT_VERSION {
+        return Err(format!(
+            "Unsupported documentation version {}, this converter supports versions up to {}",
+            version, CURRENT_VERSION
+        ));
+    }
+
+    for (from, migration) in MIGRATIONS {
+        if *from == version {
+            migration(json);
+            version += 1;
+        }
+    }
+
+    if let Some(object) = json.as_object_mut() {
+        object.insert("version".to_string(), Value::from(version));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::{migrate, CURRENT_VERSION};
+
+    #[test]
+    fn migrates_version_1_documents() {
+        let mut json = json!({
+            "values": [{ "name": "PI" }],
+            "classes": { "Greeter": { "name": "Greeter", "import_path": "util.Greeter" } }
+        });
+        migrate(&mut json).unwrap();
+        assert_eq!(json, json!({
+            "version": CURRENT_VERSION,
+            "globals": [{ "name": "PI" }],
+            "classes": { "Greeter": { "name": "Greeter", "import": { "path": "util.Greeter" } } }
+        }));
+    }
+
+    #[test]
+    fn leaves_documents_that_are_not_objects_for_validation() {
+        for mut json in [json!([]), json!("x"), json!({ "classes": [] })] {
+            let original = json.clone();
+            migrate(&mut json).unwrap();
+            if original.is_object() {
+                assert_eq!(json["classes"], original["classes"]);
+            } else {
+                assert_eq!(json, original);
+            }
+        }
+        assert!(migrate(&mut json!({ "version": CURRENT_VERSION + 1 })).is_err());
+    }
+}
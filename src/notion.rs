@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::iter::Peekable;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::vec::Drain;
+use serde_json::{json, Value};
+use crate::doc_parser::DocParser;
+use crate::html;
+use crate::output::{create_dir, write_file};
+
+pub const DIRECTORY: &str = "notion";
+
+// Notion takes at most this many blocks per request and characters per piece of text
+const BLOCKS_PER_REQUEST: usize = 100;
+const TEXT_LENGTH: usize = 2000;
+const API: &str = "https://api.notion.com/v1";
+const API_VERSION: &str = "2022-06-28";
+
+// A page as its title and the blocks of its content
+pub struct Page {
+    pub title: String,
+    pub blocks: Vec<Value>
+}
+
+// Writes the blocks of a page per class and extension as JSON, and pages.json
+// listing the title and file of each, returning the pages for uploading
+pub fn write(parser: &DocParser, path: &Path) -> Result<Vec<Page>, String> {
+    for directory in ["classes", "extensions"] {
+        create_dir(&path.join(directory))?;
+    }
+
+    let mut pages = Vec::new();
+    let mut index = Vec::new();
+    for class in parser.classes() {
+        let file = format!("classes/{}.json", class.name);
+        index.push(json!({ "title": class.name, "file": file, "kind": "class" }));
+        let title = class.name.clone();
+        let md = parser.render_class(class, &mut HashMap::new());
+        pages.push(Page { title, blocks: blocks(&md) });
+        write_file(&path.join(&file), &serde_json::to_string_pretty(&pages.last().unwrap().blocks).unwrap())?;
+    }
+
+    for (name, functions) in parser.extensions() {
+        let file = format!("extensions/{}.json", name);
+        index.push(json!({ "title": name, "file": file, "kind": "extension" }));
+        let md = parser.render_extension(&name, &functions, &mut HashMap::new());
+        pages.push(Page { title: name, blocks: blocks(&md) });
+        write_file(&path.join(&file), &serde_json::to_string_pretty(&pages.last().unwrap().blocks).unwrap())?;
+    }
+
+    write_file(&path.join("pages.json"), &serde_json::to_string_pretty(&Value::Array(index)).unwrap())?;
+    Ok(pages)
+}
+
+// Converts the markdown written by the renderer into Notion blocks. As with HTML only what the
+// renderer produces is understood, anchors are dropped as Notion links to blocks by their ids.
+pub fn blocks(md: &str) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut quote: Vec<&str> = Vec::new();
+    let mut table: Vec<&str> = Vec::new();
+    // List items with their depth, nested once the list ends
+    let mut list: Vec<(usize, String)> = Vec::new();
+    let mut code: Option<(&str, Option<&str>, String)> = None;
+
+    for line in md.lines() {
+        if let Some((language, title, block)) = &mut code {
+            if line.trim_end() == "```" {
+                blocks.push(code_block(language, *title, block));
+                code = None;
+            } else {
+                block.push_str(line);
+                block.push('\n');
+            }
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let list_depth = if trimmed.starts_with("- ") { (line.len() - trimmed.len()) / 2 } else { usize::MAX };
+
+        // Lines indented under a list item continue it
+        if list_depth == usize::MAX && !list.is_empty() && line.starts_with("  ") {
+            let item = &mut list.last_mut().unwrap().1;
+            item.push(' ');
+            item.push_str(trimmed);
+            continue;
+        }
+        if !line.starts_with('|') {
+            close_table(&mut blocks, &mut table);
+        }
+        if !line.starts_with('>') {
+            close_quote(&mut blocks, &mut quote);
+        }
+        if list_depth == usize::MAX && !line.trim().is_empty() {
+            close_list(&mut blocks, &mut list);
+        }
+
+        if line.trim().is_empty() || html::anchor(line).is_some() {
+            close_paragraph(&mut blocks, &mut paragraph);
+        } else if let Some(info) = line.strip_prefix("```") {
+            close_paragraph(&mut blocks, &mut paragraph);
+            code = Some((info.split_whitespace().next().unwrap_or_default(), html::fence_title(line), String::new()));
+        } else if let Some((level, title)) = html::heading(line) {
+            close_paragraph(&mut blocks, &mut paragraph);
+            let kind = format!("heading_{}", level.min(3));
+            blocks.push(json!({ "type": kind, kind: { "rich_text": rich_text(title) } }));
+        } else if line.starts_with('|') {
+            close_paragraph(&mut blocks, &mut paragraph);
+            table.push(line);
+        } else if let Some(quoted) = line.strip_prefix('>') {
+            close_paragraph(&mut blocks, &mut paragraph);
+            quote.push(quoted.strip_prefix(' ').unwrap_or(quoted));
+        } else if list_depth != usize::MAX {
+            close_paragraph(&mut blocks, &mut paragraph);
+            list.push((list_depth, trimmed[2..].to_string()));
+        } else {
+            paragraph.push(line);
+        }
+    }
+
+    close_paragraph(&mut blocks, &mut paragraph);
+    close_list(&mut blocks, &mut list);
+    close_quote(&mut blocks, &mut quote);
+    close_table(&mut blocks, &mut table);
+    if let Some((language, title, block)) = code {
+        blocks.push(code_block(language, title, &block));
+    }
+    blocks
+}
+
+fn code_block(language: &str, title: Option<&str>, code: &str) -> Value {
+    let language = match language {
+        "kt" | "kotlin" => "kotlin",
+        "" | "text" => "plain text",
+        other => other
+    };
+    let caption = title.map(plain).unwrap_or_default();
+    json!({ "type": "code", "code": { "language": language, "caption": caption, "rich_text": plain(code.trim_end_matches('\n')) } })
+}
+
+fn close_paragraph(blocks: &mut Vec<Value>, paragraph: &mut Vec<&str>) {
+    if !paragraph.is_empty() {
+        blocks.push(json!({ "type": "paragraph", "paragraph": { "rich_text": rich_text(&paragraph.join(" ")) } }));
+        paragraph.clear();
+    }
+}
+
+// Quoted lines keep their breaks, as they are separate notes or warnings
+fn close_quote(blocks: &mut Vec<Value>, quote: &mut Vec<&str>) {
+    if !quote.is_empty() {
+        blocks.push(json!({ "type": "quote", "quote": { "rich_text": rich_text(quote.join("\n").trim()) } }));
+        quote.clear();
+    }
+}
+
+fn close_list(blocks: &mut Vec<Value>, list: &mut Vec<(usize, String)>) {
+    let mut items = list.drain(..).peekable();
+    while items.peek().is_some() {
+        blocks.push(list_item(&mut items));
+    }
+}
+
+// An item with the deeper items after it as its children
+fn list_item(items: &mut Peekable<Drain<(usize, String)>>) -> Value {
+    let (depth, text) = items.next().unwrap();
+    let mut children = Vec::new();
+    while items.peek().is_some_and(|(next, _)| *next > depth) {
+        children.push(list_item(items));
+    }
+
+    let mut item = json!({ "rich_text": rich_text(&text) });
+    if !children.is_empty() {
+        item["children"] = Value::Array(children);
+    }
+    json!({ "type": "bulleted_list_item", "bulleted_list_item": item })
+}
+
+// The first row is the header, the row of dashes below it is left out
+fn close_table(blocks: &mut Vec<Value>, table: &mut Vec<&str>) {
+    if table.is_empty() {
+        return;
+    }
+    let rows: Vec<Vec<Value>> = table.drain(..).enumerate()
+        .filter(|(i, row)| *i != 1 || !row.chars().all(|c| matches!(c, '|' | '-' | ':' | ' ')))
+        .map(|(_, row)| cells(row).iter().map(|cell| Value::Array(rich_text(cell))).collect())
+        .collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let children: Vec<Value> = rows.into_iter().map(|mut cells| {
+        cells.resize(width, json!([]));
+        json!({ "type": "table_row", "table_row": { "cells": cells } })
+    }).collect();
+    blocks.push(json!({
+        "type": "table",
+        "table": { "table_width": width, "has_column_header": true, "has_row_header": false, "children": children }
+    }));
+}
+
+// The cells of a table row, `\|` is a pipe within a cell
+fn cells(row: &str) -> Vec<String> {
+    let row = row.trim().trim_start_matches('|');
+    let row = row.strip_suffix('|').filter(|rest| !rest.ends_with('\\')).unwrap_or(row);
+    let mut cells = vec![String::new()];
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => cells.last_mut().unwrap().push(chars.next().unwrap()),
+            '|' => cells.push(String::new()),
+            c => cells.last_mut().unwrap().push(c)
+        }
+    }
+    cells.iter().map(|cell| cell.trim().to_string()).collect()
+}
+
+// Text with inline code, bold text and links. Only links to other sites are kept as
+// links, as the pages they lead to here have other addresses in Notion.
+fn rich_text(text: &str) -> Vec<Value> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(['`', '[']) {
+        add_bold(&mut parts, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(code) = rest.strip_prefix('`') {
+            let end = code.find('`').unwrap_or(code.len());
+            add_text(&mut parts, &code[..end], json!({ "code": true }), None);
+            rest = code.get(end + 1..).unwrap_or_default();
+        } else if let Some((label, target, end)) = html::link_at(rest) {
+            let url = target.contains("://").then_some(target);
+            // Links to other items are labelled with their signature as code
+            let code = label.len() > 1 && label.starts_with('`') && label.ends_with('`');
+            let label = if code { &label[1..label.len() - 1] } else { label };
+            add_text(&mut parts, label, json!({ "code": code }), url);
+            rest = &rest[end..];
+        } else {
+            add_bold(&mut parts, "[");
+            rest = &rest[1..];
+        }
+    }
+    add_bold(&mut parts, rest);
+    parts
+}
+
+fn add_bold(parts: &mut Vec<Value>, text: &str) {
+    if text.matches("**").count() % 2 == 1 {
+        add_text(parts, text, json!({}), None);
+        return;
+    }
+    for (i, part) in text.split("**").enumerate() {
+        add_text(parts, part, json!({ "bold": i % 2 == 1 }), None);
+    }
+}
+
+// Long text is split into pieces Notion accepts
+fn add_text(parts: &mut Vec<Value>, text: &str, annotations: Value, url: Option<&str>) {
+    let chars: Vec<char> = text.chars().collect();
+    for chunk in chars.chunks(TEXT_LENGTH) {
+        let link = url.map(|url| json!({ "url": url }));
+        let content: String = chunk.iter().collect();
+        parts.push(json!({ "type": "text", "text": { "content": content, "link": link }, "annotations": annotations }));
+    }
+}
+
+fn plain(text: &str) -> Vec<Value> {
+    let mut parts = Vec::new();
+    add_text(&mut parts, text, json!({}), None);
+    parts
+}
+
+// Creates each page under the parent page through the Notion API, using the integration
+// token in `NOTION_TOKEN`. Requests are sent with curl, as Notion is only reachable over HTTPS.
+pub fn upload(pages: &[Page], parent: &str) -> Result<usize, String> {
+    let token = env::var("NOTION_TOKEN").map_err(|_| "Uploading to Notion needs an integration token in NOTION_TOKEN".to_string())?;
+    for page in pages {
+        let mut chunks = page.blocks.chunks(BLOCKS_PER_REQUEST);
+        let first = chunks.next().unwrap_or_default();
+        let body = json!({
+            "parent": { "page_id": parent },
+            "properties": { "title": { "title": plain(&page.title) } },
+            "children": first
+        });
+        let created = request(&token, "POST", &format!("{}/pages", API), &body)?;
+        let id = created["id"].as_str().ok_or_else(|| format!("Notion did not return the id of '{}'", page.title))?;
+
+        for chunk in chunks {
+            request(&token, "PATCH", &format!("{}/blocks/{}/children", API, id), &json!({ "children": chunk }))?;
+        }
+    }
+    Ok(pages.len())
+}
+
+fn request(token: &str, method: &str, url: &str, body: &Value) -> Result<Value, String> {
+    let path = env::temp_dir().join(format!("arucas-md-notion-{}.json", std::process::id()));
+    fs::write(&path, body.to_string()).map_err(|error| format!("Could not write request: {}", error))?;
+
+    // Headers are read from standard input so the token isn't visible in the process list
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--request", method, url, "--header", "@-"])
+        .arg("--data-binary").arg(format!("@{}", path.display()))
+        .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("Could not run curl: {}", error))?;
+    let headers = format!(
+        "Authorization: Bearer {}\nNotion-Version: {}\nContent-Type: application/json\n",
+        token, API_VERSION
+    );
+    let _ = child.stdin.take().unwrap().write_all(headers.as_bytes());
+    let output = child.wait_with_output().map_err(|error| format!("Could not run curl: {}", error));
+    let _ = fs::remove_file(&path);
+    let output = output?;
+    if !output.status.success() {
+        return Err(format!("Request to Notion failed: {}", String::from_utf8_lossy(&output.stderr).trim_end()));
+    }
+
+    let response: Value = serde_json::from_slice(&output.stdout).map_err(|error| format!("Invalid response from Notion: {}", error))?;
+    if response["object"] == "error" {
+        return Err(format!("Notion rejected the request: {}", response["message"].as_str().unwrap_or("unknown error")));
+    }
+    Ok(response)
+}
@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+use std::path::Path;
+use crate::doc_parser::DocParser;
+use crate::output::{create_dir, write_file};
+
+pub const DIRECTORY: &str = "obsidian";
+
+// Writes an Obsidian vault: a note per class and extension with YAML properties,
+// types link to the note of their class so the graph view shows how they relate.
+// Returns the number of notes.
+pub fn write(parser: &DocParser, path: &Path) -> Result<usize, String> {
+    for directory in ["classes", "extensions"] {
+        create_dir(&path.join(directory))?;
+    }
+
+    let mut notes = 0;
+    for class in parser.classes() {
+        let mut note = String::from("---\nkind: class\n");
+        if let Some((_, path)) = class.import() {
+            note.push_str(&format!("import: \"{}\"\n", path));
+        }
+        note.push_str("tags:\n  - arucas\n  - arucas/class\n---\n\n");
+
+        let file = path.join("classes").join(format!("{}.md", class.name));
+        note.push_str(&parser.render_class(class, &mut HashMap::new()));
+        write_file(&file, &note)?;
+        notes += 1;
+    }
+
+    for (name, functions) in parser.extensions() {
+        let mut note = String::from("---\nkind: extension\n");
+        note.push_str(&format!("functions: {}\n", functions.len()));
+        note.push_str("tags:\n  - arucas\n  - arucas/extension\n---\n\n");
+
+        note.push_str(&parser.render_extension(&name, &functions, &mut HashMap::new()));
+        write_file(&path.join("extensions").join(format!("{}.md", name)), &note)?;
+        notes += 1;
+    }
+
+    Ok(notes)
+}
@@ -0,0 +1,265 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::{mem, process};
+use crate::doc_parser::{Class, DocParser};
+use crate::front_matter;
+use crate::keep;
+use crate::metadata::{self, HashingWriter};
+
+pub fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    replace(path, content).map_err(|error| format!("Could not write {}: {}", path.display(), error))
+}
+
+// Like `fs::write`, but through a temporary file renamed into place so the file is never left
+// half written, and leaving the file alone if it already holds the content so file watchers
+// don't fire on pages that didn't change
+pub fn replace(path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> io::Result<()> {
+    let (path, content) = (path.as_ref(), content.as_ref());
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() == content.len() as u64) && fs::read(path)? == content {
+        return Ok(());
+    }
+    let mut file = AtomicFile::create(path)?;
+    file.write_all(content)?;
+    file.commit()
+}
+
+// A file written next to its path under a temporary name, which only replaces the file at the
+// path once committed. Dropping it uncommitted, such as when rendering fails, deletes it
+pub struct AtomicFile {
+    path: PathBuf,
+    temporary: PathBuf,
+    file: Option<BufWriter<File>>
+}
+
+impl AtomicFile {
+    pub fn create(path: &Path) -> io::Result<AtomicFile> {
+        let name = path.file_name().map_or_else(String::new, |name| name.to_string_lossy().to_string());
+        let temporary = path.with_file_name(format!(".{}.{}.tmp", name, process::id()));
+        let file = BufWriter::new(File::create(&temporary)?);
+        Ok(AtomicFile { path: path.to_path_buf(), temporary, file: Some(file) })
+    }
+
+    // Moves the file into place, unless the file there already holds the same content
+    pub fn commit(mut self) -> io::Result<()> {
+        let Some(file) = self.file.take() else {
+            return Ok(());
+        };
+        let result = file.into_inner().map_err(|error| error.into_error()).and_then(|_| {
+            if same_content(&self.temporary, &self.path) {
+                fs::remove_file(&self.temporary)
+            } else {
+                fs::rename(&self.temporary, &self.path)
+            }
+        });
+        if result.is_err() {
+            let _ = fs::remove_file(&self.temporary);
+        }
+        result
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.file {
+            Some(file) => file.write(buf),
+            None => Err(io::Error::other("write after commit"))
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.as_mut().map_or(Ok(()), |file| file.flush())
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if self.file.take().is_some() {
+            let _ = fs::remove_file(&self.temporary);
+        }
+    }
+}
+
+fn same_content(first: &Path, second: &Path) -> bool {
+    let sizes = (fs::metadata(first), fs::metadata(second));
+    matches!(sizes, (Ok(first), Ok(second)) if first.len() == second.len()) && fs::read(first).ok() == fs::read(second).ok()
+}
+
+pub fn create_dir(path: &Path) -> Result<(), String> {
+    fs::create_dir_all(path).map_err(|error| format!("Could not create {}: {}", path.display(), error))
+}
+
+// Classes grouped under their category for tables of contents, with a single
+// group when no class has one
+pub fn group_by_category(classes: Vec<Class>) -> Vec<(String, Vec<Class>)> {
+    if classes.iter().all(|class| class.category.is_none()) {
+        return vec![("Classes".to_string(), classes)];
+    }
+
+    let mut groups: Vec<(String, Vec<Class>)> = Vec::new();
+    for class in classes {
+        let title = class.category.clone().unwrap_or_else(|| "Other classes".to_string());
+        match groups.last_mut() {
+            Some((last, group)) if *last == title => group.push(class),
+            _ => groups.push((title, vec![class]))
+        }
+    }
+    groups
+}
+
+// How lines end and how many blank lines may follow each other in markdown output.
+// The defaults satisfy markdownlint's MD012 and MD047.
+#[derive(Clone, Copy, Debug)]
+pub struct Newlines {
+    pub crlf: bool,
+    // Consecutive blank lines kept outside of code blocks
+    pub max_blank_lines: usize
+}
+
+impl Default for Newlines {
+    fn default() -> Newlines {
+        Newlines { crlf: false, max_blank_lines: 1 }
+    }
+}
+
+// Writes markdown a line at a time with the newline policy applied. Blank lines are
+// held back until the next line, so the output ends with exactly one newline once
+// `finish` is called.
+pub struct NewlineWriter<W: Write> {
+    out: W,
+    newlines: Newlines,
+    line: Vec<u8>,
+    blank_lines: usize,
+    code: bool,
+    started: bool
+}
+
+impl<W: Write> NewlineWriter<W> {
+    pub fn new(out: W, newlines: Newlines) -> NewlineWriter<W> {
+        NewlineWriter { out, newlines, line: Vec::new(), blank_lines: 0, code: false, started: false }
+    }
+
+    fn end_line(&mut self) -> io::Result<()> {
+        let mut line = mem::take(&mut self.line);
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+
+        // Blank lines in examples are kept as they are
+        if !self.code && line.iter().all(u8::is_ascii_whitespace) {
+            self.blank_lines += 1;
+            return Ok(());
+        }
+
+        let newline: &[u8] = if self.newlines.crlf { b"\r\n" } else { b"\n" };
+        if self.started {
+            for _ in 0..self.blank_lines.min(self.newlines.max_blank_lines) {
+                self.out.write_all(newline)?;
+            }
+        }
+        self.out.write_all(&line)?;
+        self.out.write_all(newline)?;
+        self.blank_lines = 0;
+        self.started = true;
+
+        // Fences are indented inside list items
+        if line.trim_ascii_start().starts_with(b"```") {
+            self.code = !self.code;
+        }
+        Ok(())
+    }
+
+    // Writes the last line, dropping any blank lines after it
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.line.is_empty() {
+            self.end_line()?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+impl<W: Write> Write for NewlineWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &byte in buf {
+            if byte == b'\n' {
+                self.end_line()?;
+            } else {
+                self.line.push(byte);
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+// The markdown with the newline policy applied
+pub fn normalize(md: &str, newlines: Newlines) -> String {
+    let mut writer = NewlineWriter::new(Vec::new(), newlines);
+    writer.write_all(md.as_bytes()).unwrap();
+    String::from_utf8_lossy(&writer.finish().unwrap()).to_string()
+}
+
+// Writes Classes.md and Extensions.md into the directory as the converter does, with `header`
+// at the top of Classes.md after its front matter, such as a diagram
+pub fn write_markdown(parser: &DocParser, directory: &Path, input: &str, header: &str) -> Result<(), String> {
+    let template = parser.options.front_matter.as_deref();
+    write_page(parser, &directory.join("Classes.md"), "classes", input, |mut out| {
+        out.write_all(front_matter::page(template, "Classes", 1, "classes").as_bytes())?;
+        out.write_all(header.as_bytes())?;
+        parser.write_classes(&mut out)
+    }).map_err(|error| format!("Could not write classes: {}", error))?;
+
+    write_page(parser, &directory.join("Extensions.md"), "extensions", input, |mut out| {
+        out.write_all(front_matter::page(template, "Extensions", 2, "extensions").as_bytes())?;
+        parser.write_extensions(&mut out)
+    }).map_err(|error| format!("Could not write extensions: {}", error))
+}
+
+// Writes a page a piece at a time, or as a whole when it has regions to keep from the page it
+// replaces, ending with the metadata comment if asked for
+pub fn write_page(parser: &DocParser, path: &Path, source: &str, input: &str, write: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> io::Result<()> {
+    let newlines = parser.options.newlines;
+    let kept = keep::read(path);
+    if kept.is_empty() {
+        let mut page = NewlineWriter::new(HashingWriter::new(AtomicFile::create(path)?), newlines);
+        write(&mut page)?;
+        return page.finish().and_then(|page| add_metadata(parser, page, input, source)).and_then(AtomicFile::commit);
+    }
+
+    let mut page = NewlineWriter::new(Vec::new(), newlines);
+    write(&mut page)?;
+    let page = keep::merge(&String::from_utf8_lossy(&page.finish()?), &kept);
+    match &parser.options.metadata {
+        Some(options) => replace(path, metadata::append(options, page, input, source, newlines)),
+        None => replace(path, page)
+    }
+}
+
+// Ends a page written a piece at a time with the metadata comment, if asked for
+fn add_metadata(parser: &DocParser, mut page: HashingWriter<AtomicFile>, input: &str, source: &str) -> io::Result<AtomicFile> {
+    let Some(options) = &parser.options.metadata else {
+        return Ok(page.into_inner());
+    };
+    let newline = if parser.options.newlines.crlf { "\r\n" } else { "\n" };
+    page.write_all(newline.as_bytes())?;
+    let comment = metadata::comment(options, input, source, &page.hash());
+    let mut file = page.into_inner();
+    file.write_all((comment + newline).as_bytes())?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize, Newlines};
+
+    #[test]
+    fn keeps_blank_lines_in_indented_code_blocks() {
+        let md = "- Example:\n  ```kt\n  a = 1;\n\n\n  b = 2;\n  ```\n\n\n\nAfter\n";
+        let expected = "- Example:\n  ```kt\n  a = 1;\n\n\n  b = 2;\n  ```\n\nAfter\n";
+        assert_eq!(normalize(md, Newlines::default()), expected);
+    }
+}
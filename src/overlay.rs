@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::Path;
+use serde_json::{json, Value};
+use crate::toml;
+
+// Overlays are JSON, or TOML if the file ends in .toml
+pub fn load(path: &str) -> Result<Value, String> {
+    let content = fs::read_to_string(path).map_err(|error| format!("Could not read overlay '{}': {}", path, error))?;
+    let result = if path.ends_with(".toml") {
+        toml::parse(&content)
+    } else {
+        serde_json::from_str(&content).map_err(|error| error.to_string())
+    };
+    result.map_err(|error| format!("Could not parse overlay '{}': {}", path, error))
+}
+
+// Reads every .toml file in the directory as a fragment documenting a single class,
+// each is returned as an overlay so it can extend an existing class or add a new one
+pub fn load_fragments(directory: &str) -> Result<Vec<Value>, String> {
+    let entries = fs::read_dir(directory).map_err(|error| format!("Could not read fragments '{}': {}", directory, error))?;
+    let mut paths: Vec<_> = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "toml"))
+        .collect();
+    paths.sort();
+
+    paths.iter().map(|path| load_fragment(path)).collect()
+}
+
+fn load_fragment(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path).map_err(|error| format!("Could not read fragment '{}': {}", path.display(), error))?;
+    let mut class = toml::parse(&content).map_err(|error| format!("Could not parse fragment '{}': {}", path.display(), error))?;
+
+    // The class is named after the file unless it says otherwise
+    let name = match class["name"].as_str() {
+        Some(name) => name.to_string(),
+        None => path.file_stem().unwrap().to_string_lossy().to_string()
+    };
+    class["name"] = Value::from(name.clone());
+
+    expand_text(&mut class);
+    for key in ["static_members", "members", "constructors", "methods", "static_methods"] {
+        for item in class[key].as_array_mut().into_iter().flatten() {
+            expand_text(item);
+        }
+    }
+
+    Ok(json!({ "classes": { name: class } }))
+}
+
+// Multi-line strings are easier to write in TOML than arrays of lines, so
+// descriptions may be a single string and examples a single example
+fn expand_text(item: &mut Value) {
+    for key in ["desc", "deprecated"] {
+        if let Some(text) = item[key].as_str() {
+            let lines: Vec<&str> = text.trim_end_matches('\n').lines().collect();
+            item[key] = Value::from(lines);
+        }
+    }
+
+    if let Some(example) = item["examples"].as_str() {
+        item["examples"] = Value::from(vec![example.trim_end_matches('\n')]);
+    }
+}
+
+// Objects are merged recursively, arrays of named entries (methods, members)
+// are merged by name, and a key prefixed with '+' appends to an existing array
+pub fn apply(json: &mut Value, overlay: &Value) {
+    let (Some(target), Some(source)) = (json.as_object_mut(), overlay.as_object()) else {
+        *json = overlay.clone();
+        return;
+    };
+
+    for (key, value) in source {
+        if let Some(key) = key.strip_prefix('+') {
+            let existing = target.entry(key).or_insert_with(|| Value::Array(Vec::new()));
+            match (existing.as_array_mut(), value) {
+                (Some(array), Value::Array(extra)) => array.extend(extra.iter().cloned()),
+                (Some(array), extra) => array.push(extra.clone()),
+                _ => *existing = value.clone()
+            }
+            continue;
+        }
+
+        match target.get_mut(key) {
+            Some(existing) if existing.is_object() => apply(existing, value),
+            Some(Value::Array(existing)) if is_named(value) => merge_named(existing, value),
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn is_named(value: &Value) -> bool {
+    match value.as_array() {
+        Some(array) => !array.is_empty() && array.iter().all(|item| item["name"].is_string()),
+        None => false
+    }
+}
+
+fn merge_named(existing: &mut Vec<Value>, overlay: &Value) {
+    for item in overlay.as_array().unwrap() {
+        match existing.iter_mut().find(|other| other["name"] == item["name"]) {
+            Some(other) => apply(other, item),
+            None => existing.push(item.clone())
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use crate::date;
+use crate::diff::plural;
+use crate::doc_parser::DocParser;
+use crate::html;
+use crate::output::write_file;
+use crate::reference;
+
+pub const FILE: &str = "FullReference.pdf";
+
+// Programs that print HTML to PDF, tried in this order unless one is chosen
+pub const ENGINES: &[&str] = &["weasyprint", "wkhtmltopdf", "chromium"];
+
+// Pages are A4 and numbered, with a class or extension starting each page and a bookmark for each.
+// Bookmark levels are only understood by weasyprint, the others make bookmarks of every heading
+const STYLESHEET: &str = "@page { size: A4; margin: 2cm; @bottom-center { content: counter(page); } }
+@page :first { @bottom-center { content: none; } }
+body { max-width: none; padding: 0; }
+.cover { break-after: page; text-align: center; padding-top: 35%; }
+.cover .title { font-size: 2.5em; font-weight: bold; }
+h1, h2.entry { break-before: page; }
+h1 + h2.entry { break-before: auto; }
+h1 { bookmark-level: 1; }
+h2, h3, h4, h5, h6 { bookmark-level: none; }
+h2.entry { bookmark-level: 2; }
+h2, h3, h4 { break-after: avoid; }
+pre, table, blockquote { break-inside: avoid; }
+";
+
+// Writes FullReference.pdf by printing the reference with a cover page, returns the engine used
+pub fn write(parser: &DocParser, path: &Path, engine: Option<&str>) -> Result<&'static str, String> {
+    // Beside the PDF, so images in the assets directory are found
+    let page = path.with_extension("print.html");
+    write_file(&page, &html(parser))?;
+    let printed = print(&page, path, engine);
+    let _ = fs::remove_file(&page);
+    printed
+}
+
+// The page printed: a cover, then the reference with the anchors of headings moved onto them
+// so that links land on the page the heading starts
+pub fn html(parser: &DocParser) -> String {
+    let mut body = format!(
+        "<section class=\"cover\">\n<p class=\"title\">{}</p>\n<p>{} and {}</p>\n<p>Generated on {}</p>\n</section>\n",
+        html::escape(reference::TITLE),
+        plural(parser.classes().len(), "class"),
+        plural(parser.extensions().len(), "extension"),
+        date::today()
+    );
+
+    // The classes and extensions are the second level headings outside the glossary
+    let mut section = String::new();
+    let mut anchor: Option<String> = None;
+    for line in html::from_markdown(&reference::body(parser)).lines() {
+        if let Some(id) = line.strip_prefix("<a id=\"").and_then(|line| line.strip_suffix("\"></a>")) {
+            if let Some(previous) = anchor.replace(id.to_string()) {
+                body.push_str(&format!("<a id=\"{}\"></a>\n", previous));
+            }
+            continue;
+        }
+
+        let heading = line.strip_prefix("<h").and_then(|rest| {
+            let level = rest.chars().next()?.to_digit(10)?;
+            Some((level, rest[1..].strip_prefix('>')?.strip_suffix(&format!("</h{}>", level))?))
+        });
+        match heading {
+            Some((level, title)) => {
+                if level == 1 {
+                    section = title.to_string();
+                }
+                let id = anchor.take().map(|id| format!(" id=\"{}\"", id)).unwrap_or_default();
+                let class = if level == 2 && section != "Glossary" { " class=\"entry\"" } else { "" };
+                body.push_str(&format!("<h{}{}{}>{}</h{}>\n", level, id, class, title, level));
+            }
+            None => {
+                if let Some(id) = anchor.take() {
+                    body.push_str(&format!("<a id=\"{}\"></a>\n", id));
+                }
+                body.push_str(line);
+                body.push('\n');
+            }
+        }
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+        <style>\n{}{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        html::escape(reference::TITLE), html::STYLESHEET, STYLESHEET, body
+    )
+}
+
+// The programs an engine may be installed as, and the arguments printing the page to the PDF
+fn command(engine: &str, page: &Path, pdf: &Path) -> (&'static [&'static str], Vec<String>) {
+    let (page, pdf) = (page.display().to_string(), pdf.display().to_string());
+    match engine {
+        "weasyprint" => (&["weasyprint"], vec![page, pdf]),
+        "wkhtmltopdf" => (&["wkhtmltopdf"], vec![
+            "--quiet".to_string(), "--enable-local-file-access".to_string(), "--outline".to_string(),
+            "--outline-depth".to_string(), "2".to_string(), page, pdf
+        ]),
+        _ => (&["chromium", "chromium-browser", "google-chrome"], vec![
+            "--headless".to_string(), "--disable-gpu".to_string(), "--no-pdf-header-footer".to_string(),
+            "--generate-pdf-document-outline".to_string(), format!("--print-to-pdf={}", pdf), page
+        ])
+    }
+}
+
+fn print(page: &Path, pdf: &Path, engine: Option<&str>) -> Result<&'static str, String> {
+    let engines: Vec<&'static str> = match engine {
+        Some(engine) => ENGINES.iter().copied().filter(|name| *name == engine).collect(),
+        None => ENGINES.to_vec()
+    };
+    for engine in engines {
+        let (programs, args) = command(engine, page, pdf);
+        for program in programs {
+            let output = match Command::new(program).args(&args).stdin(Stdio::null()).output() {
+                Ok(output) => output,
+                // Not installed, so the next one is tried
+                Err(error) if error.kind() == ErrorKind::NotFound => continue,
+                Err(error) => return Err(format!("Could not run {}: {}", program, error))
+            };
+            if !output.status.success() {
+                return Err(format!("{} could not print the reference: {}", program, String::from_utf8_lossy(&output.stderr).trim_end()));
+            }
+            return Ok(engine);
+        }
+    }
+    match engine {
+        Some(engine) => Err(format!("Could not find {}, install it or choose another with '--pdf-engine'", engine)),
+        None => Err(format!("Could not find a program to print PDFs with, install one of {}", ENGINES.join(", ")))
+    }
+}
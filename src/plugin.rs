@@ -0,0 +1,40 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use serde_json::json;
+use crate::doc_parser::DocParser;
+
+// Bumped whenever what plugins are given changes in a way they could notice
+pub const PROTOCOL: u64 = 1;
+
+// Output formats shipped as programs of their own. The plugin runs in the output directory and
+// reads one JSON object from its standard input: the `protocol` version, the `document` after
+// migration, overlays and filtering, in the same layout as `AllDocs.json`, and the markdown `pages`
+// as they would be written, for plugins that convert markdown rather than the document itself.
+pub fn run(parser: &DocParser, command: &str, directory: &Path) -> Result<(), String> {
+    let mut words = command.split_whitespace();
+    let program = words.next().ok_or_else(|| "The plugin command is empty".to_string())?;
+
+    let mut classes = Vec::new();
+    let mut extensions = Vec::new();
+    parser.write_classes(&mut classes).and_then(|_| parser.write_extensions(&mut extensions))
+        .map_err(|error| format!("Could not render documentation: {}", error))?;
+    let input = json!({
+        "protocol": PROTOCOL,
+        "document": parser.json(),
+        "pages": {
+            "Classes.md": String::from_utf8_lossy(&classes),
+            "Extensions.md": String::from_utf8_lossy(&extensions)
+        }
+    });
+
+    let mut child = Command::new(program).args(words).current_dir(directory).stdin(Stdio::piped()).spawn()
+        .map_err(|error| format!("Could not run plugin '{}': {}", program, error))?;
+    // A plugin that exits without reading everything is judged by its status alone
+    let _ = child.stdin.take().unwrap().write_all(input.to_string().as_bytes());
+    let status = child.wait().map_err(|error| format!("Could not run plugin '{}': {}", program, error))?;
+    if !status.success() {
+        return Err(format!("Plugin '{}' failed: {}", program, status));
+    }
+    Ok(())
+}
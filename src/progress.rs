@@ -0,0 +1,60 @@
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+const WIDTH: usize = 30;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Shows progress bars from now on, they are only drawn when stderr is a terminal
+pub fn enable(enabled: bool) {
+    ENABLED.store(enabled && io::stderr().is_terminal(), Ordering::Relaxed);
+}
+
+// A bar for one phase of the run, such as rendering classes, redrawn on the same line as
+// items are done and cleared once the phase finishes
+pub struct Progress {
+    phase: &'static str,
+    total: usize,
+    done: AtomicUsize,
+    shown: bool
+}
+
+impl Progress {
+    pub fn new(phase: &'static str, total: usize) -> Progress {
+        let progress = Progress { phase, total, done: AtomicUsize::new(0), shown: ENABLED.load(Ordering::Relaxed) && total > 0 };
+        progress.draw(0);
+        progress
+    }
+
+    // Marks an item as done, items may be done from several threads
+    pub fn advance(&self) {
+        if !self.shown {
+            return;
+        }
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        // Only redrawn when the bar changes, so large phases don't flood the terminal
+        if done * WIDTH / self.total != (done - 1) * WIDTH / self.total || done == self.total {
+            self.draw(done);
+        }
+    }
+
+    fn draw(&self, done: usize) {
+        if !self.shown {
+            return;
+        }
+        let filled = done * WIDTH / self.total;
+        let bar = "#".repeat(filled) + &"-".repeat(WIDTH - filled);
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\r{} [{}] {}/{}", self.phase, bar, done.min(self.total), self.total);
+        let _ = stderr.flush();
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.shown {
+            // Clears the line for whatever is written next
+            let _ = write!(io::stderr().lock(), "\r\x1b[2K");
+        }
+    }
+}
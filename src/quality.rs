@@ -0,0 +1,115 @@
+use crate::doc_parser::{CodeExample, DocParser};
+
+// How much of the documentation is written, for failing builds that fall below a bar
+#[derive(Default)]
+pub struct Quality {
+    // Classes, members, constructors, methods and extension functions
+    pub items: usize,
+    // Items with a description, and an example if they take one
+    pub complete: usize,
+    pub missing_descriptions: Vec<String>,
+    pub missing_examples: Vec<String>
+}
+
+impl Quality {
+    pub fn coverage(&self) -> f64 {
+        if self.items == 0 {
+            return 100.0;
+        }
+        self.complete as f64 * 100.0 / self.items as f64
+    }
+
+    fn add(&mut self, location: String, described: bool, exemplified: bool) {
+        self.items += 1;
+        if described && exemplified {
+            self.complete += 1;
+        }
+        if !described {
+            self.missing_descriptions.push(location.clone());
+        }
+        if !exemplified {
+            self.missing_examples.push(location);
+        }
+    }
+}
+
+pub fn measure(parser: &DocParser) -> Quality {
+    let mut quality = Quality::default();
+    // Classes only need a description
+    each_item(parser, |location, desc, examples| {
+        quality.add(location, described(desc), examples.is_none_or(|examples| !examples.is_empty()));
+    });
+    quality
+}
+
+// The lines of examples longer than `--example-max-width` as the pages show them, after the item they are in
+pub fn long_example_lines(parser: &DocParser) -> Vec<String> {
+    let Some(max_width) = parser.options.example_max_width else {
+        return Vec::new();
+    };
+    let mut lines = Vec::new();
+    each_item(parser, |location, _, examples| {
+        for example in examples.into_iter().flatten() {
+            let code = parser.example_code(example.code());
+            for line in code.lines().filter(|line| line.chars().count() > max_width) {
+                lines.push(format!("{}: {}", location, line.trim()));
+            }
+        }
+    });
+    lines
+}
+
+// Visits every item with its location, description and examples, None for classes as they take none
+fn each_item(parser: &DocParser, mut visit: impl FnMut(String, Option<&[String]>, Option<&[CodeExample]>)) {
+    for class in parser.classes() {
+        let name = &class.name;
+        visit(name.clone(), class.desc.as_deref(), None);
+
+        for member in class.static_members.iter().chain(&class.members).flatten() {
+            let location = format!("{}.{}", name, member.name);
+            visit(location, member.desc.as_deref(), Some(member.examples.as_deref().unwrap_or_default()));
+        }
+        for constructor in class.constructors.iter().flatten() {
+            let params = constructor.params.as_ref().map_or(0, Vec::len);
+            visit(format!("new {}/{}", name, params), Some(&constructor.desc), Some(&constructor.examples));
+        }
+        for function in class.methods.iter().chain(&class.static_methods).flatten() {
+            let params = function.params.as_ref().map_or(0, Vec::len);
+            let location = format!("{}.{}/{}", name, function.name, params);
+            visit(location, function.desc.as_deref(), Some(function.examples.as_deref().unwrap_or_default()));
+        }
+    }
+
+    for (name, functions) in parser.extensions() {
+        for function in functions {
+            let params = function.params.as_ref().map_or(0, Vec::len);
+            let location = format!("{}.{}/{}", name, function.name, params);
+            visit(location, function.desc.as_deref(), Some(function.examples.as_deref().unwrap_or_default()));
+        }
+    }
+}
+
+fn described(desc: Option<&[String]>) -> bool {
+    desc.is_some_and(|desc| desc.iter().any(|line| !line.trim().is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample;
+
+    #[test]
+    fn lists_example_lines_wider_than_the_maximum() {
+        let mut parser = DocParser::from_json(sample::scaffold());
+        assert!(long_example_lines(&parser).is_empty());
+
+        parser.options.example_max_width = Some(20);
+        assert_eq!(long_example_lines(&parser), [
+            "Counter.increment/0: counter = new Counter();",
+            "Counter.increment/0: print(counter.increment());"
+        ]);
+
+        parser.options.example_max_width = Some(200);
+        assert!(long_example_lines(&parser).is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use crate::aliases;
+use crate::doc_parser::DocParser;
+use crate::glossary;
+use crate::html;
+use crate::output::group_by_category;
+
+pub const FILE: &str = "FullReference.md";
+pub const HTML_FILE: &str = "FullReference.html";
+
+pub const TITLE: &str = "Arucas Reference";
+
+// FullReference.md: the contents, every class, extension, global and type alias and the glossary in one page
+// that only links within itself, for printing or shipping as offline help. Rendered with the
+// combined layout, so links to classes and extensions are fragments of the page
+pub fn reference(parser: &DocParser) -> String {
+    format!("# {}\n\n{}", TITLE, body(parser))
+}
+
+// The reference without its title, for pages that have one of their own
+pub fn body(parser: &DocParser) -> String {
+    let link = |extension: bool, name: &str| parser.link(extension, name, None);
+    let classes = group_by_category(parser.classes());
+    let extensions = parser.extensions();
+    let globals = parser.globals();
+    let has_aliases = !parser.type_aliases().is_empty();
+
+    let mut md = String::from("<a id=\"contents\"></a>\n\n## Contents\n\n");
+    md.push_str("- [Classes](#classes)\n");
+    // Classes are only listed under their categories when there is more than one
+    let nested = classes.len() > 1;
+    for (title, classes) in &classes {
+        if nested {
+            md.push_str(&format!("  - {}\n", title));
+        }
+        let indent = if nested { "    " } else { "  " };
+        for class in classes {
+            md.push_str(&format!("{}- [{}]({})\n", indent, class.name, link(false, &class.name)));
+        }
+    }
+    md.push_str("- [Extensions](#extensions)\n");
+    for (name, _) in &extensions {
+        md.push_str(&format!("  - [{}]({})\n", name, link(true, name)));
+    }
+    if !globals.is_empty() {
+        md.push_str(&format!("- [{}](#globals)\n", parser.options.locale.globals));
+    }
+    if has_aliases {
+        md.push_str("- [Type aliases](#type-aliases)\n");
+    }
+    md.push_str("- [Glossary](#glossary)\n");
+
+    // Anchors are unique across the whole page, not just each section
+    let mut used = HashMap::new();
+    md.push_str("\n<a id=\"classes\"></a>\n\n# Classes\n");
+    for class in classes.into_iter().flat_map(|(_, classes)| classes) {
+        md.push('\n');
+        md.push_str(&demote(&parser.render_class(class, &mut used)));
+    }
+
+    md.push_str("\n<a id=\"extensions\"></a>\n\n# Extensions\n");
+    for (name, functions) in &extensions {
+        md.push('\n');
+        md.push_str(&parser.render_extension(name, functions, &mut used));
+    }
+
+    if !globals.is_empty() {
+        md.push_str("\n<a id=\"globals\"></a>\n\n");
+        md.push_str(&parser.render_globals(&globals, &mut used));
+    }
+
+    if has_aliases {
+        md.push_str("\n<a id=\"type-aliases\"></a>\n\n");
+        md.push_str(&aliases::aliases(parser));
+    }
+
+    md.push_str("\n<a id=\"glossary\"></a>\n\n");
+    md.push_str(&glossary::glossary(parser, &link));
+    md
+}
+
+// FullReference.html: the same page as HTML with its stylesheet inline, so it is a single file
+pub fn html(parser: &DocParser) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+        <style>\n{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        html::escape(TITLE), html::STYLESHEET, html::from_markdown(&reference(parser))
+    )
+}
+
+// Class pages start at `#`, in the reference they sit under the Classes heading
+fn demote(md: &str) -> String {
+    let mut code = false;
+    let mut demoted = String::with_capacity(md.len() + 64);
+    for line in md.split_inclusive('\n') {
+        if line.starts_with("```") {
+            code = !code;
+        } else if !code && html::heading(line.trim_end()).is_some_and(|(level, _)| level < 6) {
+            demoted.push('#');
+        }
+        demoted.push_str(line);
+    }
+    demoted
+}
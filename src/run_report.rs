@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use serde_json::{json, Value};
+use crate::doc_parser::{Class, DocParser};
+
+// The number of each kind of item in the document
+pub fn counts(parser: &DocParser) -> Value {
+    let classes = parser.classes();
+    let extensions = parser.extensions();
+    let count = |items: fn(&Class) -> usize| classes.iter().map(items).sum::<usize>();
+    json!({
+        "classes": classes.len(),
+        "static_members": count(|class| class.static_members.as_ref().map_or(0, Vec::len)),
+        "members": count(|class| class.members.as_ref().map_or(0, Vec::len)),
+        "constructors": count(|class| class.constructors.as_ref().map_or(0, Vec::len)),
+        "methods": count(|class| class.methods.as_ref().map_or(0, Vec::len)),
+        "static_methods": count(|class| class.static_methods.as_ref().map_or(0, Vec::len)),
+        "extensions": extensions.len(),
+        "functions": extensions.iter().map(|(_, functions)| functions.len()).sum::<usize>()
+    })
+}
+
+// Functions left out of the output for having no examples
+pub fn without_examples(parser: &DocParser) -> Vec<String> {
+    let mut skipped = Vec::new();
+    for class in parser.classes() {
+        for function in class.methods.iter().chain(&class.static_methods).flatten().filter(|function| function.examples.is_none()) {
+            skipped.push(format!("{}.{}", class.name, function.name));
+        }
+    }
+    for (name, functions) in parser.extensions() {
+        for function in functions.iter().filter(|function| function.examples.is_none()) {
+            skipped.push(format!("{}.{}", name, function.name));
+        }
+    }
+    skipped
+}
+
+// The files at or under the paths that were modified since the given time, with their sizes
+pub fn written(paths: &[PathBuf], since: SystemTime) -> Vec<(PathBuf, u64)> {
+    let mut files = Vec::new();
+    for path in paths {
+        add_written(path, since, &mut files);
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+fn add_written(path: &Path, since: SystemTime, files: &mut Vec<(PathBuf, u64)>) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+            add_written(&entry.path(), since, files);
+        }
+    } else if metadata.modified().is_ok_and(|modified| modified >= since) {
+        files.push((path.to_path_buf(), metadata.len()));
+    }
+}
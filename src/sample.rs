@@ -0,0 +1,164 @@
+use serde_json::{json, Map, Value};
+use crate::migration;
+
+// Generates synthetic documentation of the given size, the same count always
+// produces the same document so timings can be compared between runs
+pub fn generate(classes: usize) -> Value {
+    let mut class_map = Map::new();
+    for i in 0..classes {
+        let name = format!("Sample{}", i);
+        class_map.insert(name.clone(), class(&name, i));
+    }
+
+    let mut extension_map = Map::new();
+    for i in 0..classes.div_ceil(10) {
+        let functions: Vec<Value> = (0..8).map(|j| function(&format!("sample{}_{}", i, j), j % 4)).collect();
+        extension_map.insert(format!("SampleExtension{}", i), Value::Array(functions));
+    }
+
+    json!({
+        "version": migration::CURRENT_VERSION,
+        "classes": class_map,
+        "extensions": extension_map
+    })
+}
+
+fn class(name: &str, index: usize) -> Value {
+    let members: Vec<Value> = (0..3usize).map(|i| json!({
+        "name": format!("field{}", i),
+        "assignable": i.is_multiple_of(2),
+        "desc": [format!("This is field {} of {}.", i, name)],
+        "type": "String",
+        "examples": [format!("instance.field{};", i)]
+    })).collect();
+
+    json!({
+        "name": name,
+        "desc": [format!("This is the generated class {}.", name), "It exists only to measure performance."],
+        "import": if index.is_multiple_of(2) { json!({ "path": format!("sample.Module{}", index / 100) }) } else { Value::Null },
+        "static_members": [{
+            "name": "INSTANCE",
+            "assignable": false,
+            "desc": ["A shared instance of the class."],
+            "type": name,
+            "examples": [format!("{}.INSTANCE;", name)]
+        }],
+        "members": members,
+        "constructors": [{
+            "desc": [format!("Creates a new {}.", name)],
+            "params": [{ "name": "value", "type": "Object", "desc": "The initial value." }],
+            "examples": [format!("new {}(10);", name)]
+        }],
+        "methods": (0..12).map(|i| function(&format!("method{}", i), i % 4)).collect::<Vec<Value>>(),
+        "static_methods": (0..4).map(|i| function(&format!("create{}", i), i % 4)).collect::<Vec<Value>>()
+    })
+}
+
+fn function(name: &str, params: usize) -> Value {
+    let params: Vec<Value> = (0..params).map(|i| json!({
+        "name": format!("arg{}", i),
+        "type": "Number",
+        "desc": format!("The argument number {}.", i)
+    })).collect();
+    let arguments: Vec<String> = (0..params.len()).map(|i| i.to_string()).collect();
+
+    json!({
+        "name": name,
+        "desc": [format!("Calls {} with the given arguments.", name)],
+        "params": params,
+        "returns": { "type": "Number", "desc": "The result of the call." },
+        "examples": [format!("result = {}({});\n\tprint(result);", name, arguments.join(", "))]
+    })
+}
+
+// A small hand-written document using every part of the layout, for those writing
+// their own exporter to start from and to try the converter without Arucas
+pub fn scaffold() -> Value {
+    json!({
+        "version": migration::CURRENT_VERSION,
+        "authors": ["Your Name"],
+        "globals": [{
+            "name": "VERSION",
+            "type": "String",
+            "desc": ["The version of the library."],
+            "examples": ["print(VERSION);"]
+        }],
+        "type_aliases": {
+            "Degrees": { "type": "Number", "desc": ["An angle in degrees."] }
+        },
+        "classes": {
+            "Counter": {
+                "name": "Counter",
+                "desc": [
+                    "A counter that can be incremented and reset.",
+                    "",
+                    "Counters start at zero unless given a value."
+                ],
+                "import": { "path": "util.Counter" },
+                "category": "Utilities",
+                "tags": ["state"],
+                "see": ["Counter.increment", "countTo"],
+                "notes": ["Counters are not thread safe."],
+                "static_members": [{
+                    "name": "MAX",
+                    "assignable": false,
+                    "desc": ["The largest value a counter can hold."],
+                    "type": "Number",
+                    "value": 1000,
+                    "examples": ["Counter.MAX;"]
+                }],
+                "members": [{
+                    "name": "value",
+                    "assignable": true,
+                    "desc": ["The current value of the counter."],
+                    "type": "Number",
+                    "examples": ["counter.value = 5;"]
+                }],
+                "constructors": [
+                    {
+                        "desc": ["Creates a counter starting at zero."],
+                        "examples": ["new Counter();"]
+                    },
+                    {
+                        "desc": ["Creates a counter starting at the given value."],
+                        "params": [{ "name": "start", "type": "Number", "desc": "The value to start at." }],
+                        "examples": ["new Counter(10);"]
+                    }
+                ],
+                "methods": [
+                    {
+                        "name": "increment",
+                        "desc": ["Increases the value by one."],
+                        "returns": { "type": "Number", "desc": "The new value." },
+                        "throws": [{ "type": "Error", "message": "Counter is full", "condition": "the value is already `Counter.MAX`" }],
+                        "examples": [{ "code": "counter = new Counter();\nprint(counter.increment());", "output": "1" }]
+                    },
+                    {
+                        "name": "add",
+                        "deprecated": ["Use `<Counter>.increment()` instead."],
+                        "desc": ["Increases the value by the given amount."],
+                        "params": [{ "name": "amount", "type": "Number", "desc": "The amount to add." }],
+                        "examples": ["counter.add(2);"]
+                    }
+                ],
+                "static_methods": [{
+                    "name": "of",
+                    "experimental": true,
+                    "desc": ["Creates a counter from a string."],
+                    "params": [{ "name": "text", "type": "String", "desc": "The value as text." }],
+                    "returns": { "type": "Counter", "desc": "The new counter." },
+                    "throws": ["The text is not a number"],
+                    "examples": ["Counter.of('3');"]
+                }]
+            }
+        },
+        "extensions": {
+            "CounterExtension": [{
+                "name": "countTo",
+                "desc": ["Prints every number from one to the given number."],
+                "params": [{ "name": "end", "type": "Number", "desc": "The last number printed." }],
+                "examples": ["countTo(3);"]
+            }]
+        }
+    })
+}
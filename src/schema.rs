@@ -0,0 +1,330 @@
+use serde_json::{json, Value};
+
+// The contract between the Arucas doc exporter and this converter
+pub fn schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Arucas documentation",
+        "type": "object",
+        "required": ["classes", "extensions"],
+        "properties": {
+            "version": { "type": "number" },
+            "authors": { "$ref": "#/definitions/strings" },
+            "globals": { "$ref": "#/definitions/members" },
+            "values": { "$ref": "#/definitions/members" },
+            "type_aliases": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "required": ["type"],
+                    "properties": {
+                        "type": { "type": "string" },
+                        "desc": { "$ref": "#/definitions/strings" }
+                    }
+                }
+            },
+            "classes": {
+                "type": "object",
+                "additionalProperties": { "$ref": "#/definitions/class" }
+            },
+            "extension_weights": {
+                "type": "object",
+                "additionalProperties": { "type": "integer" }
+            },
+            "extensions": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "array",
+                    "items": { "$ref": "#/definitions/function" }
+                }
+            }
+        },
+        "definitions": {
+            "strings": {
+                "type": ["array", "null"],
+                "items": { "type": "string" }
+            },
+            "examples": {
+                "type": ["array", "null"],
+                "items": { "$ref": "#/definitions/example" }
+            },
+            "example": {
+                "type": ["string", "object"],
+                "required": ["code"],
+                "properties": {
+                    "code": { "type": "string" },
+                    "output": { "type": ["string", "null"] },
+                    "variant": { "type": ["string", "null"] }
+                }
+            },
+            "class": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": { "type": "string" },
+                    "desc": { "$ref": "#/definitions/strings" },
+                    "import_path": { "type": ["string", "null"] },
+                    "import": {
+                        "type": ["object", "null"],
+                        "required": ["path"],
+                        "properties": {
+                            "path": { "type": "string" },
+                            "name": { "type": ["string", "null"] }
+                        }
+                    },
+                    "category": { "type": ["string", "null"] },
+                    "module": { "type": ["string", "null"] },
+                    "weight": { "type": ["integer", "null"] },
+                    "tags": { "$ref": "#/definitions/strings" },
+                    "source": { "type": ["string", "null"] },
+                    "see": { "$ref": "#/definitions/strings" },
+                    "notes": { "$ref": "#/definitions/strings" },
+                    "warnings": { "$ref": "#/definitions/strings" },
+                    "authors": { "$ref": "#/definitions/strings" },
+                    "experimental": { "type": ["boolean", "null"] },
+                    "links": {
+                        "type": ["array", "null"],
+                        "items": {
+                            "type": "object",
+                            "required": ["title", "url"],
+                            "properties": {
+                                "title": { "type": "string" },
+                                "url": { "type": "string" }
+                            }
+                        }
+                    },
+                    "assets": { "$ref": "#/definitions/strings" },
+                    "static_members": { "$ref": "#/definitions/members" },
+                    "members": { "$ref": "#/definitions/members" },
+                    "constructors": {
+                        "type": ["array", "null"],
+                        "items": { "$ref": "#/definitions/constructor" }
+                    },
+                    "methods": { "$ref": "#/definitions/functions" },
+                    "static_methods": { "$ref": "#/definitions/functions" }
+                }
+            },
+            "functions": {
+                "type": ["array", "null"],
+                "items": { "$ref": "#/definitions/function" }
+            },
+            // Only the name is required, what the renderer can do without is left out of the page
+            "function": {
+                "type": "object",
+                "required": ["name"],
+                "description": "Left out of the page without examples. A missing description is left out of the function",
+                "properties": {
+                    "name": { "type": "string" },
+                    "is_arbitrary": { "type": ["boolean", "null"] },
+                    "experimental": { "type": ["boolean", "null"] },
+                    "deprecated": { "$ref": "#/definitions/strings" },
+                    "desc": { "$ref": "#/definitions/strings" },
+                    "params": { "$ref": "#/definitions/params" },
+                    "returns": {
+                        "type": ["object", "null"],
+                        "required": ["type", "desc"],
+                        "properties": {
+                            "type": { "type": "string" },
+                            "desc": { "type": "string" }
+                        }
+                    },
+                    "throws": {
+                        "type": ["array", "null"],
+                        "items": {
+                            "type": ["string", "object"],
+                            "required": ["type"],
+                            "properties": {
+                                "type": { "type": "string" },
+                                "message": { "type": ["string", "null"] },
+                                "condition": { "type": ["string", "null"] }
+                            }
+                        }
+                    },
+                    "assets": { "$ref": "#/definitions/strings" },
+                    "examples": { "$ref": "#/definitions/examples" },
+                    "source": { "type": ["string", "null"] },
+                    "see": { "$ref": "#/definitions/strings" },
+                    "notes": { "$ref": "#/definitions/strings" },
+                    "warnings": { "$ref": "#/definitions/strings" }
+                }
+            },
+            "constructor": {
+                "type": "object",
+                "required": ["desc", "examples"],
+                "properties": {
+                    "desc": { "type": "array", "items": { "type": "string" } },
+                    "params": { "$ref": "#/definitions/params" },
+                    "examples": { "type": "array", "items": { "$ref": "#/definitions/example" } }
+                }
+            },
+            "members": {
+                "type": ["array", "null"],
+                "items": {
+                    "type": "object",
+                    "required": ["name"],
+                    "description": "Left out of the page without assignable. A missing description, type or examples are left out of the member",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "assignable": { "type": ["boolean", "null"] },
+                        "desc": { "$ref": "#/definitions/strings" },
+                        "type": { "type": ["string", "null"] },
+                        "value": { "type": ["string", "number", "boolean", "null"] },
+                        "examples": { "$ref": "#/definitions/examples" }
+                    }
+                }
+            },
+            "params": {
+                "type": ["array", "null"],
+                "items": {
+                    "type": "object",
+                    "required": ["name", "type", "desc"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "type": { "type": "string" },
+                        "desc": { "type": "string" }
+                    }
+                }
+            }
+        }
+    })
+}
+
+// Returns every violation of the schema, each prefixed with its JSON path. Strict
+// validation also rejects fields the schema doesn't know, which would be ignored.
+pub fn validate(json: &Value, strict: bool) -> Vec<String> {
+    let root = schema();
+    let mut errors = Vec::new();
+    validate_value(&root, &root, json, "$", strict, &mut errors);
+    errors
+}
+
+// Entries that would collide once rendered: methods and constructors with the same name and arity,
+// members with the same name, and classes whose names only differ in case, whose split pages
+// would overwrite each other on case-insensitive file systems such as Windows
+pub fn duplicates(json: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut classes: Vec<(String, &str)> = Vec::new();
+    for (name, class) in json["classes"].as_object().into_iter().flatten() {
+        let path = format!("$.classes.{}", name);
+        if let Some((_, other)) = classes.iter().find(|(lower, _)| *lower == name.to_lowercase()) {
+            errors.push(format!("{}: class name collides with '{}' when case is ignored", path, other));
+        }
+        classes.push((name.to_lowercase(), name));
+
+        for key in ["members", "static_members"] {
+            duplicate_entries(&path, key, &class[key], |member| Some(format!("'{}'", member["name"].as_str()?)), &mut errors);
+        }
+        for key in ["methods", "static_methods"] {
+            duplicate_entries(&path, key, &class[key], signature, &mut errors);
+        }
+        duplicate_entries(&path, "constructors", &class["constructors"], |constructor| {
+            Some(format!("a constructor {}", parameters(constructor)))
+        }, &mut errors);
+    }
+
+    // Extensions are global functions, so they may collide across the groups they are listed in
+    let mut functions: Vec<(String, String)> = Vec::new();
+    for (name, group) in json["extensions"].as_object().into_iter().flatten() {
+        for (i, function) in group.as_array().into_iter().flatten().enumerate() {
+            let path = format!("$.extensions.{}[{}]", name, i);
+            let Some(key) = signature(function) else {
+                continue;
+            };
+            match functions.iter().find(|(other, _)| *other == key) {
+                Some((_, other)) => errors.push(format!("{}: duplicates {} at {}", path, key, other)),
+                None => functions.push((key, path))
+            }
+        }
+    }
+    errors
+}
+
+// Reports entries of the array sharing a key with an earlier one
+fn duplicate_entries(path: &str, key: &str, entries: &Value, identify: impl Fn(&Value) -> Option<String>, errors: &mut Vec<String>) {
+    let mut seen: Vec<(String, usize)> = Vec::new();
+    for (i, entry) in entries.as_array().into_iter().flatten().enumerate() {
+        let Some(identity) = identify(entry) else {
+            continue;
+        };
+        match seen.iter().find(|(other, _)| *other == identity) {
+            Some((_, other)) => errors.push(format!("{}.{}[{}]: duplicates {} at {}.{}[{}]", path, key, i, identity, path, key, other)),
+            None => seen.push((identity, i))
+        }
+    }
+}
+
+// The name with the number of parameters, arbitrary functions take any number
+fn signature(function: &Value) -> Option<String> {
+    Some(format!("'{}' {}", function["name"].as_str()?, parameters(function)))
+}
+
+fn parameters(function: &Value) -> String {
+    if function["is_arbitrary"].as_bool().unwrap_or(false) {
+        return "with any parameters".to_string();
+    }
+    match function["params"].as_array().map_or(0, Vec::len) {
+        1 => "with 1 parameter".to_string(),
+        count => format!("with {} parameters", count)
+    }
+}
+
+fn validate_value(root: &Value, schema: &Value, value: &Value, path: &str, strict: bool, errors: &mut Vec<String>) {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let name = reference.trim_start_matches("#/definitions/");
+        validate_value(root, &root["definitions"][name], value, path, strict, errors);
+        return;
+    }
+
+    let types: Vec<&str> = match &schema["type"] {
+        Value::String(type_name) => vec![type_name.as_str()],
+        Value::Array(type_names) => type_names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new()
+    };
+
+    let actual = type_of(value);
+    let integer = value.is_i64() && types.contains(&"integer");
+    if !types.is_empty() && !types.contains(&actual) && !integer {
+        errors.push(format!("{}: expected {} but found {}", path, types.join(" or "), actual));
+        return;
+    }
+
+    match value {
+        Value::Object(map) => {
+            if let Some(required) = schema["required"].as_array() {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(key) {
+                        errors.push(format!("{}: missing required field '{}'", path, key));
+                    }
+                }
+            }
+
+            for (key, child) in map {
+                let child_path = format!("{}.{}", path, key);
+                if let Some(property) = schema["properties"].get(key) {
+                    validate_value(root, property, child, &child_path, strict, errors);
+                } else if schema["additionalProperties"].is_object() {
+                    validate_value(root, &schema["additionalProperties"], child, &child_path, strict, errors);
+                } else if strict && schema["properties"].is_object() {
+                    errors.push(format!("{}: unknown field '{}'", child_path, key));
+                }
+            }
+        }
+        Value::Array(array) if schema["items"].is_object() => {
+            for (i, child) in array.iter().enumerate() {
+                validate_value(root, &schema["items"], child, &format!("{}[{}]", path, i), strict, errors);
+            }
+        }
+        _ => { }
+    }
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object"
+    }
+}
@@ -0,0 +1,23 @@
+use crate::diff::{self, Item};
+use crate::doc_parser::DocParser;
+
+// Items whose class, name or description contain the query, ignoring case. `kind`
+// and `class` narrow the results down to one kind of item or one class.
+pub fn search(parser: &DocParser, query: &str, kind: Option<&str>, class: Option<&str>) -> Vec<Item> {
+    let query = query.to_lowercase();
+    diff::items(parser).into_values()
+        .filter(|item| kind.is_none_or(|kind| item.kind == kind))
+        .filter(|item| class.is_none_or(|class| item.owner.eq_ignore_ascii_case(class)))
+        .filter(|item| {
+            item.owner.to_lowercase().contains(&query)
+                || item.signature.to_lowercase().contains(&query)
+                || item.desc.to_lowercase().contains(&query)
+        })
+        .collect()
+}
+
+// Where the item is rendered, the anchor is the one `--anchors` adds
+pub fn location(item: &Item) -> String {
+    let file = if item.kind == "function" { "Extensions.md" } else { "Classes.md" };
+    format!("{}#{}", file, DocParser::anchor_id(&item.anchor))
+}
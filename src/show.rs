@@ -0,0 +1,216 @@
+use crate::doc_parser::{Class, CodeExample, DocParser, Function, Member, Param};
+
+const BOLD: &str = "1";
+const CYAN: &str = "1;36";
+const YELLOW: &str = "33";
+const GREEN: &str = "32";
+const DIM: &str = "2";
+
+// Terminal output for `show`, colored with ANSI escapes when `color` is set
+struct Printer {
+    color: bool,
+    out: String
+}
+
+impl Printer {
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", style, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn line(&mut self, text: &str) {
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn heading(&mut self, signature: &str, kind: &str) {
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        let line = format!("{} {}", self.paint(CYAN, signature), self.paint(DIM, kind));
+        self.line(&line);
+    }
+
+    fn section(&mut self, title: &str) {
+        let line = self.paint(BOLD, title);
+        self.line(&line);
+    }
+
+    fn desc(&mut self, desc: &[String]) {
+        for line in desc {
+            self.line(&format!("  {}", line));
+        }
+    }
+
+    fn admonitions(&mut self, experimental: bool, warnings: &Option<Vec<String>>, notes: &Option<Vec<String>>) {
+        if experimental {
+            let line = format!("  {}", self.paint(YELLOW, "Experimental"));
+            self.line(&line);
+        }
+        for (lines, title) in [(warnings, "Warning:"), (notes, "Note:")] {
+            if let Some(lines) = lines.as_ref().filter(|lines| !lines.is_empty()) {
+                let line = format!("  {} {}", self.paint(YELLOW, title), lines.join(" "));
+                self.line(&line);
+            }
+        }
+    }
+
+    fn params(&mut self, params: &[Param]) {
+        if params.is_empty() {
+            return;
+        }
+        self.section("Parameters");
+        for param in params {
+            let line = format!("  {}: {} - {}", param.name, self.paint(YELLOW, &param.type_name), param.desc);
+            self.line(&line);
+        }
+    }
+
+    fn examples(&mut self, examples: &[CodeExample]) {
+        if examples.is_empty() {
+            return;
+        }
+        self.section(if examples.len() > 1 { "Examples" } else { "Example" });
+        for example in examples {
+            for code in example.code().trim_end().lines() {
+                let line = format!("  {}", self.paint(GREEN, &code.replace('\t', "    ")));
+                self.line(&line);
+            }
+            for output in example.output().unwrap_or_default().trim_end().lines() {
+                self.line(&format!("  > {}", output));
+            }
+        }
+    }
+
+    fn function(&mut self, prefix: Option<&str>, function: &Function, kind: &str) {
+        let params = function.params.as_deref().unwrap_or_default();
+        let names: Vec<&str> = params.iter().map(|param| param.name.as_str()).collect();
+        let name = match prefix {
+            Some(prefix) => format!("{}.{}", prefix, function.name),
+            None => function.name.clone()
+        };
+        self.heading(&format!("{}({})", name, names.join(", ")), kind);
+
+        if let Some(deprecation) = &function.deprecated {
+            let line = format!("  {} {}", self.paint(YELLOW, "Deprecated:"), deprecation.join(" "));
+            self.line(&line);
+        }
+        self.admonitions(function.experimental.unwrap_or(false), &function.warnings, &function.notes);
+        self.desc(function.desc.as_deref().unwrap_or_default());
+        self.params(params);
+
+        if let Some(returns) = &function.returns {
+            self.section("Returns");
+            let line = format!("  {} - {}", self.paint(YELLOW, &returns.type_name), returns.desc);
+            self.line(&line);
+        }
+        if let Some(throws) = &function.throws {
+            self.section("Throws");
+            for throw in throws {
+                self.line(&format!("  {}", throw.text()));
+            }
+        }
+        self.examples(function.examples.as_deref().unwrap_or_default());
+    }
+
+    fn member(&mut self, prefix: &str, member: &Member, kind: &str) {
+        self.heading(&format!("{}.{}", prefix, member.name), kind);
+        self.desc(member.desc.as_deref().unwrap_or_default());
+        let type_name = self.paint(YELLOW, member.type_name.as_deref().unwrap_or("Value"));
+        self.line(&format!("  Type: {}", type_name));
+        self.line(&format!("  Assignable: {}", member.assignable.unwrap_or(false)));
+        if let Some(value) = &member.value {
+            self.line(&format!("  Value: {}", value));
+        }
+        self.examples(member.examples.as_deref().unwrap_or_default());
+    }
+
+    fn class(&mut self, class: &Class) {
+        self.heading(&class.name, "class");
+        self.admonitions(class.experimental.unwrap_or(false), &class.warnings, &class.notes);
+        self.desc(class.desc.as_deref().unwrap_or_default());
+        if let Some((name, path)) = class.import() {
+            self.line(&format!("  import {} from {};", name, path));
+        }
+
+        let sections = [
+            ("Static members", names(class.static_members.iter().flatten().map(|member| &member.name))),
+            ("Members", names(class.members.iter().flatten().map(|member| &member.name))),
+            ("Methods", names(class.methods.iter().flatten().map(|method| &method.name))),
+            ("Static methods", names(class.static_methods.iter().flatten().map(|method| &method.name)))
+        ];
+        for (title, names) in sections {
+            if !names.is_empty() {
+                self.section(title);
+                self.line(&format!("  {}", names));
+            }
+        }
+    }
+}
+
+fn names<'a>(names: impl Iterator<Item = &'a String>) -> String {
+    let mut names: Vec<&str> = names.map(|name| name.as_str()).collect();
+    names.sort();
+    names.dedup();
+    names.join(", ")
+}
+
+// Everything documented under `Class`, `Class.name` or an extension function's name
+pub fn show(parser: &DocParser, query: &str, color: bool) -> Result<String, String> {
+    let mut printer = Printer { color, out: String::new() };
+    let classes = parser.classes();
+    let (owner, name) = match query.split_once('.') {
+        Some((owner, name)) => (owner, Some(name)),
+        None => (query, None)
+    };
+
+    if let Some(class) = classes.iter().find(|class| class.name == owner) {
+        let Some(name) = name else {
+            printer.class(class);
+            return Ok(printer.out);
+        };
+
+        let member_class = format!("<{}>", class.name);
+        for member in class.static_members.iter().flatten().filter(|member| member.name == name) {
+            printer.member(&class.name, member, "static member");
+        }
+        for member in class.members.iter().flatten().filter(|member| member.name == name) {
+            printer.member(&member_class, member, "member");
+        }
+        if name == "new" {
+            for constructor in class.constructors.iter().flatten() {
+                let params = constructor.params.as_deref().unwrap_or_default();
+                let names: Vec<&str> = params.iter().map(|param| param.name.as_str()).collect();
+                printer.heading(&format!("new {}({})", class.name, names.join(", ")), "constructor");
+                printer.desc(&constructor.desc);
+                printer.params(params);
+                printer.examples(&constructor.examples);
+            }
+        }
+        for method in class.methods.iter().flatten().filter(|method| method.name == name) {
+            printer.function(Some(&member_class), method, "method");
+        }
+        for method in class.static_methods.iter().flatten().filter(|method| method.name == name) {
+            printer.function(Some(&class.name), method, "static method");
+        }
+    }
+
+    for (extension, functions) in parser.extensions() {
+        let wanted = match name {
+            Some(name) if extension == owner => name,
+            None => owner,
+            Some(_) => continue
+        };
+        for function in functions.iter().filter(|function| function.name == wanted) {
+            printer.function(None, function, "function");
+        }
+    }
+
+    if printer.out.is_empty() {
+        return Err(format!("No documentation for '{}'", query));
+    }
+    Ok(printer.out)
+}
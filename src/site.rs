@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use serde_json::{json, Value};
+use crate::date;
+use crate::diff;
+use crate::doc_parser::DocParser;
+use crate::html;
+use crate::output::{create_dir, group_by_category, write_file};
+
+pub const DIRECTORY: &str = "html";
+
+// Both themes share one stylesheet, the `data-theme` attribute of the page picks one
+const STYLESHEET: &str = ":root { --background: #ffffff; --text: #222222; --muted: #666666; --code: #f3f3f3; --link: #0b5cad; }
+[data-theme=\"dark\"] { --background: #1e1f22; --text: #dcdcdc; --muted: #9a9a9a; --code: #2b2d31; --link: #6cb6ff; }
+body { font-family: sans-serif; max-width: 60em; margin: 0 auto; padding: 1em; line-height: 1.5; background: var(--background); color: var(--text); }
+a { color: var(--link); }
+nav { display: flex; justify-content: space-between; align-items: center; border-bottom: 1px solid var(--muted); padding-bottom: 0.5em; }
+nav button { background: var(--code); color: var(--text); border: 1px solid var(--muted); border-radius: 4px; cursor: pointer; }
+code { background: var(--code); padding: 0 0.2em; border-radius: 3px; }
+pre { background: var(--code); padding: 0.8em; overflow-x: auto; border-radius: 4px; }
+pre code { padding: 0; }
+img { max-width: 100%; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid var(--muted); padding: 0.2em 0.6em; text-align: left; }
+blockquote { border-left: 4px solid var(--muted); margin: 1em 0; padding: 0 1em; }
+.code-title { margin: 0.5em 0 0; font-weight: bold; }
+.tabs { display: flex; flex-wrap: wrap; margin: 0.5em 0; }
+.tabs > input { display: none; }
+.tabs > label { padding: 0.2em 0.8em; cursor: pointer; border-bottom: 2px solid transparent; }
+.tabs > input:checked + label { border-bottom-color: var(--link); }
+.tabs > .tab { display: none; order: 1; width: 100%; }
+.tabs > input:checked + label + .tab { display: block; }
+h3 { margin-top: 1.8em; }
+";
+
+// Applies the theme picked last time before the page is drawn, the button switches it
+const SCRIPT: &str = "(function () {
+    var root = document.documentElement;
+    var stored = localStorage.getItem(\"arucas-theme\");
+    if (stored) {
+        root.setAttribute(\"data-theme\", stored);
+    }
+    document.addEventListener(\"DOMContentLoaded\", function () {
+        document.getElementById(\"theme-toggle\").addEventListener(\"click\", function () {
+            var theme = root.getAttribute(\"data-theme\") === \"dark\" ? \"light\" : \"dark\";
+            root.setAttribute(\"data-theme\", theme);
+            localStorage.setItem(\"arucas-theme\", theme);
+        });
+    });
+})();
+";
+
+// Ranks entries by how closely their name matches the query as a subsequence,
+// consecutive and leading matches count for more
+const SEARCH_SCRIPT: &str = "(function () {
+    function score(query, text) {
+        var matched = 0, last = -2, total = 0;
+        for (var i = 0; i < text.length && matched < query.length; i++) {
+            if (text[i] === query[matched]) {
+                total += last === i - 1 ? 3 : 1;
+                if (i === 0) {
+                    total += 2;
+                }
+                last = i;
+                matched++;
+            }
+        }
+        return matched === query.length ? total - text.length / 100 : -1;
+    }
+
+    document.addEventListener(\"DOMContentLoaded\", function () {
+        var input = document.getElementById(\"search\");
+        var results = document.getElementById(\"results\");
+        input.addEventListener(\"input\", function () {
+            var query = input.value.toLowerCase().replace(/\\s+/g, \"\");
+            results.innerHTML = \"\";
+            if (!query) {
+                return;
+            }
+            SEARCH_INDEX.map(function (entry) {
+                return { entry: entry, score: score(query, entry.name.toLowerCase()) };
+            }).filter(function (result) {
+                return result.score >= 0;
+            }).sort(function (a, b) {
+                return b.score - a.score;
+            }).slice(0, 50).forEach(function (result) {
+                var item = document.createElement(\"li\");
+                var link = document.createElement(\"a\");
+                link.href = result.entry.url;
+                link.textContent = result.entry.name;
+                item.appendChild(link);
+                item.appendChild(document.createTextNode(\" \" + result.entry.kind));
+                results.appendChild(item);
+            });
+        });
+        input.focus();
+    });
+})();
+";
+
+const SEARCH_BODY: &str = "<h1>Search</h1>
+<input id=\"search\" type=\"search\" placeholder=\"Class or method name\" autocomplete=\"off\">
+<ul id=\"results\"></ul>
+<script src=\"search-index.js\"></script>
+<script src=\"search.js\"></script>
+";
+
+// `light` and `dark` pick the theme pages start with, anything else is a path to
+// a stylesheet used instead of the bundled one
+pub struct Theme {
+    pub default: &'static str,
+    pub stylesheet: String
+}
+
+impl Theme {
+    pub fn load(name: Option<&str>) -> Result<Theme, String> {
+        match name {
+            None | Some("light") => Ok(Theme { default: "light", stylesheet: STYLESHEET.to_string() }),
+            Some("dark") => Ok(Theme { default: "dark", stylesheet: STYLESHEET.to_string() }),
+            Some(path) => {
+                let stylesheet = fs::read_to_string(path).map_err(|error| format!("Could not read theme {}: {}", path, error))?;
+                Ok(Theme { default: "light", stylesheet })
+            }
+        }
+    }
+}
+
+// Writes a static site with a page per class and extension and an index linking
+// them, returns the number of pages. With a base URL every page names its canonical
+// URL and sitemap.xml lists them all.
+pub fn write(parser: &DocParser, path: &Path, theme: &Theme, base_url: Option<&str>, tabs: bool) -> Result<usize, String> {
+    let flavour = html::Flavour { tabs, ..html::HTML };
+    for directory in ["classes", "extensions"] {
+        create_dir(&path.join(directory))?;
+    }
+
+    write_file(&path.join("style.css"), &theme.stylesheet)?;
+    write_file(&path.join("theme.js"), SCRIPT)?;
+
+    let mut site = Site { path, theme, base_url: base_url.map(|url| url.trim_end_matches('/')), pages: Vec::new() };
+    let mut index = String::from("# Arucas\n");
+
+    for (title, classes) in group_by_category(parser.classes()) {
+        index.push_str(&format!("\n## {}\n\n", title));
+        for class in classes {
+            let file = format!("classes/{}.html", parser.file_name(&class.name));
+            index.push_str(&format!("- [{}]({})\n", class.name, file));
+
+            let title = class.name.clone();
+            let md = parser.render_class(class, &mut HashMap::new());
+            site.page(&file, &title, &html::convert(&md, &flavour))?;
+        }
+    }
+
+    index.push_str("\n## Extensions\n\n");
+    for (name, functions) in parser.extensions() {
+        let file = format!("extensions/{}.html", parser.file_name(&name));
+        index.push_str(&format!("- [{}]({})\n", name, file));
+
+        let md = parser.render_extension(&name, &functions, &mut HashMap::new());
+        site.page(&file, &name, &html::convert(&md, &flavour))?;
+    }
+
+    site.page("index.html", "Arucas", &html::from_markdown(&index))?;
+    write_search(parser, &mut site)?;
+    site.write_sitemap()?;
+    Ok(site.pages.len())
+}
+
+// The search page, with the index as JSON for other tools and as a script the page
+// loads since browsers don't let pages opened from disk fetch files
+fn write_search(parser: &DocParser, site: &mut Site) -> Result<(), String> {
+    let entries: Vec<Value> = diff::items(parser).into_values().map(|item| {
+        let directory = if item.kind == "function" { "extensions" } else { "classes" };
+        let url = format!("{}/{}.html#{}", directory, parser.file_name(&item.owner), DocParser::anchor_id(&item.anchor));
+        json!({ "name": parser.signature(&item.signature), "kind": item.kind, "url": url })
+    }).collect();
+
+    let index = serde_json::to_string(&entries).unwrap();
+    write_file(&site.path.join("search-index.json"), &index)?;
+    write_file(&site.path.join("search-index.js"), &format!("var SEARCH_INDEX = {};\n", index))?;
+    write_file(&site.path.join("search.js"), SEARCH_SCRIPT)?;
+    site.page("search.html", "Search", SEARCH_BODY)
+}
+
+struct Site<'a> {
+    path: &'a Path,
+    theme: &'a Theme,
+    base_url: Option<&'a str>,
+    // Every page written so far, relative to the top of the site
+    pages: Vec<String>
+}
+
+impl Site<'_> {
+    fn page(&mut self, file: &str, title: &str, body: &str) -> Result<(), String> {
+        let root = "../".repeat(file.matches('/').count());
+        let canonical = match self.base_url {
+            Some(base_url) => format!("<link rel=\"canonical\" href=\"{}/{}\">\n", html::escape(base_url), html::escape(file)),
+            None => String::new()
+        };
+
+        let content = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\" data-theme=\"{theme}\">\n<head>\n<meta charset=\"utf-8\">\n\
+            <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{title}</title>\n{canonical}\
+            <link rel=\"stylesheet\" href=\"{root}style.css\">\n<script src=\"{root}theme.js\"></script>\n</head>\n<body>\n\
+            <nav><a href=\"{root}index.html\">Arucas</a><a href=\"{root}search.html\">Search</a>\
+            <button id=\"theme-toggle\" type=\"button\">Toggle theme</button></nav>\n{body}</body>\n</html>\n",
+            theme = self.theme.default, title = html::escape(title), canonical = canonical, root = root, body = body
+        );
+        write_file(&self.path.join(file), &content)?;
+        self.pages.push(file.to_string());
+        Ok(())
+    }
+
+    // Sitemaps need absolute URLs, so there is only one with a base URL
+    fn write_sitemap(&self) -> Result<(), String> {
+        let Some(base_url) = self.base_url else {
+            return Ok(());
+        };
+
+        let today = date::today();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+        for page in &self.pages {
+            xml.push_str(&format!(
+                "  <url><loc>{}/{}</loc><lastmod>{}</lastmod></url>\n",
+                html::escape(base_url), html::escape(page), today
+            ));
+        }
+        xml.push_str("</urlset>\n");
+        write_file(&self.path.join("sitemap.xml"), &xml)
+    }
+}
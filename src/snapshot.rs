@@ -0,0 +1,89 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+use crate::date;
+use crate::output::write_file;
+
+pub const STATE_DIR: &str = ".arucas-md";
+
+// FNV-1a, enough to tell documentation versions apart
+pub fn hash(content: &str) -> String {
+    let mut hash = Fnv::default();
+    hash.update(content.as_bytes());
+    hash.finish()
+}
+
+// The state of an FNV-1a hash, for content hashed a piece at a time
+pub struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Fnv {
+        Fnv(0xcbf29ce484222325)
+    }
+}
+
+impl Fnv {
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    pub fn finish(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+fn snapshot_dir() -> PathBuf {
+    Path::new(STATE_DIR).join("snapshots")
+}
+
+// Archived snapshots, oldest first
+pub fn list() -> Vec<PathBuf> {
+    let mut snapshots: Vec<PathBuf> = match fs::read_dir(snapshot_dir()) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect(),
+        Err(_) => Vec::new()
+    };
+    snapshots.retain(|path| path.extension().is_some_and(|extension| extension == "json"));
+    snapshots.sort();
+    snapshots
+}
+
+pub fn hash_of(path: &Path) -> Option<&str> {
+    path.file_stem()?.to_str()?.split('-').nth(1)
+}
+
+// When the snapshot was recorded, in seconds since the unix epoch
+pub fn seconds_of(path: &Path) -> Option<u64> {
+    path.file_stem()?.to_str()?.split('-').next()?.parse().ok()
+}
+
+// The most recent snapshot that differs from the given documentation
+pub fn previous(json: &Value) -> Option<PathBuf> {
+    let current = hash(&serde_json::to_string(json).unwrap());
+    list().into_iter().rev().find(|path| hash_of(path) != Some(current.as_str()))
+}
+
+// Stores the normalized documentation unless the latest snapshot is identical
+pub fn archive(json: &Value) -> Result<Option<PathBuf>, String> {
+    let content = serde_json::to_string(json).unwrap();
+    let hash = hash(&content);
+
+    if list().last().is_some_and(|latest| hash_of(latest) == Some(hash.as_str())) {
+        return Ok(None);
+    }
+
+    let directory = snapshot_dir();
+    fs::create_dir_all(&directory).map_err(|error| format!("Could not create {}: {}", directory.display(), error))?;
+    let path = directory.join(format!("{:020}-{}.json", date::now_seconds(), hash));
+    write_file(&path, &content)?;
+    Ok(Some(path))
+}
+
+// Deletes all but the newest snapshots, returning how many were removed
+pub fn prune(keep: usize) -> usize {
+    let snapshots = list();
+    let remove = snapshots.len().saturating_sub(keep);
+    snapshots.iter().take(remove).filter(|path| fs::remove_file(path).is_ok()).count()
+}
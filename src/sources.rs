@@ -0,0 +1,74 @@
+use serde_json::Value;
+
+// Sets the `source` of the items named in the mapping: `Class` for a class,
+// `Class.name` for its methods and an extension function's name for the function.
+// Returns the names that matched nothing.
+pub fn apply(json: &mut Value, mapping: &Value) -> Vec<String> {
+    let mut unmatched = Vec::new();
+    let Some(mapping) = mapping.as_object() else {
+        return unmatched;
+    };
+
+    for (name, source) in mapping {
+        let matched = match name.split_once('.') {
+            Some((class, method)) => match json["classes"].get_mut(class) {
+                Some(class) => {
+                    let mut matched = false;
+                    for section in ["methods", "static_methods"] {
+                        if let Some(functions) = class.get_mut(section) {
+                            matched |= set_source(functions, method, source);
+                        }
+                    }
+                    matched
+                }
+                None => false
+            },
+            None if json["classes"].get(name).is_some() => {
+                json["classes"][name]["source"] = source.clone();
+                true
+            }
+            None => {
+                let mut matched = false;
+                if let Some(extensions) = json["extensions"].as_object_mut() {
+                    for functions in extensions.values_mut() {
+                        matched |= set_source(functions, name, source);
+                    }
+                }
+                matched
+            }
+        };
+
+        if !matched {
+            unmatched.push(name.clone());
+        }
+    }
+
+    unmatched
+}
+
+fn set_source(functions: &mut Value, name: &str, source: &Value) -> bool {
+    let mut matched = false;
+    for function in functions.as_array_mut().into_iter().flatten() {
+        if function["name"] == name {
+            function["source"] = source.clone();
+            matched = true;
+        }
+    }
+    matched
+}
+
+// A link to the source, `path:line` becomes a link to the line under `base`
+pub fn url(source: &str, base: Option<&str>) -> String {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        return source.to_string();
+    }
+
+    let link = match source.rsplit_once(':') {
+        Some((path, line)) if !line.is_empty() && line.chars().all(|c| c.is_ascii_digit()) => format!("{}#L{}", path, line),
+        _ => source.to_string()
+    };
+    match base {
+        Some(base) => format!("{}/{}", base.trim_end_matches('/'), link.trim_start_matches('/')),
+        None => link
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+use std::fs;
+use crate::config::Spelling;
+use crate::doc_parser::DocParser;
+
+// Every word known to be spelled correctly, in lowercase
+pub struct Words {
+    known: HashSet<String>
+}
+
+impl Words {
+    // The word list, the project dictionary and the names of everything documented
+    pub fn load(spelling: &Spelling, parser: &DocParser) -> Result<Words, String> {
+        let mut known = HashSet::new();
+        let mut paths = vec![spelling.word_list.as_str()];
+        paths.extend(spelling.dictionary.as_deref());
+        for path in paths {
+            let content = fs::read_to_string(path).map_err(|error| format!("Could not read word list '{}': {}", path, error))?;
+            known.extend(content.lines().map(|line| line.trim().to_lowercase()).filter(|word| !word.is_empty() && !word.starts_with('#')));
+        }
+
+        for class in parser.classes() {
+            known.insert(class.name.to_lowercase());
+            let members = class.static_members.iter().chain(class.members.iter()).flatten();
+            known.extend(members.map(|member| member.name.to_lowercase()));
+            let functions = class.methods.iter().chain(class.static_methods.iter()).flatten();
+            for function in functions {
+                known.insert(function.name.to_lowercase());
+                known.extend(function.params.iter().flatten().map(|param| param.name.to_lowercase()));
+            }
+        }
+        for (_, functions) in parser.extensions() {
+            known.extend(functions.iter().map(|function| function.name.to_lowercase()));
+        }
+        known.extend(parser.type_aliases().iter().map(|alias| alias.name.to_lowercase()));
+        Ok(Words { known })
+    }
+
+    // The words of the text that aren't known, once each. Code, link targets, and words
+    // that look like identifiers, such as `camelCase`, `ACRONYMS` or ones with digits, are skipped.
+    pub fn misspelled(&self, lines: &[String]) -> Vec<String> {
+        let mut misspelled: Vec<String> = Vec::new();
+        let mut code = false;
+        for line in lines {
+            if line.trim_start().starts_with("```") {
+                code = !code;
+                continue;
+            }
+            if code {
+                continue;
+            }
+
+            for word in words(&prose(line)) {
+                let lower = word.to_lowercase();
+                let stem = lower.strip_suffix("'s").unwrap_or(&lower);
+                if !self.known.contains(&lower) && !self.known.contains(stem) && !misspelled.contains(&word) {
+                    misspelled.push(word);
+                }
+            }
+        }
+        misspelled
+    }
+}
+
+// The line without inline code, link targets and URLs
+fn prose(line: &str) -> String {
+    let mut prose = String::new();
+    for (i, part) in line.split('`').enumerate() {
+        if i % 2 == 0 {
+            prose.push_str(part);
+            prose.push(' ');
+        }
+    }
+
+    let mut rest = prose.as_str();
+    let mut text = String::new();
+    while let Some(start) = rest.find("](") {
+        text.push_str(&rest[..start]);
+        rest = rest[start..].split_once(')').map_or("", |(_, after)| after);
+    }
+    text.push_str(rest);
+
+    text.split_whitespace().filter(|word| !word.contains("://")).collect::<Vec<&str>>().join(" ")
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '_')
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| word.chars().count() > 1)
+        .filter(|word| word.chars().all(|c| c.is_alphabetic() || c == '\''))
+        .filter(|word| !word.chars().skip(1).any(char::is_uppercase))
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,176 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+use crate::diff;
+use crate::doc_parser::DocParser;
+use crate::keep;
+use crate::log;
+use crate::metadata;
+use crate::front_matter;
+use crate::output::{create_dir, normalize, write_file};
+use crate::progress::Progress;
+use crate::snapshot::{self, STATE_DIR};
+
+// Hashes of the source of every split file, keyed by the file's path. Doubles as the manifest
+// of the files generated, for pruning those no longer written
+const HASHES_FILE: &str = "hashes.json";
+
+#[derive(Default)]
+pub struct Outcome {
+    pub regenerated: usize,
+    pub skipped: usize,
+    // Files generated by an earlier run for classes and extensions that are gone
+    pub stale: usize,
+    pub pruned: usize
+}
+
+// Writes every class and extension to its own file, skipping files whose source, render
+// settings and the rest of the document they link into are unchanged since the last run.
+// With `prune`, files an earlier run generated that aren't written any more are deleted
+pub fn write(parser: &DocParser, directory: &Path, prune: bool) -> Result<Outcome, String> {
+    let state = Path::new(STATE_DIR).join(HASHES_FILE);
+    let mut hashes: BTreeMap<String, String> = fs::read_to_string(&state).ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let mut outcome = Outcome::default();
+    let mut written = BTreeSet::new();
+    let settings = &settings(parser);
+
+    // Module to the classes in it, each module gets an index page
+    let mut modules: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let template = parser.options.front_matter.as_deref();
+    let classes = parser.classes();
+    let extensions = parser.extensions();
+    let progress = Progress::new("Writing pages", classes.len() + extensions.len());
+    for (i, class) in classes.into_iter().enumerate() {
+        progress.advance();
+        let class_directory = parser.class_directory(&class.name);
+        if class_directory != "classes" {
+            modules.entry(class_directory.clone()).or_default().push(class.name.clone());
+        }
+
+        let path = directory.join(parser.page_path(false, &class.name));
+        let front_matter = front_matter::page(template, &class.name, i + 1, "classes");
+        let hash = source_hash(&parser.json()["classes"][&class.name], settings, &front_matter);
+        written.insert(path.display().to_string());
+        if is_current(&hashes, &path, &hash) {
+            outcome.skipped += 1;
+            continue;
+        }
+
+        let source = format!("class:{}", class.name);
+        let json = &parser.json()["classes"][&class.name];
+        let md = front_matter + &parser.render_class(class, &mut HashMap::new());
+        create_dir(path.parent().unwrap_or(directory))?;
+        write_file(&path, &finish_page(parser, &path, &md, json, &source))?;
+        hashes.insert(path.display().to_string(), hash);
+        outcome.regenerated += 1;
+    }
+
+    for (i, (name, functions)) in extensions.into_iter().enumerate() {
+        progress.advance();
+        let path = directory.join(parser.page_path(true, &name));
+        let front_matter = front_matter::page(template, &name, i + 1, "extensions");
+        let hash = source_hash(&parser.json()["extensions"][&name], settings, &front_matter);
+        written.insert(path.display().to_string());
+        if is_current(&hashes, &path, &hash) {
+            outcome.skipped += 1;
+            continue;
+        }
+
+        let md = front_matter + &parser.render_extension(&name, &functions, &mut HashMap::new());
+        create_dir(path.parent().unwrap_or(directory))?;
+        let json = &parser.json()["extensions"][&name];
+        write_file(&path, &finish_page(parser, &path, &md, json, &format!("extension:{}", name)))?;
+        hashes.insert(path.display().to_string(), hash);
+        outcome.regenerated += 1;
+    }
+
+    for (module, classes) in modules {
+        let mut index = format!("# {}\n\n", module);
+        for class in classes {
+            index.push_str(&format!("- [{}]({}.md)\n", class, parser.file_name(&class)));
+        }
+        let path = directory.join(&module).join("README.md");
+        write_file(&path, &normalize(&index, parser.options.newlines))?;
+        hashes.insert(path.display().to_string(), snapshot::hash(&index));
+        written.insert(path.display().to_string());
+    }
+
+    // Only files of this output directory, other languages have their own
+    let stale: Vec<String> = hashes.keys()
+        .filter(|file| !written.contains(*file) && Path::new(file).starts_with(directory))
+        .cloned()
+        .collect();
+    outcome.stale = stale.len();
+    if prune {
+        for file in stale {
+            let path = PathBuf::from(&file);
+            if path.exists() {
+                fs::remove_file(&path).map_err(|error| format!("Could not delete {}: {}", path.display(), error))?;
+                log::debug(&format!("Pruned {}", path.display()));
+                remove_empty_parents(&path, directory);
+            }
+            hashes.remove(&file);
+            outcome.pruned += 1;
+        }
+    }
+
+    create_dir(Path::new(STATE_DIR))?;
+    write_file(&state, &serde_json::to_string_pretty(&hashes).unwrap())?;
+    Ok(outcome)
+}
+
+// Directories left empty by pruning go too, up to the output directory
+fn remove_empty_parents(path: &Path, directory: &Path) {
+    for parent in path.ancestors().skip(1).take_while(|parent| *parent != directory) {
+        if fs::remove_dir(parent).is_err() {
+            break;
+        }
+    }
+}
+
+// The normalized page with the regions to keep from the page at the path, ending with the metadata
+// comment if asked for. The input of a split page is its own class or extension, as unchanged
+// pages aren't rewritten when others change
+fn finish_page(parser: &DocParser, path: &Path, md: &str, source: &Value, name: &str) -> String {
+    let page = keep::merge(&normalize(md, parser.options.newlines), &keep::read(path));
+    match &parser.options.metadata {
+        Some(options) => {
+            let input = snapshot::hash(&serde_json::to_string(source).unwrap());
+            metadata::append(options, page, &input, name, parser.options.newlines)
+        }
+        None => page
+    }
+}
+
+// What every page depends on besides its own source: the version and render options, and
+// what it may link to. Links depend on which classes and items are documented, their anchors
+// and headings, where their pages are and the type aliases, so a change to any of them
+// regenerates every page
+fn settings(parser: &DocParser) -> String {
+    let mut settings = format!("{}\n{}\n", env!("CARGO_PKG_VERSION"), parser.options.fingerprint());
+    for (key, item) in diff::items(parser) {
+        settings.push_str(&format!("{} {} {} {} {}\n", key, item.signature, item.anchor, item.deprecated, item.experimental));
+    }
+    for class in parser.classes() {
+        settings.push_str(&parser.page_path(false, &class.name));
+        settings.push('\n');
+    }
+    for (name, _) in parser.extensions() {
+        settings.push_str(&parser.page_path(true, &name));
+        settings.push('\n');
+    }
+    settings.push_str(&serde_json::to_string(&parser.json()["type_aliases"]).unwrap());
+    snapshot::hash(&settings)
+}
+
+fn source_hash(source: &Value, settings: &str, front_matter: &str) -> String {
+    snapshot::hash(&format!("{}\n{}\n{}", settings, front_matter, serde_json::to_string(source).unwrap()))
+}
+
+// A file is only skipped if it still exists, in case it was deleted by hand
+fn is_current(hashes: &BTreeMap<String, String>, path: &Path, hash: &str) -> bool {
+    path.exists() && hashes.get(&path.display().to_string()).is_some_and(|previous| previous == hash)
+}
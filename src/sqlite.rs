@@ -0,0 +1,278 @@
+use std::io;
+use std::path::Path;
+use crate::output::replace;
+
+const PAGE_SIZE: usize = 4096;
+const HEADER_SIZE: usize = 100;
+
+// Writes a SQLite database holding a single table of text columns, which is all
+// search indexes like the one Dash and Zeal read need. Rows get rowids counting up
+// from 1, the first column of the table is that rowid.
+pub fn write_table(path: &Path, table: &str, columns: &[&str], rows: &[Vec<String>]) -> io::Result<()> {
+    let mut pages: Vec<Vec<u8>> = Vec::new();
+
+    // Leaves hold as many rows as fit, each level above points to the level below
+    let mut level: Vec<(u32, u64)> = Vec::new();
+    let mut cells = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        let rowid = i as u64 + 1;
+        let mut fields = vec![Field::Null];
+        fields.extend(row.iter().map(|value| Field::Text(value)));
+        let payload = record(&fields);
+        if payload.len() > PAGE_SIZE - 35 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("row {} is too large for the index", rowid)));
+        }
+
+        let mut cell = Vec::new();
+        varint(payload.len() as u64, &mut cell);
+        varint(rowid, &mut cell);
+        cell.extend(payload);
+
+        if !fits(8, &cells, &cell) {
+            pages.push(page(0x0d, 0, &cells, None));
+            level.push((pages.len() as u32 + 1, rowid - 1));
+            cells.clear();
+        }
+        cells.push(cell);
+    }
+    pages.push(page(0x0d, 0, &cells, None));
+    level.push((pages.len() as u32 + 1, rows.len() as u64));
+
+    while level.len() > 1 {
+        let mut parents = Vec::new();
+        let mut cells = Vec::new();
+        let mut children = level.into_iter().peekable();
+        while let Some((child, key)) = children.next() {
+            let mut cell = child.to_be_bytes().to_vec();
+            varint(key, &mut cell);
+
+            // The last child of a page is its right-most pointer rather than a cell
+            if children.peek().is_none() || !fits(12, &cells, &cell) {
+                pages.push(page(0x05, 0, &cells, Some(child)));
+                parents.push((pages.len() as u32 + 1, key));
+                cells.clear();
+            } else {
+                cells.push(cell);
+            }
+        }
+        level = parents;
+    }
+    let root = level[0].0;
+
+    let mut sql = format!("CREATE TABLE {}(id INTEGER PRIMARY KEY", table);
+    for column in columns {
+        sql.push_str(", ");
+        sql.push_str(column);
+        sql.push_str(" TEXT");
+    }
+    sql.push(')');
+
+    let schema = record(&[Field::Text("table"), Field::Text(table), Field::Text(table), Field::Integer(root), Field::Text(&sql)]);
+    let mut cell = Vec::new();
+    varint(schema.len() as u64, &mut cell);
+    varint(1, &mut cell);
+    cell.extend(schema);
+
+    let mut first = page(0x0d, HEADER_SIZE, &[cell], None);
+    first[..HEADER_SIZE].copy_from_slice(&header(pages.len() as u32 + 1));
+
+    let mut content = first;
+    for page in pages {
+        content.extend(page);
+    }
+    replace(path, content)
+}
+
+fn header(page_count: u32) -> [u8; HEADER_SIZE] {
+    let mut header = [0u8; HEADER_SIZE];
+    header[..16].copy_from_slice(b"SQLite format 3\0");
+    header[16..18].copy_from_slice(&(PAGE_SIZE as u16).to_be_bytes());
+    header[18] = 1;
+    header[19] = 1;
+    header[21] = 64;
+    header[22] = 32;
+    header[23] = 32;
+    header[24..28].copy_from_slice(&1u32.to_be_bytes());
+    header[28..32].copy_from_slice(&page_count.to_be_bytes());
+    header[40..44].copy_from_slice(&1u32.to_be_bytes());
+    header[44..48].copy_from_slice(&4u32.to_be_bytes());
+    header[56..60].copy_from_slice(&1u32.to_be_bytes());
+    header[92..96].copy_from_slice(&1u32.to_be_bytes());
+    header[96..100].copy_from_slice(&3045000u32.to_be_bytes());
+    header
+}
+
+fn fits(header_size: usize, cells: &[Vec<u8>], cell: &[u8]) -> bool {
+    let used: usize = cells.iter().map(|cell| cell.len() + 2).sum();
+    header_size + used + cell.len() + 2 <= PAGE_SIZE
+}
+
+// A b-tree page with its cells packed at the end, `offset` leaves room for the
+// database header on the first page
+fn page(kind: u8, offset: usize, cells: &[Vec<u8>], right: Option<u32>) -> Vec<u8> {
+    let mut page = vec![0u8; PAGE_SIZE];
+    let header_size = if right.is_some() { 12 } else { 8 };
+
+    let mut end = PAGE_SIZE;
+    for (i, cell) in cells.iter().enumerate() {
+        end -= cell.len();
+        page[end..end + cell.len()].copy_from_slice(cell);
+        let pointer = offset + header_size + i * 2;
+        page[pointer..pointer + 2].copy_from_slice(&(end as u16).to_be_bytes());
+    }
+
+    page[offset] = kind;
+    page[offset + 3..offset + 5].copy_from_slice(&(cells.len() as u16).to_be_bytes());
+    page[offset + 5..offset + 7].copy_from_slice(&(end as u16).to_be_bytes());
+    if let Some(right) = right {
+        page[offset + 8..offset + 12].copy_from_slice(&right.to_be_bytes());
+    }
+    page
+}
+
+enum Field<'a> {
+    Null,
+    Integer(u32),
+    Text(&'a str)
+}
+
+fn record(fields: &[Field]) -> Vec<u8> {
+    let mut types = Vec::new();
+    let mut body = Vec::new();
+    for field in fields {
+        match field {
+            Field::Null => varint(0, &mut types),
+            Field::Integer(value) => {
+                varint(4, &mut types);
+                body.extend(value.to_be_bytes());
+            }
+            Field::Text(text) => {
+                varint(text.len() as u64 * 2 + 13, &mut types);
+                body.extend(text.as_bytes());
+            }
+        }
+    }
+
+    let mut record = Vec::new();
+    varint(types.len() as u64 + 1, &mut record);
+    record.extend(types);
+    record.extend(body);
+    record
+}
+
+// SQLite's big-endian variable length integer, values here never need the 9 byte form
+fn varint(value: u64, out: &mut Vec<u8>) {
+    let mut groups = vec![(value & 0x7f) as u8];
+    let mut rest = value >> 7;
+    while rest > 0 {
+        groups.push((rest & 0x7f) as u8 | 0x80);
+        rest >>= 7;
+    }
+    out.extend(groups.into_iter().rev());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+    use super::{write_table, PAGE_SIZE};
+
+    fn read_varint(data: &[u8], index: &mut usize) -> u64 {
+        let mut value = 0;
+        loop {
+            let byte = data[*index];
+            *index += 1;
+            value = (value << 7) | (byte & 0x7f) as u64;
+            if byte & 0x80 == 0 {
+                return value;
+            }
+        }
+    }
+
+    // The fields of a record, integers written as text
+    fn read_record(data: &[u8]) -> Vec<Option<String>> {
+        let mut index = 0;
+        let header_size = read_varint(data, &mut index) as usize;
+        let mut types = Vec::new();
+        while index < header_size {
+            types.push(read_varint(data, &mut index));
+        }
+        types.into_iter().map(|kind| match kind {
+            0 => None,
+            4 => {
+                let value = u32::from_be_bytes(data[index..index + 4].try_into().unwrap());
+                index += 4;
+                Some(value.to_string())
+            }
+            kind => {
+                let length = (kind as usize - 13) / 2;
+                index += length;
+                Some(String::from_utf8(data[index - length..index].to_vec()).unwrap())
+            }
+        }).collect()
+    }
+
+    // Every row of the table b-tree starting at the page, in the order of their rowids
+    fn read_rows(file: &[u8], number: usize, rows: &mut Vec<(u64, Vec<Option<String>>)>) {
+        let page = &file[(number - 1) * PAGE_SIZE..number * PAGE_SIZE];
+        let offset = if number == 1 { 100 } else { 0 };
+        let kind = page[offset];
+        let count = u16::from_be_bytes([page[offset + 3], page[offset + 4]]) as usize;
+        let header_size = if kind == 0x05 { 12 } else { 8 };
+        for i in 0..count {
+            let pointer = offset + header_size + i * 2;
+            let mut index = u16::from_be_bytes([page[pointer], page[pointer + 1]]) as usize;
+            if kind == 0x05 {
+                let child = u32::from_be_bytes(page[index..index + 4].try_into().unwrap());
+                read_rows(file, child as usize, rows);
+            } else {
+                let size = read_varint(page, &mut index) as usize;
+                let rowid = read_varint(page, &mut index);
+                rows.push((rowid, read_record(&page[index..index + size])));
+            }
+        }
+        if kind == 0x05 {
+            let right = u32::from_be_bytes(page[offset + 8..offset + 12].try_into().unwrap());
+            read_rows(file, right as usize, rows);
+        }
+    }
+
+    #[test]
+    fn writes_a_table_sqlite_can_read() {
+        // Enough rows for several leaves and an interior page above them
+        let rows: Vec<Vec<String>> = (0..2000).map(|i| vec![format!("Class{}.method{}", i / 10, i), "Method".to_string(), format!("classes/Class{}.md", i / 10)]).collect();
+        let path = env::temp_dir().join(format!("arucas-md-sqlite-{}.dsidx", process::id()));
+        write_table(&path, "searchIndex", &["name", "type", "path"], &rows).unwrap();
+        let file = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(file.starts_with(b"SQLite format 3\0"));
+        assert_eq!(file.len() % PAGE_SIZE, 0);
+        let page_count = u32::from_be_bytes(file[28..32].try_into().unwrap()) as usize;
+        assert_eq!(page_count, file.len() / PAGE_SIZE);
+        assert!(page_count > 3);
+
+        let mut schema = Vec::new();
+        read_rows(&file, 1, &mut schema);
+        assert_eq!(schema.len(), 1);
+        let fields = &schema[0].1;
+        assert_eq!(fields[..3], [Some("table".to_string()), Some("searchIndex".to_string()), Some("searchIndex".to_string())]);
+        assert_eq!(fields[4].as_deref(), Some("CREATE TABLE searchIndex(id INTEGER PRIMARY KEY, name TEXT, type TEXT, path TEXT)"));
+
+        let mut read = Vec::new();
+        read_rows(&file, fields[3].as_ref().unwrap().parse().unwrap(), &mut read);
+        assert_eq!(read.len(), rows.len());
+        for (i, ((rowid, fields), row)) in read.iter().zip(&rows).enumerate() {
+            assert_eq!(*rowid, i as u64 + 1);
+            assert_eq!(fields[0], None);
+            assert_eq!(fields[1..], row.iter().cloned().map(Some).collect::<Vec<_>>()[..]);
+        }
+    }
+
+    #[test]
+    fn rejects_rows_too_large_for_a_page() {
+        let path = env::temp_dir().join(format!("arucas-md-sqlite-{}-large.dsidx", process::id()));
+        let error = write_table(&path, "searchIndex", &["name"], &[vec!["x".repeat(PAGE_SIZE)]]).unwrap_err();
+        assert_eq!(error.to_string(), "row 1 is too large for the index");
+        assert!(!path.exists());
+    }
+}
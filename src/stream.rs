@@ -0,0 +1,87 @@
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, Visitor};
+use serde_json::Value;
+
+// A single top-level entry of the input, read on its own
+pub enum Entry {
+    Class(String, Value),
+    Extension(String, Value),
+    Other(String, Value)
+}
+
+// Reads the input one class or extension at a time, so memory stays proportional
+// to the largest entry rather than the whole document. Entries are handed over in
+// the order they appear in the file.
+pub fn read(path: &str, mut handle: impl FnMut(Entry) -> Result<(), String>) -> Result<(), String> {
+    let file = File::open(path).map_err(|error| format!("Could not read {}: {}", path, error))?;
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(file));
+
+    DocumentSeed { handle: &mut handle }.deserialize(&mut deserializer)
+        .and_then(|_| deserializer.end())
+        .map_err(|error| format!("{}: {}", path, error))
+}
+
+struct DocumentSeed<'a, F> {
+    handle: &'a mut F
+}
+
+impl<'de, F: FnMut(Entry) -> Result<(), String>> DeserializeSeed<'de> for DocumentSeed<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F: FnMut(Entry) -> Result<(), String>> Visitor<'de> for DocumentSeed<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a documentation object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "classes" => map.next_value_seed(SectionSeed { handle: &mut *self.handle, entry: Entry::Class })?,
+                "extensions" => map.next_value_seed(SectionSeed { handle: &mut *self.handle, entry: Entry::Extension })?,
+                _ => {
+                    let value: Value = map.next_value()?;
+                    (self.handle)(Entry::Other(key, value)).map_err(de::Error::custom)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Hands over every entry of the classes or extensions object as soon as it is read
+struct SectionSeed<'a, F> {
+    handle: &'a mut F,
+    entry: fn(String, Value) -> Entry
+}
+
+impl<'de, F: FnMut(Entry) -> Result<(), String>> DeserializeSeed<'de> for SectionSeed<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F: FnMut(Entry) -> Result<(), String>> Visitor<'de> for SectionSeed<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an object of documented entries")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some((name, value)) = map.next_entry::<String, Value>()? {
+            (self.handle)((self.entry)(name, value)).map_err(de::Error::custom)?;
+        }
+        Ok(())
+    }
+}
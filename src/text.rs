@@ -0,0 +1,108 @@
+use std::path::Path;
+use crate::doc_parser::DocParser;
+use crate::html;
+use crate::output::write_file;
+
+pub const DEFAULT_WIDTH: usize = 80;
+
+// Writes Classes.txt and Extensions.txt for reading in a terminal or pager
+pub fn write(parser: &DocParser, directory: &Path, width: usize) -> Result<(), String> {
+    let mut classes = Vec::new();
+    let mut extensions = Vec::new();
+    parser.write_classes(&mut classes).and_then(|_| parser.write_extensions(&mut extensions))
+        .map_err(|error| format!("Could not render documentation: {}", error))?;
+
+    write_file(&directory.join("Classes.txt"), &from_markdown(&String::from_utf8_lossy(&classes), width))?;
+    write_file(&directory.join("Extensions.txt"), &from_markdown(&String::from_utf8_lossy(&extensions), width))
+}
+
+// Converts the markdown written by the renderer to plain text: headings are underlined,
+// paragraphs and list items are wrapped to `width` and examples are indented
+pub fn from_markdown(md: &str, width: usize) -> String {
+    let mut text = String::new();
+    let mut code = false;
+    // The indent of the list item that following lines continue
+    let mut item: Option<usize> = None;
+
+    for line in md.lines() {
+        if code {
+            if line.trim_end() == "```" {
+                code = false;
+            } else {
+                text.push_str("    ");
+                text.push_str(line);
+                text.push('\n');
+            }
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        if line.starts_with("```") {
+            code = true;
+            item = None;
+        } else if let Some((level, title)) = html::heading(line) {
+            let title = inline(title);
+            let underline = match level { 1 => '=', 2 => '-', _ => '~' };
+            text.push_str(&title);
+            text.push('\n');
+            text.push_str(&underline.to_string().repeat(title.chars().count()));
+            text.push('\n');
+            item = None;
+        } else if html::anchor(line).is_some() {
+            continue;
+        } else if let Some(content) = trimmed.strip_prefix("- ") {
+            let indent = line.len() - trimmed.len();
+            text.push_str(&wrap(&inline(content), &format!("{}* ", " ".repeat(indent)), indent + 2, width));
+            item = Some(indent + 2);
+        } else if line.trim().is_empty() {
+            text.push('\n');
+            item = None;
+        } else {
+            let indent = item.unwrap_or(0);
+            text.push_str(&wrap(&inline(line), &" ".repeat(indent), indent, width));
+        }
+    }
+
+    text
+}
+
+// Wraps the words of the text, the first line starts with `first` and the rest are indented
+pub fn wrap(text: &str, first: &str, indent: usize, width: usize) -> String {
+    let mut wrapped = String::new();
+    let mut line = first.to_string();
+    let mut empty = true;
+    for word in text.split_whitespace() {
+        if !empty && line.chars().count() + 1 + word.chars().count() > width {
+            wrapped.push_str(&line);
+            wrapped.push('\n');
+            line = " ".repeat(indent);
+            empty = true;
+        }
+        if !empty {
+            line.push(' ');
+        }
+        line.push_str(word);
+        empty = false;
+    }
+    wrapped.push_str(&line);
+    wrapped.push('\n');
+    wrapped
+}
+
+// Markup that means nothing in a terminal is removed, links keep their target
+fn inline(text: &str) -> String {
+    let mut plain = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        let Some((label, target, end)) = html::link_at(&rest[start..]) else {
+            plain.push_str(&rest[..start + 1]);
+            rest = &rest[start + 1..];
+            continue;
+        };
+        plain.push_str(&rest[..start]);
+        plain.push_str(&format!("{} ({})", label, target));
+        rest = &rest[start + end..];
+    }
+    plain.push_str(rest);
+    plain.replace('`', "").replace("**", "")
+}
@@ -0,0 +1,480 @@
+use std::collections::HashSet;
+use serde_json::{Map, Value};
+
+// A small TOML reader covering what hand written doc files need: tables,
+// arrays of tables, dotted keys, strings, numbers, booleans, arrays and
+// inline tables. Dates, inf and nan are not supported. The result is a plain JSON value
+// so it can be merged into, and deserialized like, the rest of the docs.
+pub fn parse(content: &str) -> Result<Value, String> {
+    let mut parser = TomlParser { chars: content.chars().collect(), index: 0, line: 1 };
+    parser.parse_document().map_err(|message| format!("line {}: {}", parser.line, message))
+}
+
+struct TomlParser {
+    chars: Vec<char>,
+    index: usize,
+    line: usize
+}
+
+impl TomlParser {
+    fn parse_document(&mut self) -> Result<Value, String> {
+        let mut root = Value::Object(Map::new());
+        let mut current: Vec<String> = Vec::new();
+        // Tables with a `[header]`, which may only appear once
+        let mut headers: HashSet<Vec<String>> = HashSet::new();
+
+        loop {
+            self.skip_whitespace_and_comments();
+            let Some(c) = self.peek() else {
+                break;
+            };
+
+            if c == '[' {
+                self.index += 1;
+                let is_array = self.peek() == Some('[');
+                if is_array {
+                    self.index += 1;
+                }
+
+                current = self.parse_key()?;
+                self.expect(']')?;
+                if is_array {
+                    self.expect(']')?;
+                    let array = TomlParser::resolve(&mut root, &current[..current.len() - 1])?;
+                    let last = current.last().unwrap().clone();
+                    let entry = array.entry(last).or_insert_with(|| Value::Array(Vec::new()));
+                    match entry {
+                        Value::Array(items) => items.push(Value::Object(Map::new())),
+                        _ => return Err(format!("'{}' is not an array of tables", current.join(".")))
+                    }
+                    // Each table of the array has headers of its own
+                    headers.retain(|header| !header.starts_with(&current));
+                } else {
+                    if !headers.insert(current.clone()) {
+                        return Err(format!("table '{}' is defined twice", current.join(".")));
+                    }
+                    TomlParser::resolve(&mut root, &current)?;
+                }
+            } else {
+                let key = self.parse_key()?;
+                self.expect('=')?;
+                self.skip_spaces();
+                let value = self.parse_value()?;
+
+                let mut path = current.clone();
+                path.extend_from_slice(&key[..key.len() - 1]);
+                let table = TomlParser::resolve(&mut root, &path)?;
+                let last = key.last().unwrap().clone();
+                if table.contains_key(&last) {
+                    return Err(format!("duplicate key '{}'", last));
+                }
+                table.insert(last, value);
+            }
+
+            self.skip_spaces();
+            self.skip_comment();
+            match self.peek() {
+                None | Some('\n') | Some('\r') => { }
+                Some(c) => return Err(format!("unexpected character '{}'", c))
+            }
+        }
+
+        Ok(root)
+    }
+
+    // Walks to the table at the path, creating tables as needed, and entering
+    // the most recent table of an array of tables
+    fn resolve<'a>(root: &'a mut Value, path: &[String]) -> Result<&'a mut Map<String, Value>, String> {
+        let mut table = root.as_object_mut().unwrap();
+        for key in path {
+            let entry = table.entry(key.clone()).or_insert_with(|| Value::Object(Map::new()));
+            let entry = match entry {
+                Value::Array(items) => items.last_mut().ok_or_else(|| format!("'{}' is an empty array", key))?,
+                other => other
+            };
+            table = entry.as_object_mut().ok_or_else(|| format!("'{}' is not a table", key))?;
+        }
+        Ok(table)
+    }
+
+    fn parse_key(&mut self) -> Result<Vec<String>, String> {
+        let mut keys = Vec::new();
+        loop {
+            self.skip_spaces();
+            let key = match self.peek() {
+                Some('"') => self.parse_basic_string()?,
+                Some('\'') => self.parse_literal_string()?,
+                _ => {
+                    let mut key = String::new();
+                    while let Some(c) = self.peek() {
+                        if !(c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+                            break;
+                        }
+                        key.push(c);
+                        self.index += 1;
+                    }
+                    if key.is_empty() {
+                        return Err("expected a key".to_string());
+                    }
+                    key
+                }
+            };
+            keys.push(key);
+
+            self.skip_spaces();
+            if self.peek() != Some('.') {
+                return Ok(keys);
+            }
+            self.index += 1;
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        match self.peek() {
+            Some('"') => {
+                if self.starts_with("\"\"\"") {
+                    self.parse_multiline_string().map(Value::String)
+                } else {
+                    self.parse_basic_string().map(Value::String)
+                }
+            }
+            Some('\'') => {
+                if self.starts_with("'''") {
+                    self.parse_multiline_literal_string().map(Value::String)
+                } else {
+                    self.parse_literal_string().map(Value::String)
+                }
+            }
+            Some('[') => self.parse_array(),
+            Some('{') => self.parse_inline_table(),
+            Some('t') if self.starts_with("true") => {
+                self.index += 4;
+                Ok(Value::Bool(true))
+            }
+            Some('f') if self.starts_with("false") => {
+                self.index += 5;
+                Ok(Value::Bool(false))
+            }
+            Some(_) => self.parse_number(),
+            None => Err("expected a value".to_string())
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace_and_comments();
+            if self.peek() == Some(']') {
+                self.index += 1;
+                return Ok(Value::Array(items));
+            }
+
+            items.push(self.parse_value()?);
+            self.skip_whitespace_and_comments();
+            match self.next() {
+                Some(',') => { }
+                Some(']') => return Ok(Value::Array(items)),
+                _ => return Err("expected ',' or ']' in array".to_string())
+            }
+        }
+    }
+
+    fn parse_inline_table(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+        let mut table = Value::Object(Map::new());
+        loop {
+            self.skip_spaces();
+            if self.peek() == Some('}') {
+                self.index += 1;
+                return Ok(table);
+            }
+
+            let key = self.parse_key()?;
+            self.expect('=')?;
+            self.skip_spaces();
+            let value = self.parse_value()?;
+            let inner = TomlParser::resolve(&mut table, &key[..key.len() - 1])?;
+            inner.insert(key.last().unwrap().clone(), value);
+
+            self.skip_spaces();
+            match self.next() {
+                Some(',') => { }
+                Some('}') => return Ok(table),
+                _ => return Err("expected ',' or '}' in inline table".to_string())
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value, String> {
+        let mut raw = String::new();
+        while let Some(c) = self.peek() {
+            if !(c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.' || c == '_') {
+                break;
+            }
+            raw.push(c);
+            self.index += 1;
+        }
+        let text = raw.replace('_', "");
+
+        if matches!(text.trim_start_matches(['+', '-']), "inf" | "nan") {
+            return Err(format!("'{}' is not supported, as JSON has no infinite or NaN numbers", raw));
+        }
+        // Each underscore has to be between two digits
+        let digits: Vec<char> = raw.chars().collect();
+        let separated = |i: usize| i > 0 && digits[i - 1].is_ascii_digit() && digits.get(i + 1).is_some_and(char::is_ascii_digit);
+        if (0..digits.len()).any(|i| digits[i] == '_' && !separated(i)) {
+            return Err(format!("invalid value '{}', underscores must be between digits", raw));
+        }
+
+        // Dates are the only other values starting with a digit
+        if text.len() >= 5 && text[..4].bytes().all(|c| c.is_ascii_digit()) && text.as_bytes()[4] == b'-' {
+            return Err(format!("dates are not supported, write '{}' as a string", text));
+        }
+        if let Ok(integer) = text.parse::<i64>() {
+            return Ok(Value::from(integer));
+        }
+        match text.parse::<f64>() {
+            Ok(float) if float.is_finite() => Ok(Value::from(float)),
+            _ => Err(format!("invalid value '{}'", text))
+        }
+    }
+
+    fn parse_basic_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => string.push(self.parse_escape()?),
+                Some('\n') | None => return Err("unterminated string".to_string()),
+                Some(c) => string.push(c)
+            }
+        }
+    }
+
+    fn parse_multiline_string(&mut self) -> Result<String, String> {
+        self.skip_opening("\"\"\"");
+        let mut string = String::new();
+        loop {
+            if self.closes('"', &mut string) {
+                return Ok(string);
+            }
+            match self.next() {
+                Some('\\') => {
+                    // A line ending backslash joins the lines together
+                    if matches!(self.peek(), Some('\n') | Some('\r') | Some(' ') | Some('\t')) {
+                        self.skip_whitespace();
+                    } else {
+                        string.push(self.parse_escape()?);
+                    }
+                }
+                Some('\r') => { }
+                Some(c) => string.push(c),
+                None => return Err("unterminated string".to_string())
+            }
+        }
+    }
+
+    // Like a multi-line basic string, but backslashes are kept as they are
+    fn parse_multiline_literal_string(&mut self) -> Result<String, String> {
+        self.skip_opening("'''");
+        let mut string = String::new();
+        loop {
+            if self.closes('\'', &mut string) {
+                return Ok(string);
+            }
+            match self.next() {
+                Some('\r') => { }
+                Some(c) => string.push(c),
+                None => return Err("unterminated string".to_string())
+            }
+        }
+    }
+
+    // Skips the quotes opening a multi-line string, and the newline directly after them which is trimmed
+    fn skip_opening(&mut self, quotes: &str) {
+        self.index += quotes.len();
+        if self.peek() == Some('\r') {
+            self.index += 1;
+        }
+        if self.peek() == Some('\n') {
+            self.next();
+        }
+    }
+
+    // Whether three quotes close the multi-line string here. Up to two more quotes right before
+    // them are part of the string, such as the one in `"""say "hi""""`
+    fn closes(&mut self, quote: char, string: &mut String) -> bool {
+        let count = self.chars[self.index..].iter().take_while(|c| **c == quote).count();
+        if count < 3 {
+            return false;
+        }
+        let extra = count.min(5) - 3;
+        string.extend(std::iter::repeat_n(quote, extra));
+        self.index += extra + 3;
+        true
+    }
+
+    fn parse_literal_string(&mut self) -> Result<String, String> {
+        self.expect('\'')?;
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('\'') => return Ok(string),
+                Some('\n') | None => return Err("unterminated string".to_string()),
+                Some(c) => string.push(c)
+            }
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<char, String> {
+        match self.next() {
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some('r') => Ok('\r'),
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('u') => self.parse_unicode(4),
+            Some('U') => self.parse_unicode(8),
+            Some(c) => Err(format!("invalid escape sequence '\\{}'", c)),
+            None => Err("unterminated string".to_string())
+        }
+    }
+
+    fn parse_unicode(&mut self, length: usize) -> Result<char, String> {
+        let mut hex = String::new();
+        for _ in 0..length {
+            hex.extend(self.next());
+        }
+        u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32).ok_or_else(|| "invalid unicode escape".to_string())
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_spaces();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("expected '{}' but found '{}'", expected, c)),
+            None => Err(format!("expected '{}'", expected))
+        }
+    }
+
+    fn starts_with(&self, string: &str) -> bool {
+        string.chars().enumerate().all(|(i, c)| self.chars.get(self.index + i) == Some(&c))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.index += 1;
+        }
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ') | Some('\t')) {
+            self.index += 1;
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ') | Some('\t') | Some('\n') | Some('\r')) {
+            self.next();
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            while !matches!(self.peek(), Some('\n') | None) {
+                self.index += 1;
+            }
+        }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('#') {
+                return;
+            }
+            self.skip_comment();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::parse;
+
+    #[test]
+    fn parses_tables_and_values() {
+        let value = parse("title = \"Docs\"\ncount = 3\nratio = 0.5\nenabled = true\n\n[lint]\nspelling = false\n\n[[pages]]\nname = \"a\"\n\n[[pages]]\nname = \"b\"\n").unwrap();
+        assert_eq!(value, json!({
+            "title": "Docs",
+            "count": 3,
+            "ratio": 0.5,
+            "enabled": true,
+            "lint": { "spelling": false },
+            "pages": [{ "name": "a" }, { "name": "b" }]
+        }));
+    }
+
+    #[test]
+    fn parses_dotted_keys_arrays_and_inline_tables() {
+        let value = parse("a.b = 1 # comment\nlist = [\n  \"x\",\n  \"y\",\n]\nimport = { path = \"util.Greeter\", name = \"Hi\" }\n").unwrap();
+        assert_eq!(value, json!({
+            "a": { "b": 1 },
+            "list": ["x", "y"],
+            "import": { "path": "util.Greeter", "name": "Hi" }
+        }));
+    }
+
+    #[test]
+    fn parses_strings() {
+        let value = parse(concat!(
+            "basic = \"tab\\there \\u00e9 \\b\\f\"\n",
+            "literal = 'C:\\path'\n",
+            "multiline = \"\"\"\nfirst\nsecond\"\"\"\n",
+            "quoted = \"\"\"say \"hi\"\"\"\"\"\n",
+            "multiline_literal = '''\nno \\escapes\n'''\n",
+            "ending_quote = '''it''''\n"
+        )).unwrap();
+        assert_eq!(value["basic"], "tab\there \u{e9} \u{8}\u{c}");
+        assert_eq!(value["literal"], "C:\\path");
+        assert_eq!(value["multiline"], "first\nsecond");
+        assert_eq!(value["quoted"], "say \"hi\"\"");
+        assert_eq!(value["multiline_literal"], "no \\escapes\n");
+        assert_eq!(value["ending_quote"], "it'");
+    }
+
+    #[test]
+    fn rejects_what_it_does_not_support() {
+        assert!(parse("bad = \"\\q\"").unwrap_err().contains("invalid escape sequence '\\q'"));
+        assert!(parse("open = \"never closed").unwrap_err().contains("unterminated string"));
+        assert!(parse("released = 2024-01-01").unwrap_err().contains("dates are not supported"));
+        assert!(parse("a = 1\na = 2").is_err());
+        assert!(parse("[a]\nb = 1\n[a]\nc = 2").unwrap_err().contains("table 'a' is defined twice"));
+        assert!(parse("a = +inf").unwrap_err().contains("'+inf' is not supported"));
+        assert!(parse("a = nan").unwrap_err().contains("'nan' is not supported"));
+        for number in ["1_", "_1", "1__000", "1_.5", "1._5"] {
+            assert!(parse(&format!("a = {}", number)).unwrap_err().contains("underscores must be between digits"), "{}", number);
+        }
+    }
+
+    #[test]
+    fn parses_numbers_and_repeated_tables() {
+        assert_eq!(parse("a = 1_000\nb = -0.000_5\nc = +3").unwrap(), json!({ "a": 1000, "b": -0.0005, "c": 3 }));
+        let value = parse("[[pages]]\n[pages.meta]\nname = \"a\"\n[[pages]]\n[pages.meta]\nname = \"b\"\n").unwrap();
+        assert_eq!(value, json!({ "pages": [{ "meta": { "name": "a" } }, { "meta": { "name": "b" } }] }));
+    }
+}
@@ -0,0 +1,67 @@
+// The API of the WebAssembly build, used through `wasm/arucas_md.js`. Strings cross the boundary
+// as UTF-8 in memory the module allocates, results are read from an `Output` and then freed.
+use std::{mem, slice};
+use crate::embed;
+
+#[repr(C)]
+pub struct Output {
+    // 1 if the text is the rendered markdown, 0 if it is an error message
+    pub ok: u32,
+    pub text: *mut u8,
+    pub length: usize
+}
+
+#[no_mangle]
+pub extern "C" fn alloc(length: usize) -> *mut u8 {
+    let mut buffer = Vec::<u8>::with_capacity(length);
+    let pointer = buffer.as_mut_ptr();
+    mem::forget(buffer);
+    pointer
+}
+
+/// # Safety
+/// The buffer must have been returned by `alloc` with the same length.
+#[no_mangle]
+pub unsafe extern "C" fn dealloc(pointer: *mut u8, length: usize) {
+    drop(Vec::from_raw_parts(pointer, 0, length));
+}
+
+/// # Safety
+/// Both strings must be `length` bytes of UTF-8 written to buffers from `alloc`.
+#[no_mangle]
+pub unsafe extern "C" fn render_classes(json: *const u8, json_length: usize, options: *const u8, options_length: usize) -> *mut Output {
+    output(read(json, json_length).and_then(|json| embed::render_classes(&json, &read(options, options_length)?)))
+}
+
+/// # Safety
+/// Both strings must be `length` bytes of UTF-8 written to buffers from `alloc`.
+#[no_mangle]
+pub unsafe extern "C" fn render_extensions(json: *const u8, json_length: usize, options: *const u8, options_length: usize) -> *mut Output {
+    output(read(json, json_length).and_then(|json| embed::render_extensions(&json, &read(options, options_length)?)))
+}
+
+/// # Safety
+/// The output must have been returned by a render function and not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn free_output(output: *mut Output) {
+    let output = Box::from_raw(output);
+    drop(Vec::from_raw_parts(output.text, output.length, output.length));
+}
+
+unsafe fn read(pointer: *const u8, length: usize) -> Result<String, String> {
+    if length == 0 {
+        return Ok(String::new());
+    }
+    String::from_utf8(slice::from_raw_parts(pointer, length).to_vec()).map_err(|_| "Input is not valid UTF-8".to_string())
+}
+
+fn output(result: Result<String, String>) -> *mut Output {
+    let (ok, text) = match result {
+        Ok(md) => (1, md),
+        Err(message) => (0, message)
+    };
+    let mut text = text.into_bytes().into_boxed_slice();
+    let output = Output { ok, text: text.as_mut_ptr(), length: text.len() };
+    mem::forget(text);
+    Box::into_raw(Box::new(output))
+}
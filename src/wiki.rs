@@ -0,0 +1,46 @@
+use std::path::Path;
+use std::process::Command;
+
+// Where the wiki is cloned, inside the state directory
+pub const DIRECTORY: &str = "wiki";
+
+// Clones the wiki, or brings an earlier clone up to date with the remote. The clone belongs
+// to the converter, so anything left in it by an earlier dry run is discarded.
+pub fn checkout(url: &str, directory: &Path) -> Result<(), String> {
+    if !directory.join(".git").exists() {
+        let path = directory.to_string_lossy();
+        return git(None, &["clone", "--quiet", url, &path]).map(|_| ());
+    }
+
+    git(Some(directory), &["remote", "set-url", "origin", url])?;
+    git(Some(directory), &["fetch", "--quiet", "origin"])?;
+    git(Some(directory), &["reset", "--quiet", "--hard", "@{upstream}"])?;
+    git(Some(directory), &["clean", "--quiet", "-fd"])?;
+    Ok(())
+}
+
+// The pages changed since the last commit, as `git status --short` lines
+pub fn changes(directory: &Path) -> Result<Vec<String>, String> {
+    git(Some(directory), &["add", "--all"])?;
+    let status = git(Some(directory), &["status", "--short"])?;
+    Ok(status.lines().map(str::to_string).collect())
+}
+
+pub fn publish(directory: &Path, message: &str) -> Result<(), String> {
+    git(Some(directory), &["commit", "--quiet", "--message", message])?;
+    git(Some(directory), &["push", "--quiet"])?;
+    Ok(())
+}
+
+fn git(directory: Option<&Path>, args: &[&str]) -> Result<String, String> {
+    let mut command = Command::new("git");
+    if let Some(directory) = directory {
+        command.current_dir(directory);
+    }
+    let output = command.args(args).output().map_err(|error| format!("Could not run git: {}", error))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("'git {}' failed: {}", args.join(" "), stderr.trim_end()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
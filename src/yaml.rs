@@ -0,0 +1,565 @@
+use serde_json::{Map, Number, Value};
+
+// A small YAML reader covering what hand written docs need: block mappings and
+// sequences, flow collections, plain and quoted scalars, and literal (|) or
+// folded (>) block scalars for examples. Anchors, tags and multiple documents
+// are not supported, anchors and aliases are an error rather than text. The
+// result is a plain JSON value like the JSON input.
+pub fn parse(content: &str) -> Result<Value, String> {
+    let mut lines = Vec::new();
+    for (i, raw) in content.lines().enumerate() {
+        let raw = raw.trim_end_matches('\r');
+        let text = raw.trim_start_matches(' ');
+        if text.starts_with('\t') {
+            return Err(format!("line {}: tabs can't be used for indentation", i + 1));
+        }
+        lines.push(Line { number: i + 1, indent: raw.len() - text.len(), text: text.to_string() });
+    }
+
+    let mut parser = YamlParser { lines, index: 0 };
+    parser.skip_blank();
+    if parser.peek().is_some_and(|line| line.text.trim_end() == "---") {
+        parser.index += 1;
+        parser.skip_blank();
+    }
+
+    let value = match parser.peek() {
+        Some(line) => parser.parse_node(line.indent)?,
+        None => Value::Null
+    };
+
+    parser.skip_blank();
+    match parser.peek() {
+        Some(line) if line.text.trim_end() != "..." => Err(format!("line {}: unexpected content", line.number)),
+        _ => Ok(value)
+    }
+}
+
+struct Line {
+    number: usize,
+    indent: usize,
+    text: String
+}
+
+struct YamlParser {
+    lines: Vec<Line>,
+    index: usize
+}
+
+impl YamlParser {
+    fn peek(&self) -> Option<&Line> {
+        self.lines.get(self.index)
+    }
+
+    fn skip_blank(&mut self) {
+        while self.peek().is_some_and(|line| strip_comment(&line.text).is_empty()) {
+            self.index += 1;
+        }
+    }
+
+    // The next significant line, if it is indented at least as far as `indent`
+    fn next_at(&mut self, indent: usize) -> Option<&Line> {
+        self.skip_blank();
+        self.peek().filter(|line| line.indent >= indent)
+    }
+
+    fn parse_node(&mut self, indent: usize) -> Result<Value, String> {
+        let line = self.next_at(indent).ok_or("expected a value")?;
+        let indent = line.indent;
+        let text = strip_comment(&line.text);
+
+        if is_sequence_entry(text) {
+            self.parse_sequence(indent)
+        } else if key_end(text).is_some() {
+            self.parse_mapping(indent)
+        } else {
+            let (text, number) = (text.to_string(), line.number);
+            self.index += 1;
+            self.parse_inline(&text, indent, number)
+        }
+    }
+
+    fn parse_sequence(&mut self, indent: usize) -> Result<Value, String> {
+        let mut items = Vec::new();
+
+        while let Some(line) = self.next_at(indent) {
+            let text = strip_comment(&line.text);
+            if line.indent != indent || !is_sequence_entry(text) {
+                break;
+            }
+
+            let rest = text[1..].trim_start();
+            if rest.is_empty() {
+                self.index += 1;
+                let nested = self.next_at(indent + 1).is_some();
+                items.push(if nested { self.parse_node(indent + 1)? } else { Value::Null });
+                continue;
+            }
+
+            if rest.starts_with(['|', '>']) {
+                let header = rest.to_string();
+                self.index += 1;
+                items.push(self.parse_block_scalar(&header, indent));
+                continue;
+            }
+
+            // A compact entry like "- name: value" is read as if it started on its own line
+            let offset = line.text.len() - line.text[1..].trim_start().len();
+            let line = &mut self.lines[self.index];
+            line.indent += offset;
+            line.text = line.text[offset..].to_string();
+            let indent = line.indent;
+            items.push(self.parse_node(indent)?);
+        }
+
+        Ok(Value::Array(items))
+    }
+
+    fn parse_mapping(&mut self, indent: usize) -> Result<Value, String> {
+        let mut map = Map::new();
+
+        while let Some(line) = self.next_at(indent) {
+            let number = line.number;
+            let text = strip_comment(&line.text).to_string();
+            if line.indent != indent {
+                return Err(format!("line {}: unexpected indentation", number));
+            }
+            let Some(end) = key_end(&text) else {
+                break;
+            };
+
+            let key = parse_key(text[..end].trim()).map_err(|message| format!("line {}: {}", number, message))?;
+            if map.contains_key(&key) {
+                return Err(format!("line {}: duplicate key '{}'", number, key));
+            }
+
+            let rest = text[end + 1..].trim();
+            self.index += 1;
+
+            let value = if rest.is_empty() {
+                match self.next_at(indent) {
+                    Some(next) if next.indent > indent => {
+                        let nested = next.indent;
+                        self.parse_node(nested)?
+                    }
+                    // Sequences may start at the same indentation as their key
+                    Some(next) if is_sequence_entry(strip_comment(&next.text)) => self.parse_sequence(indent)?,
+                    _ => Value::Null
+                }
+            } else if rest.starts_with(['|', '>']) {
+                self.parse_block_scalar(rest, indent)
+            } else {
+                let rest = rest.to_string();
+                self.parse_inline(&rest, indent, number)?
+            };
+            map.insert(key, value);
+        }
+
+        Ok(Value::Object(map))
+    }
+
+    // A scalar or flow collection, which may continue on more indented lines
+    fn parse_inline(&mut self, text: &str, indent: usize, number: usize) -> Result<Value, String> {
+        let mut text = strip_comment(text).to_string();
+
+        if text.starts_with(['[', '{']) {
+            while !is_balanced(&text) {
+                let Some(line) = self.peek() else {
+                    return Err(format!("line {}: unterminated flow collection", number));
+                };
+                text.push(' ');
+                text.push_str(strip_comment(&line.text));
+                self.index += 1;
+            }
+        } else if !text.starts_with(['"', '\'']) {
+            // Plain scalars fold onto following lines that are indented further
+            while let Some(line) = self.next_at(indent + 1) {
+                let continuation = strip_comment(&line.text);
+                if is_sequence_entry(continuation) || key_end(continuation).is_some() {
+                    break;
+                }
+                text.push(' ');
+                text.push_str(continuation);
+                self.index += 1;
+            }
+        }
+
+        let mut flow = Flow { chars: text.chars().collect(), index: 0 };
+        let value = flow.parse_value(false).map_err(|message| format!("line {}: {}", number, message))?;
+        flow.skip_spaces();
+        if flow.index < flow.chars.len() {
+            return Err(format!("line {}: unexpected '{}'", number, flow.chars[flow.index..].iter().collect::<String>()));
+        }
+        Ok(value)
+    }
+
+    fn parse_block_scalar(&mut self, header: &str, indent: usize) -> Value {
+        let folded = header.starts_with('>');
+        let chomp = if header.contains('-') { "strip" } else if header.contains('+') { "keep" } else { "clip" };
+
+        let mut raw = Vec::new();
+        while let Some(line) = self.peek() {
+            if !line.text.is_empty() && line.indent <= indent {
+                break;
+            }
+            raw.push((line.indent, line.text.clone()));
+            self.index += 1;
+        }
+
+        let content_indent = raw.iter().filter(|(_, text)| !text.is_empty()).map(|(indent, _)| *indent).min();
+        let Some(content_indent) = content_indent else {
+            return Value::String(String::new());
+        };
+
+        let lines: Vec<String> = raw.into_iter().map(|(indent, text)| {
+            if text.is_empty() { text } else { " ".repeat(indent - content_indent) + &text }
+        }).collect();
+
+        let mut value = if folded {
+            let mut folded = String::new();
+            for (i, line) in lines.iter().enumerate() {
+                if i > 0 {
+                    let previous = &lines[i - 1];
+                    let joins = !line.is_empty() && !previous.is_empty() && !line.starts_with(' ') && !previous.starts_with(' ');
+                    folded.push(if joins { ' ' } else { '\n' });
+                }
+                folded.push_str(line);
+            }
+            folded
+        } else {
+            lines.join("\n")
+        };
+
+        match chomp {
+            "strip" => value.truncate(value.trim_end_matches('\n').len()),
+            "clip" => {
+                value.truncate(value.trim_end_matches('\n').len());
+                value.push('\n');
+            }
+            _ => value.push('\n')
+        }
+
+        Value::String(value)
+    }
+}
+
+const UNSUPPORTED_ANCHOR: &str = "anchors and aliases are not supported, repeat the value instead";
+
+fn is_sequence_entry(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+// The index of the colon ending a mapping key, if the text is a mapping entry
+fn key_end(text: &str) -> Option<usize> {
+    if text.starts_with(['[', '{', '|', '>']) {
+        return None;
+    }
+
+    unquoted(text).into_iter()
+        .find(|(i, c)| *c == ':' && (text[i + 1..].is_empty() || text[i + 1..].starts_with(' ')))
+        .map(|(i, _)| i)
+}
+
+fn parse_key(key: &str) -> Result<String, String> {
+    if key.starts_with(['&', '*']) {
+        return Err(UNSUPPORTED_ANCHOR.to_string());
+    }
+    if key.starts_with(['"', '\'']) {
+        let mut flow = Flow { chars: key.chars().collect(), index: 0 };
+        return match flow.parse_value(false)? {
+            Value::String(key) => Ok(key),
+            _ => Err("invalid key".to_string())
+        };
+    }
+    Ok(key.to_string())
+}
+
+// Removes a trailing comment that isn't inside a quoted string
+fn strip_comment(text: &str) -> &str {
+    match unquoted(text).into_iter().find(|(i, c)| *c == '#' && (*i == 0 || text[..*i].ends_with(' '))) {
+        Some((i, _)) => text[..i].trim_end(),
+        None => text.trim_end()
+    }
+}
+
+fn is_balanced(text: &str) -> bool {
+    let depth: i32 = unquoted(text).into_iter().map(|(_, c)| match c {
+        '[' | '{' => 1,
+        ']' | '}' => -1,
+        _ => 0
+    }).sum();
+    depth <= 0
+}
+
+// The characters outside of quoted strings, with their byte index. Quotes only
+// start a string at the beginning of a value, so apostrophes in words are kept
+fn unquoted(text: &str) -> Vec<(usize, char)> {
+    let mut result = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    let mut previous = ' ';
+
+    while let Some((i, c)) = chars.next() {
+        if matches!(c, '"' | '\'') && matches!(previous, ' ' | '[' | '{' | ',' | ':') {
+            while let Some((_, inner)) = chars.next() {
+                let escaped = c == '"' && inner == '\\';
+                let doubled = c == '\'' && inner == c && chars.peek().is_some_and(|(_, next)| *next == '\'');
+                if escaped || doubled {
+                    chars.next();
+                } else if inner == c {
+                    break;
+                }
+            }
+        } else {
+            result.push((i, c));
+        }
+        previous = c;
+    }
+
+    result
+}
+
+struct Flow {
+    chars: Vec<char>,
+    index: usize
+}
+
+impl Flow {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(|c| c == ' ') {
+            self.index += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_spaces();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.index += 1;
+                Ok(())
+            }
+            Some(c) => Err(format!("expected '{}' but found '{}'", expected, c)),
+            None => Err(format!("expected '{}'", expected))
+        }
+    }
+
+    fn parse_value(&mut self, in_flow: bool) -> Result<Value, String> {
+        self.skip_spaces();
+        match self.peek() {
+            Some('[') => {
+                self.index += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_spaces();
+                    if self.peek() == Some(']') {
+                        self.index += 1;
+                        break;
+                    }
+                    items.push(self.parse_value(true)?);
+                    self.skip_spaces();
+                    match self.peek() {
+                        Some(',') => self.index += 1,
+                        _ => {
+                            self.expect(']')?;
+                            break;
+                        }
+                    }
+                }
+                Ok(Value::Array(items))
+            }
+            Some('{') => {
+                self.index += 1;
+                let mut map = Map::new();
+                loop {
+                    self.skip_spaces();
+                    if self.peek() == Some('}') {
+                        self.index += 1;
+                        break;
+                    }
+                    let key = match self.parse_value(true)? {
+                        Value::String(key) => key,
+                        other => other.to_string()
+                    };
+                    self.expect(':')?;
+                    map.insert(key, self.parse_value(true)?);
+                    self.skip_spaces();
+                    match self.peek() {
+                        Some(',') => self.index += 1,
+                        _ => {
+                            self.expect('}')?;
+                            break;
+                        }
+                    }
+                }
+                Ok(Value::Object(map))
+            }
+            Some('"') => self.parse_double_quoted(),
+            Some('\'') => self.parse_single_quoted(),
+            Some('&' | '*') => Err(UNSUPPORTED_ANCHOR.to_string()),
+            _ => {
+                let start = self.index;
+                while let Some(c) = self.peek() {
+                    let ends_key = c == ':' && self.chars.get(self.index + 1).is_none_or(|next| *next == ' ');
+                    if in_flow && (matches!(c, ',' | ']' | '}') || ends_key) {
+                        break;
+                    }
+                    self.index += 1;
+                }
+                let text: String = self.chars[start..self.index].iter().collect();
+                Ok(plain(text.trim()))
+            }
+        }
+    }
+
+    fn parse_double_quoted(&mut self) -> Result<Value, String> {
+        self.index += 1;
+        let mut string = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err("unterminated string".to_string());
+            };
+            self.index += 1;
+            match c {
+                '"' => break,
+                '\\' => {
+                    let escape = self.peek().ok_or("unterminated string")?;
+                    self.index += 1;
+                    match escape {
+                        'n' => string.push('\n'),
+                        't' => string.push('\t'),
+                        'r' => string.push('\r'),
+                        '0' => string.push('\0'),
+                        '"' | '\\' | '/' | ' ' => string.push(escape),
+                        'u' => {
+                            let hex: String = self.chars.iter().skip(self.index).take(4).collect();
+                            let code = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                            string.push(code.ok_or_else(|| format!("invalid unicode escape '\\u{}'", hex))?);
+                            self.index += 4;
+                        }
+                        _ => return Err(format!("invalid escape '\\{}'", escape))
+                    }
+                }
+                _ => string.push(c)
+            }
+        }
+        Ok(Value::String(string))
+    }
+
+    fn parse_single_quoted(&mut self) -> Result<Value, String> {
+        self.index += 1;
+        let mut string = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err("unterminated string".to_string());
+            };
+            self.index += 1;
+            if c == '\'' {
+                if self.peek() != Some('\'') {
+                    break;
+                }
+                self.index += 1;
+            }
+            string.push(c);
+        }
+        Ok(Value::String(string))
+    }
+}
+
+// Resolves an unquoted scalar to null, a boolean, a number or a string
+fn plain(text: &str) -> Value {
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => Value::Null,
+        "true" | "True" | "TRUE" => Value::Bool(true),
+        "false" | "False" | "FALSE" => Value::Bool(false),
+        _ => {
+            if let Ok(int) = text.parse::<i64>() {
+                return Value::from(int);
+            }
+            let is_float = text.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'))
+                && text.chars().any(|c| c.is_ascii_digit());
+            match text.parse::<f64>().ok().filter(|_| is_float).and_then(Number::from_f64) {
+                Some(number) => Value::Number(number),
+                None => Value::String(text.to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::parse;
+
+    #[test]
+    fn parses_block_collections() {
+        let value = parse(concat!(
+            "---\n",
+            "version: 2\n",
+            "classes:\n",
+            "  Counter:\n",
+            "    desc:\n",
+            "      - A counter # trailing comment\n",
+            "      - 'It''s simple'\n",
+            "    methods:\n",
+            "    - name: increment\n",
+            "      returns: { type: Number, desc: \"The new value\" }\n",
+            "    deprecated: ~\n",
+            "    experimental: true\n",
+            "    weight: 1.5\n"
+        )).unwrap();
+        assert_eq!(value, json!({
+            "version": 2,
+            "classes": {
+                "Counter": {
+                    "desc": ["A counter", "It's simple"],
+                    "methods": [{ "name": "increment", "returns": { "type": "Number", "desc": "The new value" } }],
+                    "deprecated": null,
+                    "experimental": true,
+                    "weight": 1.5
+                }
+            }
+        }));
+    }
+
+    #[test]
+    fn parses_block_scalars() {
+        let value = parse(concat!(
+            "literal: |\n",
+            "  counter = new Counter();\n",
+            "    print(counter);\n",
+            "folded: >-\n",
+            "  one\n",
+            "  line\n",
+            "\n",
+            "  next\n",
+            "plain: a plain\n",
+            "  continued scalar\n",
+            "list: [a, 'b, c', [1, 2]]\n"
+        )).unwrap();
+        assert_eq!(value["literal"], "counter = new Counter();\n  print(counter);\n");
+        assert_eq!(value["folded"], "one line\n\nnext");
+        assert_eq!(value["plain"], "a plain continued scalar");
+        assert_eq!(value["list"], json!(["a", "b, c", [1, 2]]));
+    }
+
+    #[test]
+    fn reports_errors_with_their_line() {
+        assert_eq!(parse("a: 1\na: 2").unwrap_err(), "line 2: duplicate key 'a'");
+        assert_eq!(parse("a:\n\tb: 1").unwrap_err(), "line 2: tabs can't be used for indentation");
+        assert!(parse("a: \"open").unwrap_err().starts_with("line 1: unterminated string"));
+    }
+
+    #[test]
+    fn rejects_anchors_and_aliases() {
+        let error = "anchors and aliases are not supported, repeat the value instead";
+        assert_eq!(parse("a: &shared [x]\nb: 1").unwrap_err(), format!("line 1: {}", error));
+        assert_eq!(parse("a: 1\nb: *shared").unwrap_err(), format!("line 2: {}", error));
+        assert_eq!(parse("- 1\n- [2, *shared]").unwrap_err(), format!("line 2: {}", error));
+        assert_eq!(parse("a: 1\n&anchor b: 2").unwrap_err(), format!("line 2: {}", error));
+        // Only at the start of a value, so they may still appear in text
+        assert_eq!(parse("a: this & *that*").unwrap(), json!({ "a": "this & *that*" }));
+    }
+}
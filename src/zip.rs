@@ -0,0 +1,396 @@
+use std::fs;
+
+// Reads a single entry from a zip or jar archive. Only stored and deflated
+// entries are supported, which is what jar tooling produces; ZIP64 archives
+// and encryption are not.
+pub fn read_entry(archive: &str, name: &str) -> Result<String, String> {
+    let data = fs::read(archive).map_err(|error| format!("Could not read {}: {}", archive, error))?;
+    let entries = central_directory(&data).map_err(|message| format!("{}: {}", archive, message))?;
+
+    // The entry may also be given by file name alone if only one entry has it
+    let entry = match entries.iter().find(|entry| entry.name == name) {
+        Some(entry) => entry,
+        None => {
+            let suffix = format!("/{}", name.trim_start_matches('/'));
+            let matching: Vec<&Entry> = entries.iter().filter(|entry| entry.name.ends_with(&suffix)).collect();
+            match matching[..] {
+                [entry] => entry,
+                [] => return Err(format!("{}: no entry named '{}'", archive, name)),
+                _ => return Err(format!("{}: several entries are named '{}', give the full path", archive, name))
+            }
+        }
+    };
+
+    let content = extract(&data, entry).map_err(|message| format!("{}!{}: {}", archive, entry.name, message))?;
+    String::from_utf8(content).map_err(|_| format!("{}!{}: entry is not valid UTF-8", archive, entry.name))
+}
+
+struct Entry {
+    name: String,
+    method: u16,
+    crc: u32,
+    compressed_size: usize,
+    size: usize,
+    offset: usize
+}
+
+fn u16_at(data: &[u8], index: usize) -> Result<u16, String> {
+    data.get(index..index + 2).map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]])).ok_or_else(|| "archive is truncated".to_string())
+}
+
+fn u32_at(data: &[u8], index: usize) -> Result<u32, String> {
+    data.get(index..index + 4).map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])).ok_or_else(|| "archive is truncated".to_string())
+}
+
+fn central_directory(data: &[u8]) -> Result<Vec<Entry>, String> {
+    // The end of central directory record is followed by a comment of up to 64KB
+    let end = (0..data.len().saturating_sub(21)).rev()
+        .take(65536 + 22)
+        .find(|index| data[*index..].starts_with(&[0x50, 0x4b, 0x05, 0x06]))
+        .ok_or("not a zip archive")?;
+
+    let count = u16_at(data, end + 10)? as usize;
+    let mut index = u32_at(data, end + 16)? as usize;
+    if index == 0xffffffff {
+        return Err("ZIP64 archives are not supported".to_string());
+    }
+
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if u32_at(data, index)? != 0x02014b50 {
+            return Err("invalid central directory".to_string());
+        }
+
+        let name_length = u16_at(data, index + 28)? as usize;
+        let extra_length = u16_at(data, index + 30)? as usize;
+        let comment_length = u16_at(data, index + 32)? as usize;
+        let name = data.get(index + 46..index + 46 + name_length).ok_or("archive is truncated")?;
+
+        entries.push(Entry {
+            name: String::from_utf8_lossy(name).to_string(),
+            method: u16_at(data, index + 10)?,
+            crc: u32_at(data, index + 16)?,
+            compressed_size: u32_at(data, index + 20)? as usize,
+            size: u32_at(data, index + 24)? as usize,
+            offset: u32_at(data, index + 42)? as usize
+        });
+        index += 46 + name_length + extra_length + comment_length;
+    }
+
+    Ok(entries)
+}
+
+fn extract(data: &[u8], entry: &Entry) -> Result<Vec<u8>, String> {
+    if u32_at(data, entry.offset)? != 0x04034b50 {
+        return Err("invalid local header".to_string());
+    }
+    if u16_at(data, entry.offset + 6)? & 1 != 0 {
+        return Err("encrypted entries are not supported".to_string());
+    }
+
+    let start = entry.offset + 30 + u16_at(data, entry.offset + 26)? as usize + u16_at(data, entry.offset + 28)? as usize;
+    let compressed = data.get(start..start + entry.compressed_size).ok_or("archive is truncated")?;
+
+    let content = match entry.method {
+        0 => compressed.to_vec(),
+        8 => inflate(compressed, entry.size)?,
+        method => return Err(format!("compression method {} is not supported", method))
+    };
+
+    if content.len() != entry.size || crc32(&content) != entry.crc {
+        return Err("entry is corrupt".to_string());
+    }
+    Ok(content)
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (i, value) in table.iter_mut().enumerate() {
+        let mut crc = i as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xedb88320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+        *value = crc;
+    }
+
+    !data.iter().fold(!0u32, |crc, byte| table[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+const LENGTH_BASE: [u16; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577
+];
+const DISTANCE_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+// Canonical Huffman code, as the number of codes of each length and the symbols in code order
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0u16; 16];
+        for length in lengths {
+            counts[*length as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut symbols = Vec::with_capacity(lengths.len());
+        for length in 1..16 {
+            for (symbol, _) in lengths.iter().enumerate().filter(|(_, l)| **l as usize == length) {
+                symbols.push(symbol as u16);
+            }
+        }
+        Huffman { counts, symbols }
+    }
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    index: usize,
+    bit: u32
+}
+
+impl BitReader<'_> {
+    fn bits(&mut self, count: u32) -> Result<u32, String> {
+        let mut value = 0;
+        for i in 0..count {
+            let byte = *self.data.get(self.index).ok_or("compressed data is truncated")?;
+            value |= (((byte >> self.bit) & 1) as u32) << i;
+            self.bit += 1;
+            if self.bit == 8 {
+                self.bit = 0;
+                self.index += 1;
+            }
+        }
+        Ok(value)
+    }
+
+    fn decode(&mut self, huffman: &Huffman) -> Result<u16, String> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for length in 1..16 {
+            code |= self.bits(1)? as i32;
+            let count = huffman.counts[length] as i32;
+            if code - first < count {
+                return Ok(huffman.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err("invalid Huffman code".to_string())
+    }
+}
+
+fn inflate(data: &[u8], size: usize) -> Result<Vec<u8>, String> {
+    let mut reader = BitReader { data, index: 0, bit: 0 };
+    let mut out = Vec::with_capacity(size);
+
+    loop {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                if reader.bit > 0 {
+                    reader.bit = 0;
+                    reader.index += 1;
+                }
+                let length = u16_at(data, reader.index)? as usize;
+                let block = data.get(reader.index + 4..reader.index + 4 + length).ok_or("compressed data is truncated")?;
+                out.extend_from_slice(block);
+                reader.index += 4 + length;
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                inflate_block(&mut reader, &mut out, &Huffman::new(&lengths), &Huffman::new(&[5; 30]))?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut reader)?;
+                inflate_block(&mut reader, &mut out, &literals, &distances)?;
+            }
+            _ => return Err("invalid block type".to_string())
+        }
+
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+fn dynamic_codes(reader: &mut BitReader) -> Result<(Huffman, Huffman), String> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_count = reader.bits(4)? as usize + 4;
+
+    let mut code_lengths = [0u8; 19];
+    for index in CODE_LENGTH_ORDER.iter().take(code_count) {
+        code_lengths[*index] = reader.bits(3)? as u8;
+    }
+    let code_lengths = Huffman::new(&code_lengths);
+
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let (value, repeat) = match reader.decode(&code_lengths)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => (*lengths.last().ok_or("repeat without a previous length")?, 3 + reader.bits(2)?),
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?)
+        };
+        lengths.extend(std::iter::repeat_n(value, repeat as usize));
+    }
+    if lengths.len() > literal_count + distance_count {
+        return Err("too many code lengths".to_string());
+    }
+
+    Ok((Huffman::new(&lengths[..literal_count]), Huffman::new(&lengths[literal_count..])))
+}
+
+fn inflate_block(reader: &mut BitReader, out: &mut Vec<u8>, literals: &Huffman, distances: &Huffman) -> Result<(), String> {
+    loop {
+        let symbol = reader.decode(literals)? as usize;
+        if symbol < 256 {
+            out.push(symbol as u8);
+            continue;
+        }
+        if symbol == 256 {
+            return Ok(());
+        }
+
+        let index = symbol - 257;
+        if index >= LENGTH_BASE.len() {
+            return Err("invalid length code".to_string());
+        }
+        let length = LENGTH_BASE[index] as usize + reader.bits(LENGTH_EXTRA[index] as u32)? as usize;
+
+        let index = reader.decode(distances)? as usize;
+        if index >= DISTANCE_BASE.len() {
+            return Err("invalid distance code".to_string());
+        }
+        let distance = DISTANCE_BASE[index] as usize + reader.bits(DISTANCE_EXTRA[index] as u32)? as usize;
+        if distance > out.len() {
+            return Err("distance is too far back".to_string());
+        }
+
+        let start = out.len() - distance;
+        for i in 0..length {
+            out.push(out[start + i]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+    use super::{crc32, inflate, read_entry};
+
+    // `{"version": 2}` compressed with the fixed Huffman codes
+    const FIXED: [u8; 16] = [171, 86, 42, 75, 45, 42, 206, 204, 207, 83, 178, 82, 48, 170, 5, 0];
+    const STORED: [u8; 17] = [1, 12, 0, 243, 255, 115, 116, 111, 114, 101, 100, 32, 98, 108, 111, 99, 107];
+    // `dynamic_text()` compressed with codes of its own
+    const DYNAMIC: &str = "7dd1ad0ec2401045e157d9ac46f4f6bf581e8320dab20e106c50cdbe3b948a1975dccde4a8f9b6b83ee69c538ee7b0\
+        c5cbbeabffbca7bcfec6f5b885d7e7b9a477a8e2ad9c8e932093653564b5650d648d652d64ad651d649d653d64bd65036483652364a3651364\
+        937b2f31c83b2084931051c8598830e434441c721e2210391111899c8908454e45c422e72282d12e53ca17";
+
+    fn dynamic_text() -> String {
+        let classes: Vec<String> = (0..20).map(|i| format!("\"Class{}\": {{\"desc\": [\"Class number {}\"]}}", i, i)).collect();
+        format!("{{\"classes\": {{{}}}}}", classes.join(","))
+    }
+
+    fn dynamic() -> Vec<u8> {
+        (0..DYNAMIC.len()).step_by(2).map(|i| u8::from_str_radix(&DYNAMIC[i..i + 2], 16).unwrap()).collect()
+    }
+
+    // An archive of the entries, each with its compression method, the data stored and what it holds
+    fn archive(entries: &[(&str, u16, &[u8], &[u8])]) -> Vec<u8> {
+        let (mut data, mut directory) = (Vec::new(), Vec::new());
+        for (name, method, stored, content) in entries {
+            let mut fields = Vec::new();
+            fields.extend(20u16.to_le_bytes());
+            fields.extend(0u16.to_le_bytes());
+            fields.extend(method.to_le_bytes());
+            fields.extend([0; 4]);
+            fields.extend(crc32(content).to_le_bytes());
+            fields.extend((stored.len() as u32).to_le_bytes());
+            fields.extend((content.len() as u32).to_le_bytes());
+            fields.extend((name.len() as u16).to_le_bytes());
+            fields.extend(0u16.to_le_bytes());
+
+            directory.extend(0x02014b50u32.to_le_bytes());
+            directory.extend(20u16.to_le_bytes());
+            directory.extend(&fields);
+            directory.extend([0; 10]);
+            directory.extend((data.len() as u32).to_le_bytes());
+            directory.extend(name.as_bytes());
+
+            data.extend(0x04034b50u32.to_le_bytes());
+            data.extend(&fields);
+            data.extend(name.as_bytes());
+            data.extend(*stored);
+        }
+
+        let offset = data.len() as u32;
+        let size = directory.len() as u32;
+        data.extend(directory);
+        data.extend(0x06054b50u32.to_le_bytes());
+        data.extend([0; 4]);
+        data.extend((entries.len() as u16).to_le_bytes());
+        data.extend((entries.len() as u16).to_le_bytes());
+        data.extend(size.to_le_bytes());
+        data.extend(offset.to_le_bytes());
+        data.extend([0; 2]);
+        data
+    }
+
+    fn write_archive(name: &str, data: &[u8]) -> String {
+        let path = env::temp_dir().join(format!("arucas-md-zip-{}-{}.jar", process::id(), name));
+        fs::write(&path, data).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn inflates_every_block_type() {
+        assert_eq!(inflate(&STORED, 12).unwrap(), b"stored block");
+        assert_eq!(inflate(&FIXED, 14).unwrap(), b"{\"version\": 2}");
+        let text = dynamic_text();
+        assert_eq!(inflate(&dynamic(), text.len()).unwrap(), text.as_bytes());
+        assert!(inflate(&FIXED[..4], 14).is_err());
+    }
+
+    #[test]
+    fn reads_entries_by_path_or_file_name() {
+        let text = dynamic_text();
+        let path = write_archive("entries", &archive(&[
+            ("META-INF/MANIFEST.MF", 0, b"Manifest-Version: 1.0\n", b"Manifest-Version: 1.0\n"),
+            ("docs/AllDocs.json", 8, &dynamic(), text.as_bytes()),
+            ("version.json", 8, &FIXED, b"{\"version\": 2}")
+        ]));
+        let manifest = read_entry(&path, "META-INF/MANIFEST.MF");
+        let docs = read_entry(&path, "AllDocs.json");
+        let version = read_entry(&path, "version.json");
+        let missing = read_entry(&path, "missing.json");
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(manifest.unwrap(), "Manifest-Version: 1.0\n");
+        assert_eq!(docs.unwrap(), text);
+        assert_eq!(version.unwrap(), "{\"version\": 2}");
+        assert_eq!(missing.unwrap_err(), format!("{}: no entry named 'missing.json'", path));
+    }
+
+    #[test]
+    fn rejects_corrupt_archives() {
+        let path = write_archive("corrupt", &archive(&[("a.json", 0, b"{}", b"[]")]));
+        let corrupt = read_entry(&path, "a.json");
+        fs::write(&path, b"not an archive").unwrap();
+        let invalid = read_entry(&path, "a.json");
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(corrupt.unwrap_err(), format!("{}!a.json: entry is corrupt", path));
+        assert_eq!(invalid.unwrap_err(), format!("{}: not a zip archive", path));
+    }
+}
@@ -0,0 +1,16 @@
+use json_to_md::doc_parser::RenderOptions;
+use json_to_md::golden;
+use json_to_md::output::Newlines;
+
+// Each fixture in tests/golden is rendered and compared with the pages beside it,
+// run with UPDATE_GOLDEN=1 to accept changes to the output
+#[test]
+fn renders_the_fixtures_like_the_goldens() {
+    golden::assert_render_matches("tests/golden");
+}
+
+#[test]
+fn renders_with_the_newline_policy() {
+    let options = RenderOptions { newlines: Newlines { crlf: true, max_blank_lines: 0 }, ..RenderOptions::default() };
+    golden::assert_render_matches_with("tests/golden-crlf", &options);
+}
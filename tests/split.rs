@@ -0,0 +1,65 @@
+use std::{env, fs, process};
+use std::path::Path;
+use serde_json::Value;
+use json_to_md::doc_parser::{DocParser, Layout};
+use json_to_md::sample;
+use json_to_md::snapshot::STATE_DIR;
+use json_to_md::split;
+
+fn parser(json: &Value, link_types: bool) -> DocParser {
+    let mut parser = DocParser::from_json(json.clone());
+    parser.options.layout = Layout::Split;
+    parser.options.link_types = link_types;
+    parser
+}
+
+// (regenerated, skipped, stale, pruned)
+fn write(json: &Value, link_types: bool, prune: bool) -> (usize, usize, usize, usize) {
+    let outcome = split::write(&parser(json, link_types), Path::new("docs"), prune).unwrap();
+    (outcome.regenerated, outcome.skipped, outcome.stale, outcome.pruned)
+}
+
+fn read(page: &str) -> String {
+    fs::read_to_string(Path::new("docs").join(page)).unwrap()
+}
+
+// A single test, as the hashes of earlier runs are kept in the working directory
+#[test]
+fn only_regenerates_pages_that_would_change() {
+    let directory = env::temp_dir().join(format!("arucas-md-split-{}", process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    env::set_current_dir(&directory).unwrap();
+
+    let json = sample::generate(3);
+    assert_eq!(write(&json, false, false), (4, 0, 0, 0));
+    assert_eq!(write(&json, false, false), (0, 4, 0, 0));
+
+    // Only the class that changed
+    let mut changed = json.clone();
+    changed["classes"]["Sample1"]["desc"] = serde_json::json!(["A new description."]);
+    assert_eq!(write(&changed, false, false), (1, 3, 0, 0));
+    assert!(read("classes/Sample1.md").contains("A new description."));
+
+    // Every page when the options they are rendered with change
+    assert_eq!(write(&changed, true, false), (4, 0, 0, 0));
+
+    // Every page when a class they could link to is gone, and the page of the class is stale
+    let mut removed = changed.clone();
+    removed["classes"].as_object_mut().unwrap().remove("Sample2");
+    assert_eq!(write(&removed, true, false), (3, 0, 1, 0));
+    assert!(Path::new("docs/classes/Sample2.md").exists());
+    assert_eq!(write(&removed, true, true), (0, 3, 1, 1));
+    assert!(!Path::new("docs/classes/Sample2.md").exists());
+
+    // Adding it back gives the same pages as a run from scratch
+    assert_eq!(write(&changed, true, false), (4, 0, 0, 0));
+    let pages: Vec<String> = ["classes/Sample0.md", "classes/Sample1.md", "classes/Sample2.md"].iter().map(|page| read(page)).collect();
+    fs::remove_dir_all(STATE_DIR).unwrap();
+    assert_eq!(write(&changed, true, false), (4, 0, 0, 0));
+    for (page, expected) in ["classes/Sample0.md", "classes/Sample1.md", "classes/Sample2.md"].iter().zip(pages) {
+        assert_eq!(read(page), expected);
+    }
+
+    env::set_current_dir(env::temp_dir()).unwrap();
+    fs::remove_dir_all(&directory).unwrap();
+}
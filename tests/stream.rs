@@ -0,0 +1,59 @@
+use std::{env, fs, process};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use serde_json::Value;
+use json_to_md::sample;
+
+// Converts the input in a directory of its own, with or without '--stream'
+fn convert(directory: &Path, input: &Path, stream: bool) -> PathBuf {
+    let output = directory.join(if stream { "stream" } else { "document" });
+    fs::create_dir_all(&output).unwrap();
+    let mut command = Command::new(env!("CARGO_BIN_EXE_json_to_md"));
+    command.current_dir(&output).arg("--input").arg(input);
+    if stream {
+        command.arg("--stream");
+    }
+    let status = command.status().unwrap();
+    assert!(status.success(), "converting {} failed with {}", input.display(), status);
+    output
+}
+
+fn assert_streaming_matches(name: &str, json: &Value) {
+    let directory = env::temp_dir().join(format!("arucas-md-stream-{}-{}", process::id(), name));
+    fs::create_dir_all(&directory).unwrap();
+    // Written sorted, as streaming keeps the order of the input
+    let input = directory.join("input.json");
+    fs::write(&input, serde_json::to_string(json).unwrap()).unwrap();
+
+    let document = convert(&directory, &input, false);
+    let stream = convert(&directory, &input, true);
+    let mut pages: Vec<_> = fs::read_dir(&document).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    pages.sort();
+    assert!(!pages.is_empty());
+    for page in pages {
+        let expected = fs::read_to_string(document.join(&page)).unwrap();
+        let actual = fs::read_to_string(stream.join(&page))
+            .unwrap_or_else(|error| panic!("{:?} was not written with '--stream': {}", page, error));
+        assert_eq!(actual, expected, "{:?} differs with '--stream'", page);
+    }
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+fn fixture(name: &str) -> Value {
+    serde_json::from_str(&fs::read_to_string(format!("tests/golden/{}.json", name)).unwrap()).unwrap()
+}
+
+#[test]
+fn streaming_renders_the_scaffold_like_the_whole_document() {
+    assert_streaming_matches("scaffold", &fixture("scaffold"));
+}
+
+#[test]
+fn streaming_migrates_entries_like_the_whole_document() {
+    assert_streaming_matches("version1", &fixture("version1"));
+}
+
+#[test]
+fn streaming_renders_samples_like_the_whole_document() {
+    assert_streaming_matches("sample", &sample::generate(3));
+}
@@ -0,0 +1,48 @@
+// Loads the converter compiled to WebAssembly, build it with
+// `cargo build --release --lib --target wasm32-unknown-unknown`
+// and pass the URL or bytes of `target/wasm32-unknown-unknown/release/json_to_md.wasm`.
+//
+//   const converter = await load("json_to_md.wasm");
+//   const markdown = converter.renderClasses(json, { anchors: true });
+//
+// Options are those of the command line in camelCase, such as `linkTypes`, `groupMembers` or
+// `anchorKey`. Invalid documentation throws an Error with the problems found.
+export async function load(source) {
+    const bytes = source instanceof ArrayBuffer || ArrayBuffer.isView(source)
+        ? source
+        : await (await fetch(source)).arrayBuffer();
+    const { instance } = await WebAssembly.instantiate(bytes, {});
+    const wasm = instance.exports;
+    const encoder = new TextEncoder();
+    const decoder = new TextDecoder();
+
+    function write(text) {
+        const bytes = encoder.encode(text);
+        const pointer = wasm.alloc(bytes.length);
+        new Uint8Array(wasm.memory.buffer, pointer, bytes.length).set(bytes);
+        return [pointer, bytes.length];
+    }
+
+    function call(render, json, options) {
+        const [jsonPointer, jsonLength] = write(typeof json === "string" ? json : JSON.stringify(json));
+        const [optionsPointer, optionsLength] = write(JSON.stringify(options ?? {}));
+        const output = render(jsonPointer, jsonLength, optionsPointer, optionsLength);
+        wasm.dealloc(jsonPointer, jsonLength);
+        wasm.dealloc(optionsPointer, optionsLength);
+
+        // struct Output { ok: u32, text: *mut u8, length: usize }
+        const view = new DataView(wasm.memory.buffer, output, 12);
+        const ok = view.getUint32(0, true) === 1;
+        const text = decoder.decode(new Uint8Array(wasm.memory.buffer, view.getUint32(4, true), view.getUint32(8, true)));
+        wasm.free_output(output);
+        if (!ok) {
+            throw new Error(text);
+        }
+        return text;
+    }
+
+    return {
+        renderClasses: (json, options) => call(wasm.render_classes, json, options),
+        renderExtensions: (json, options) => call(wasm.render_extensions, json, options)
+    };
+}